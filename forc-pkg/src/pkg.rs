@@ -148,6 +148,10 @@ pub struct PkgEntry {
 pub enum PkgEntryKind {
     Main,
     Test(PkgTestEntry),
+    /// A `#[test(setup)]` fixture, executed before the tests declared within the same module.
+    TestSetup(PkgTestEntry),
+    /// A `#[bench]` function, executed repeatedly by `forc test --bench`.
+    Bench(PkgTestEntry),
 }
 
 /// The possible conditions for a test result to be considered "passing".
@@ -163,6 +167,8 @@ pub struct PkgTestEntry {
     pub pass_condition: TestPassCondition,
     pub span: Span,
     pub file_path: Arc<PathBuf>,
+    /// The maximum amount of gas the test may use, as specified by `#[test(gas_limit = N)]`.
+    pub gas_limit: Option<u64>,
 }
 
 /// The result of successfully compiling a workspace.
@@ -1779,7 +1785,7 @@ pub fn compile(
         );
         bail!("Failed to compile {}", pkg.name);
     };
-    let source = pkg.manifest_file.entry_string()?;
+    let source = engines.se().read_source(&entry_path)?;
 
     let handler = Handler::default();
 
@@ -1966,10 +1972,7 @@ impl PkgEntry {
 
     fn from_finalized_entry(finalized_entry: &FinalizedEntry, engines: &Engines) -> Result<Self> {
         let pkg_entry_kind = match &finalized_entry.test_decl_ref {
-            Some(test_decl_ref) => {
-                let pkg_test_entry = PkgTestEntry::from_decl(test_decl_ref, engines)?;
-                PkgEntryKind::Test(pkg_test_entry)
-            }
+            Some(test_decl_ref) => PkgEntryKind::from_test_decl(test_decl_ref, engines)?,
             None => PkgEntryKind::Main,
         };
 
@@ -1988,18 +1991,46 @@ impl PkgEntryKind {
             _ => None,
         }
     }
+
+    /// Returns `Some` if the `PkgEntryKind` is `TestSetup`.
+    pub fn test_setup(&self) -> Option<&PkgTestEntry> {
+        match self {
+            PkgEntryKind::TestSetup(test_setup) => Some(test_setup),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if the `PkgEntryKind` is `Bench`.
+    pub fn bench(&self) -> Option<&PkgTestEntry> {
+        match self {
+            PkgEntryKind::Bench(bench) => Some(bench),
+            _ => None,
+        }
+    }
 }
 
-impl PkgTestEntry {
-    fn from_decl(decl_ref: &DeclRefFunction, engines: &Engines) -> Result<Self> {
+impl PkgEntryKind {
+    /// Returns the kind of the entry declared by a `#[test]` or `#[bench]` function, along with
+    /// its test data.
+    fn from_test_decl(decl_ref: &DeclRefFunction, engines: &Engines) -> Result<Self> {
         let span = decl_ref.span();
         let test_function_decl = engines.de().get_function(decl_ref);
 
         const FAILING_TEST_KEYWORD: &str = "should_revert";
-
+        const TEST_SETUP_KEYWORD: &str = "setup";
+        const GAS_LIMIT_KEYWORD: &str = "gas_limit";
+
+        // Benchmarks only support limiting their gas.
+        let (attribute_kind, valid_args) = match test_function_decl.is_bench() {
+            true => (AttributeKind::Bench, &[GAS_LIMIT_KEYWORD][..]),
+            false => (
+                AttributeKind::Test,
+                &[FAILING_TEST_KEYWORD, TEST_SETUP_KEYWORD, GAS_LIMIT_KEYWORD][..],
+            ),
+        };
         let test_args: HashMap<String, Option<String>> = test_function_decl
             .attributes
-            .get(&AttributeKind::Test)
+            .get(&attribute_kind)
             .expect("test declaration is missing test attribute")
             .iter()
             .flat_map(|attr| attr.args.iter())
@@ -2013,21 +2044,29 @@ impl PkgTestEntry {
             })
             .collect();
 
-        let pass_condition = if test_args.is_empty() {
-            anyhow::Ok(TestPassCondition::ShouldNotRevert)
-        } else if let Some(args) = test_args.get(FAILING_TEST_KEYWORD) {
-            let expected_revert_code = args
-                .as_ref()
-                .map(|arg| {
-                    let arg_str = arg.replace('"', "");
-                    arg_str.parse::<u64>()
-                })
-                .transpose()?;
-            anyhow::Ok(TestPassCondition::ShouldRevert(expected_revert_code))
-        } else {
-            let test_name = &test_function_decl.name;
+        let test_name = &test_function_decl.name;
+        let is_setup = test_args.contains_key(TEST_SETUP_KEYWORD);
+        if test_args
+            .keys()
+            .any(|arg| !valid_args.contains(&arg.as_str()))
+            || (is_setup && test_args.contains_key(FAILING_TEST_KEYWORD))
+        {
             bail!("Invalid test argument(s) for test: {test_name}.")
-        }?;
+        }
+
+        let parse_u64_arg = |arg: &str| arg.replace(['"', '_'], "").parse::<u64>();
+        let pass_condition = match test_args.get(FAILING_TEST_KEYWORD) {
+            Some(args) => {
+                let expected_revert_code = args.as_deref().map(parse_u64_arg).transpose()?;
+                TestPassCondition::ShouldRevert(expected_revert_code)
+            }
+            None => TestPassCondition::ShouldNotRevert,
+        };
+        let gas_limit = match test_args.get(GAS_LIMIT_KEYWORD) {
+            Some(Some(arg)) => Some(parse_u64_arg(arg)?),
+            Some(None) => bail!("Missing gas limit value for test: {test_name}."),
+            None => None,
+        };
 
         let file_path = Arc::new(
             engines.se().get_path(
@@ -2035,10 +2074,18 @@ impl PkgTestEntry {
                     .ok_or_else(|| anyhow::anyhow!("Missing span for test function"))?,
             ),
         );
-        Ok(Self {
+        let pkg_test_entry = PkgTestEntry {
             pass_condition,
             span,
             file_path,
+            gas_limit,
+        };
+        Ok(if test_function_decl.is_bench() {
+            PkgEntryKind::Bench(pkg_test_entry)
+        } else if is_setup {
+            PkgEntryKind::TestSetup(pkg_test_entry)
+        } else {
+            PkgEntryKind::Test(pkg_test_entry)
         })
     }
 }
@@ -2139,6 +2186,15 @@ fn is_contract_dependency(graph: &Graph, node: NodeIx) -> bool {
 
 /// Builds a project with given BuildOptions.
 pub fn build_with_options(build_options: &BuildOpts) -> Result<Built> {
+    build_with_options_and_engines(build_options, &Engines::default())
+}
+
+/// Builds the package or workspace according to the given build options using the given
+/// `engines`, see [build_with_engines].
+pub fn build_with_options_and_engines(
+    build_options: &BuildOpts,
+    engines: &Engines,
+) -> Result<Built> {
     let BuildOpts {
         minify,
         binary_outfile,
@@ -2187,7 +2243,7 @@ pub fn build_with_options(build_options: &BuildOpts) -> Result<Built> {
     // Build it!
     let mut built_workspace = Vec::new();
     let build_start = std::time::Instant::now();
-    let built_packages = build(
+    let built_packages = build_with_engines(
         &build_plan,
         *build_target,
         &build_profile,
@@ -2195,6 +2251,7 @@ pub fn build_with_options(build_options: &BuildOpts) -> Result<Built> {
         sway_core::ExperimentalFlags {
             new_encoding: experimental.new_encoding,
         },
+        engines,
     )?;
     let output_dir = pkg.output_directory.as_ref().map(PathBuf::from);
     let total_size = built_packages
@@ -2306,6 +2363,29 @@ pub fn build(
     profile: &BuildProfile,
     outputs: &HashSet<NodeIx>,
     experimental: sway_core::ExperimentalFlags,
+) -> anyhow::Result<Vec<(NodeIx, BuiltPackage)>> {
+    build_with_engines(
+        plan,
+        target,
+        profile,
+        outputs,
+        experimental,
+        &Engines::default(),
+    )
+}
+
+/// Build an entire forc package using the given `engines`, see [build].
+///
+/// Reusing the same `engines` across builds allows the compiler to reuse the cached modules of
+/// unchanged sources. Modules affected by changes in other packages must be invalidated
+/// beforehand, see [sway_core::query_engine::QueryEngine::invalidate_typed_caches].
+pub fn build_with_engines(
+    plan: &BuildPlan,
+    target: BuildTarget,
+    profile: &BuildProfile,
+    outputs: &HashSet<NodeIx>,
+    experimental: sway_core::ExperimentalFlags,
+    engines: &Engines,
 ) -> anyhow::Result<Vec<(NodeIx, BuiltPackage)>> {
     let mut built_packages = Vec::new();
 
@@ -2314,7 +2394,6 @@ pub fn build(
         .flat_map(|output_node| plan.node_deps(*output_node))
         .collect();
 
-    let engines = Engines::default();
     let include_tests = profile.include_tests;
 
     // This is the Contract ID of the current contract being compiled.
@@ -2383,7 +2462,7 @@ pub fn build(
                 &compiled_contract_deps,
                 plan.graph(),
                 node,
-                &engines,
+                engines,
                 None,
                 experimental,
             ) {
@@ -2394,7 +2473,7 @@ pub fn build(
             let compiled_without_tests = compile(
                 &descriptor,
                 &profile,
-                &engines,
+                engines,
                 &mut dep_namespace,
                 &mut source_map,
             )?;
@@ -2447,7 +2526,7 @@ pub fn build(
             &compiled_contract_deps,
             plan.graph(),
             node,
-            &engines,
+            engines,
             contract_id_value.clone(),
             experimental,
         ) {
@@ -2467,7 +2546,7 @@ pub fn build(
         let compiled = compile(
             &descriptor,
             &profile,
-            &engines,
+            engines,
             &mut dep_namespace,
             &mut source_map,
         )?;
@@ -2500,6 +2579,7 @@ pub fn build(
         }
     }
 
+    engines.qe().commit();
     Ok(built_packages)
 }
 
@@ -2564,7 +2644,7 @@ pub fn check(
         .with_include_tests(include_tests)
         .with_lsp_mode(lsp_mode.clone());
 
-        let input = manifest.entry_string()?;
+        let input = engines.se().read_source(&manifest.entry_path())?;
         let handler = Handler::default();
         let programs_res = sway_core::compile_to_ast(
             &handler,
@@ -2600,7 +2680,7 @@ pub fn check(
                     .read(engines, |m| m.clone());
                 module.set_span(
                     Span::new(
-                        manifest.entry_string()?,
+                        engines.se().read_source(&manifest.entry_path())?,
                         0,
                         0,
                         Some(engines.se().get_source_id(&manifest.entry_path())),
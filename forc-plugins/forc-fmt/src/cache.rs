@@ -0,0 +1,111 @@
+//! A cache of the hashes of formatted files, used to skip the files that did not change since
+//! they were last formatted.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use swayfmt::Formatter;
+
+/// The name of the cache file, stored in the output directory of the project.
+const CACHE_FILE_NAME: &str = "fmt-cache.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FormatCache {
+    /// The hash of each formatted file, along with the configuration it was formatted with.
+    files: BTreeMap<PathBuf, String>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl FormatCache {
+    /// Loads the cache from the given output directory, or returns an empty cache if there is
+    /// none yet or it cannot be read.
+    pub(crate) fn load(out_dir: &Path) -> Self {
+        let path = out_dir.join(CACHE_FILE_NAME);
+        let cache = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            ..cache
+        }
+    }
+
+    /// Checks if the file is known to be formatted with the given formatter.
+    pub(crate) fn is_formatted(&self, file: &Path, content: &str, formatter: &Formatter) -> bool {
+        self.files.get(file) == Some(&hash(content, formatter))
+    }
+
+    /// Records that the file is formatted with the given formatter.
+    pub(crate) fn insert(&mut self, file: PathBuf, formatted_content: &str, formatter: &Formatter) {
+        self.files.insert(file, hash(formatted_content, formatter));
+    }
+
+    /// Writes the cache to the output directory it was loaded from.
+    pub(crate) fn save(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(out_dir) = path.parent() {
+                fs::create_dir_all(out_dir)?;
+            }
+            fs::write(path, serde_json::to_string_pretty(self)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hashes the content of a file along with the version of `forc-fmt` and the configuration of the
+/// formatter, so that changing either of them invalidates the cache.
+fn hash(content: &str, formatter: &Formatter) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    hasher.update(format!("{:?}", formatter.config));
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_is_formatted() {
+        let formatter = Formatter::default();
+        let file = PathBuf::from("src/main.sw");
+        let mut cache = FormatCache::default();
+        assert!(!cache.is_formatted(&file, "library;\n", &formatter));
+
+        cache.insert(file.clone(), "library;\n", &formatter);
+        assert!(cache.is_formatted(&file, "library;\n", &formatter));
+        assert!(!cache.is_formatted(&file, "library ;\n", &formatter));
+        assert!(!cache.is_formatted(Path::new("src/lib.sw"), "library;\n", &formatter));
+
+        // Changing the configuration invalidates the cache.
+        let mut other_formatter = Formatter::default();
+        other_formatter.config.whitespace.max_width += 1;
+        assert!(!cache.is_formatted(&file, "library;\n", &other_formatter));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempdir().unwrap();
+        let out_dir = dir.path().join("out");
+        let formatter = Formatter::default();
+        let file = PathBuf::from("src/main.sw");
+
+        let mut cache = FormatCache::load(&out_dir);
+        cache.insert(file.clone(), "library;\n", &formatter);
+        cache.save().unwrap();
+        assert!(FormatCache::load(&out_dir).is_formatted(&file, "library;\n", &formatter));
+
+        // A cache that cannot be read is empty.
+        fs::write(out_dir.join(CACHE_FILE_NAME), "not json").unwrap();
+        assert!(!FormatCache::load(&out_dir).is_formatted(&file, "library;\n", &formatter));
+    }
+}
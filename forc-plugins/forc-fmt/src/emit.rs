@@ -0,0 +1,165 @@
+//! The ways formatted files can be emitted, see [EmitMode].
+
+use prettydiff::{basic::DiffOp, diff_lines};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Where and how to emit the formatted files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum EmitMode {
+    /// Write the formatted files in place and print the paths of the files that changed.
+    Files,
+    /// Print the diff between each file and its formatted version.
+    Diff,
+    /// Print the text edits to apply to each file to format it, as JSON.
+    Json,
+    /// Print the formatted file, which must be the only file to format.
+    Stdout,
+}
+
+/// The text edits to apply to a file to format it.
+#[derive(Debug, Serialize)]
+pub(crate) struct FileEdits {
+    pub(crate) file: PathBuf,
+    pub(crate) edits: Vec<TextEdit>,
+}
+
+/// A replacement of a range of lines of a file, in the shape of an LSP `TextEdit`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TextEdit {
+    pub(crate) range: Range,
+    pub(crate) new_text: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct Range {
+    pub(crate) start: Position,
+    pub(crate) end: Position,
+}
+
+/// A zero-based position in a file.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct Position {
+    pub(crate) line: usize,
+    pub(crate) character: usize,
+}
+
+impl Position {
+    /// The position of the end of the given lines of a file, i.e. the start of the line following
+    /// them, or the end of the last line of a file not ending with a newline.
+    fn end_of_lines(lines: &[&str], end: usize) -> Self {
+        match lines[..end].last() {
+            Some(last) if end == lines.len() && !last.ends_with('\n') => Self {
+                line: end - 1,
+                character: last.encode_utf16().count(),
+            },
+            _ => Self {
+                line: end,
+                character: 0,
+            },
+        }
+    }
+}
+
+/// Computes the edits turning the content of a file into its formatted content, replacing whole
+/// lines of the file.
+///
+/// Lines keep their own line endings, so files using CRLF line endings or not ending with a
+/// newline are edited as they are.
+pub(crate) fn text_edits(content: &str, formatted_content: &str) -> Vec<TextEdit> {
+    let lines = content.split_inclusive('\n').collect::<Vec<_>>();
+    let formatted_lines = formatted_content.split_inclusive('\n').collect::<Vec<_>>();
+    let mut edits = vec![];
+    let mut line = 0;
+    let mut formatted_line = 0;
+    let mut push_edit = |start: usize, end: usize, new_text: &[&str]| {
+        edits.push(TextEdit {
+            range: Range {
+                start: Position {
+                    line: start,
+                    character: 0,
+                },
+                end: Position::end_of_lines(&lines, end),
+            },
+            new_text: new_text.concat(),
+        })
+    };
+    for diff in diff_lines(content, formatted_content).diff() {
+        match diff {
+            DiffOp::Equal(old) => {
+                line += old.len();
+                formatted_line += old.len();
+            }
+            DiffOp::Insert(new) => {
+                let new = &formatted_lines[formatted_line..formatted_line + new.len()];
+                push_edit(line, line, new);
+                formatted_line += new.len();
+            }
+            DiffOp::Remove(old) => {
+                push_edit(line, line + old.len(), &[]);
+                line += old.len();
+            }
+            DiffOp::Replace(old, new) => {
+                let new = &formatted_lines[formatted_line..formatted_line + new.len()];
+                push_edit(line, line + old.len(), new);
+                line += old.len();
+                formatted_line += new.len();
+            }
+        }
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies the edits to the content, from the end of the file so that the ranges stay valid.
+    fn apply(content: &str, edits: &[TextEdit]) -> String {
+        let offset = |position: &Position| {
+            let line_start = content
+                .split_inclusive('\n')
+                .take(position.line)
+                .map(str::len)
+                .sum::<usize>();
+            line_start + position.character
+        };
+        let mut applied = content.to_string();
+        for edit in edits.iter().rev() {
+            applied.replace_range(
+                offset(&edit.range.start)..offset(&edit.range.end),
+                &edit.new_text,
+            );
+        }
+        applied
+    }
+
+    #[test]
+    fn text_edits_replace_changed_lines() {
+        let content = "library;\nfn  foo() {}\n\n\nfn bar() {}\n";
+        let formatted_content = "library;\n\nfn foo() {}\n\nfn bar() {}\n";
+        let edits = text_edits(content, formatted_content);
+        assert_eq!(apply(content, &edits), formatted_content);
+    }
+
+    #[test]
+    fn text_edits_keep_line_endings() {
+        let content = "library;\r\nfn  foo() {}\r\n";
+        let formatted_content = "library;\r\n\r\nfn foo() {}\r\n";
+        let edits = text_edits(content, formatted_content);
+        assert_eq!(apply(content, &edits), formatted_content);
+
+        let content = "library;\nfn  foo() {}";
+        let formatted_content = "library;\n\nfn foo() {}\n";
+        let edits = text_edits(content, formatted_content);
+        assert_eq!(
+            edits.last().unwrap().range.end,
+            Position {
+                line: 1,
+                character: 12
+            }
+        );
+        assert_eq!(apply(content, &edits), formatted_content);
+    }
+}
@@ -1,23 +1,33 @@
 //! A `forc` plugin for running the Sway code formatter.
 
+mod cache;
+mod emit;
+
 use anyhow::{bail, Result};
+use cache::FormatCache;
 use clap::Parser;
-use forc_pkg::{
-    manifest::{GenericManifestFile, ManifestFile},
-    WorkspaceManifestFile,
+use emit::{text_edits, EmitMode, FileEdits};
+use forc_pkg::manifest::{GenericManifestFile, ManifestFile};
+use forc_tracing::{
+    init_tracing_subscriber, println_error, println_green, println_red, println_warning,
+    TracingSubscriberOptions, TracingWriterMode,
 };
-use forc_tracing::{init_tracing_subscriber, println_error, println_green, println_red};
-use forc_util::fs_locking::PidFileLocking;
+use forc_util::{default_output_directory, fs_locking::PidFileLocking};
 use prettydiff::{basic::DiffOp, diff_lines};
+use rayon::prelude::*;
 use std::{
+    collections::{hash_map::Entry, BTreeSet, HashMap},
     default::Default,
-    fs,
+    ffi::OsStr,
+    fs, io,
     path::{Path, PathBuf},
     sync::Arc,
 };
 use sway_core::{BuildConfig, BuildTarget};
-use sway_utils::{constants, find_parent_manifest_dir, get_sway_files, is_sway_file};
-use swayfmt::Formatter;
+use sway_utils::{
+    constants, find_parent_dir_with_file, find_parent_manifest_dir, get_sway_files, is_sway_file,
+};
+use swayfmt::{Formatter, FormatterWarning, SWAY_FORMAT_FILE_NAME};
 use taplo::formatter as taplo_fmt;
 use tracing::{debug, info};
 
@@ -29,6 +39,9 @@ forc_util::cli_examples! {
         [ Run formatter against a given file with short format => "forc fmt -f {path}/src/main.sw"]
         [ Run formatter against a given dir => "forc fmt --path {path}"]
         [ Run formatter against a given dir with short format => "forc fmt -p {path}"]
+        [ Run formatter against the files changed since a git revision => "forc fmt --changed-since HEAD"]
+        [ Run formatter against the files listed on the standard input => "forc fmt --stdin"]
+        [ Print the edits needed to format the current directory as JSON => "forc fmt --emit json"]
     }
 }
 
@@ -54,55 +67,207 @@ pub struct App {
     /// If not specified, current working directory will be formatted using a Forc.toml
     /// configuration.
     pub file: Option<String>,
+    /// Formats only the Sway files and manifests changed since the given git revision,
+    /// including uncommitted and untracked changes.
+    #[clap(long, conflicts_with_all = ["file", "stdin"])]
+    pub changed_since: Option<String>,
+    /// Formats only the Sway files and manifests listed on the standard input, one per line.
+    #[clap(long, conflicts_with = "file")]
+    pub stdin: bool,
+    /// Formats every file, instead of skipping the files that did not change since they were
+    /// last formatted.
+    ///
+    /// The hashes of formatted files are cached in the output directory of the project.
+    #[clap(long)]
+    pub no_cache: bool,
+    /// What to do with the formatted files.
+    ///
+    /// Defaults to writing the files in place, or to printing diffs in 'check' mode, which can't
+    /// be combined with writing files. Logs are written to stderr when emitting JSON or the
+    /// formatted file to stdout.
+    #[clap(long, value_enum)]
+    pub emit: Option<EmitMode>,
+}
+
+impl App {
+    fn emit_mode(&self) -> EmitMode {
+        match self.emit {
+            Some(emit) => emit,
+            None if self.check => EmitMode::Diff,
+            None => EmitMode::Files,
+        }
+    }
 }
 
 fn main() {
-    init_tracing_subscriber(Default::default());
-    if let Err(err) = run() {
+    let app = App::parse();
+    // The formatted files or edits printed to stdout are not to be mixed with the logs.
+    let writer_mode = matches!(app.emit_mode(), EmitMode::Json | EmitMode::Stdout)
+        .then_some(TracingWriterMode::Stderr);
+    init_tracing_subscriber(TracingSubscriberOptions {
+        writer_mode,
+        ..Default::default()
+    });
+    if let Err(err) = run(app) {
         println_error("Formatting skipped due to error.");
         println_error(&format!("{}", err));
         std::process::exit(1);
     }
 }
 
-fn run() -> Result<()> {
-    let app = App::parse();
+fn run(app: App) -> Result<()> {
+    if app.check && app.emit == Some(EmitMode::Files) {
+        bail!("`--emit files` cannot be used in 'check' mode, which never writes files");
+    }
 
     let dir = match app.path.as_ref() {
         Some(path) => PathBuf::from(path),
         None => std::env::current_dir()?,
     };
 
-    let mut formatter = Formatter::from_dir(&dir)?;
-    if let Some(f) = app.file.as_ref() {
-        let file_path = &PathBuf::from(f);
-
-        // If we're formatting a single file, find the nearest manifest if within a project.
-        // Otherwise, we simply provide 'None' to format_file().
-        let manifest_file = find_parent_manifest_dir(file_path)
-            .map(|path| path.join(constants::MANIFEST_FILE_NAME));
-
-        if is_sway_file(file_path) {
-            format_file(&app, file_path.to_path_buf(), manifest_file, &mut formatter)?;
-            return Ok(());
+    let (files, manifests) = if let Some(f) = app.file.as_ref() {
+        let file_path = PathBuf::from(f);
+        if !is_sway_file(&file_path) {
+            bail!(
+                "Provided file '{}' is not a valid Sway file",
+                file_path.display()
+            );
         }
+        (vec![file_path], vec![])
+    } else if app.stdin {
+        split_files(read_paths(io::stdin().lock())?)
+    } else if let Some(git_ref) = app.changed_since.as_ref() {
+        split_files(changed_files(&dir, git_ref)?)
+    } else {
+        project_files(&dir)?
+    };
+    if app.emit_mode() == EmitMode::Stdout && files.len() + manifests.len() > 1 {
+        bail!("`--emit stdout` can only print a single file, select it with `--file`");
+    }
 
+    // Files known to be formatted are skipped, so the cache cannot be used to print all of them.
+    let use_cache = !app.no_cache && app.emit_mode() != EmitMode::Stdout;
+    let mut cache = match find_parent_manifest_dir(&dir) {
+        Some(manifest_dir) if use_cache => {
+            Some(FormatCache::load(&default_output_directory(&manifest_dir)))
+        }
+        _ => None,
+    };
+    let mut file_edits = vec![];
+    let (mut contains_edits, failed_files) =
+        format_files(&app, files, cache.as_mut(), &mut file_edits)?;
+    for manifest_file in manifests {
+        // format manifest using taplo formatter
+        contains_edits |= format_manifest(&app, manifest_file, &mut file_edits)?;
+    }
+    if let Some(cache) = &cache {
+        cache.save()?;
+    }
+    if app.emit_mode() == EmitMode::Json {
+        println!("{}", serde_json::to_string_pretty(&file_edits)?);
+    }
+    if failed_files > 0 {
         bail!(
-            "Provided file '{}' is not a valid Sway file",
-            file_path.display()
+            "{failed_files} {} could not be formatted.",
+            match failed_files {
+                1 => "file",
+                _ => "files",
+            }
         );
+    }
+
+    if app.check && contains_edits {
+        // One or more files are not formatted, exit with error
+        bail!("Files contain formatting violations.");
+    }
+
+    Ok(())
+}
+
+/// Reads the paths listed one per line, ignoring blank lines.
+fn read_paths(reader: impl io::BufRead) -> Result<Vec<PathBuf>> {
+    let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+    let paths = lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    Ok(paths)
+}
+
+/// Splits the given paths into the Sway files and the manifests to format, ignoring any other
+/// and deleted files.
+fn split_files(paths: impl IntoIterator<Item = PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let (files, manifests): (Vec<_>, Vec<_>) = paths
+        .into_iter()
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            is_sway_file(path)
+                || path.file_name() == Some(OsStr::new(constants::MANIFEST_FILE_NAME))
+        })
+        .partition(|path| is_sway_file(path));
+    (files, manifests)
+}
+
+/// Returns the files changed in the git repository containing the given directory since the given
+/// revision, including uncommitted and untracked changes.
+fn changed_files(dir: &Path, git_ref: &str) -> Result<Vec<PathBuf>> {
+    let repo = git2::Repository::discover(dir)?;
+    let Some(workdir) = repo.workdir() else {
+        bail!("Cannot find the changed files of a bare git repository");
     };
+    let tree = repo.revparse_single(git_ref)?.peel_to_tree()?;
+    let mut diff_options = git2::DiffOptions::new();
+    diff_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+    let diff = repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut diff_options))?;
+    let dir = dir.canonicalize()?;
+    let files = diff
+        .deltas()
+        .filter(|delta| delta.status() != git2::Delta::Deleted)
+        .filter_map(|delta| delta.new_file().path().map(|path| workdir.join(path)))
+        .filter(|path| path.canonicalize().is_ok_and(|path| path.starts_with(&dir)))
+        .collect();
+    Ok(files)
+}
 
-    let manifest_file = forc_pkg::manifest::ManifestFile::from_dir(&dir)?;
+/// Returns the Sway files and the manifests of the package or workspace at the given directory.
+fn project_files(dir: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let manifest_file = forc_pkg::manifest::ManifestFile::from_dir(dir)?;
+    let mut files = BTreeSet::new();
+    let mut manifests = vec![];
     match manifest_file {
         ManifestFile::Workspace(ws) => {
-            format_workspace_at_dir(&app, &ws, &dir)?;
+            // Format files at the root - we do not want to collect the files of the whole
+            // directory here, since the subdirectories are collected below.
+            if let Ok(read_dir) = fs::read_dir(ws.dir()) {
+                for entry in read_dir.filter_map(|res| res.ok()) {
+                    let path = entry.path();
+                    if is_sway_file(&path) {
+                        files.insert(path);
+                    }
+                }
+            }
+            // Format subdirectories. We do not format the members only here, since
+            // in workspaces, it is perfectly valid to have subdirectories containing Sway files,
+            // yet not be a member of the workspace.
+            for sub_dir in get_sway_dirs(ws.dir().to_path_buf()) {
+                files.extend(get_sway_files(sub_dir.clone()));
+                manifests.push(sub_dir.join(constants::MANIFEST_FILE_NAME));
+            }
+            manifests.push(ws.path().to_path_buf());
         }
-        ManifestFile::Package(_) => {
-            format_pkg_at_dir(&app, &dir, &mut formatter)?;
+        ManifestFile::Package(pkg) => {
+            let Some(pkg_dir) = pkg.path().parent() else {
+                bail!("Manifest file does not exist");
+            };
+            files.extend(get_sway_files(pkg_dir.to_path_buf()));
+            manifests.push(pkg.path().to_path_buf());
         }
     }
-    Ok(())
+    Ok((files.into_iter().collect(), manifests))
 }
 
 /// Checks if the specified file is marked as "dirty".
@@ -137,18 +302,99 @@ fn get_sway_dirs(workspace_dir: PathBuf) -> Vec<PathBuf> {
     dirs_to_format
 }
 
-/// Format a file, given its path.
+/// The result of formatting a single file.
+struct FormattedFile {
+    file: PathBuf,
+    content: Arc<str>,
+    /// The formatted content, or `None` if the file is known to be formatted from the cache.
+    formatted_content: Option<String>,
+    warnings: Vec<FormatterWarning>,
+}
+
+/// Formats the given files in parallel.
+///
+/// Each file is formatted with the `swayfmt.toml` closest to it, which is read once for all of
+/// the files sharing it. A file failing to format is reported without preventing the other files
+/// from being emitted.
+///
 /// Returns:
-/// - Ok(true) if executed successfully and formatted,
-/// - Ok(false) if executed successfully and not formatted,
-/// - Err if it fails to execute at all.
-fn format_file(
+/// - Ok((true, failed)) if executed successfully and any file was formatted,
+/// - Ok((false, failed)) if executed successfully and no file was formatted,
+/// - Err if it fails to execute at all,
+///
+/// where `failed` is the number of files that could not be formatted.
+fn format_files(
     app: &App,
+    files: Vec<PathBuf>,
+    mut cache: Option<&mut FormatCache>,
+    file_edits: &mut Vec<FileEdits>,
+) -> Result<(bool, usize)> {
+    let mut formatters: HashMap<Option<PathBuf>, Formatter> = HashMap::new();
+    let mut jobs = vec![];
+    for file in files {
+        let file = file.canonicalize()?;
+        let file_dir = file.parent().unwrap_or(&file).to_path_buf();
+        let config_dir = find_parent_dir_with_file(&file_dir, SWAY_FORMAT_FILE_NAME);
+        if let Entry::Vacant(entry) = formatters.entry(config_dir.clone()) {
+            entry.insert(Formatter::from_dir(&file_dir)?);
+        }
+        jobs.push((file, config_dir));
+    }
+
+    let cache_ref = cache.as_deref();
+    let formatted_files = jobs
+        .into_par_iter()
+        .map(|(file, config_dir)| {
+            let mut formatter = formatters[&config_dir].clone();
+            format_file(file, &mut formatter, cache_ref).map(|formatted| (formatted, config_dir))
+        })
+        .collect::<Vec<_>>();
+
+    let mut contains_edits = false;
+    let mut failed_files = 0;
+    for formatted_file in formatted_files {
+        let (formatted_file, config_dir) = match formatted_file {
+            Ok(formatted_file) => formatted_file,
+            Err(err) => {
+                println_error(&format!("{err}"));
+                failed_files += 1;
+                continue;
+            }
+        };
+        let FormattedFile {
+            file,
+            content,
+            formatted_content,
+            warnings,
+        } = formatted_file;
+        for warning in &warnings {
+            println_warning(&format!("{}: {warning}", file.display()));
+        }
+        let Some(formatted_content) = formatted_content else {
+            continue;
+        };
+        if app.emit_mode() == EmitMode::Diff && *content != formatted_content {
+            info!("File was edited by formatter: \n{:?}\n", file);
+        }
+        let edited = emit(app, &file, &content, &formatted_content, file_edits)?;
+        contains_edits |= edited;
+        // Only the files written in place are formatted from now on.
+        if !edited || app.emit_mode() == EmitMode::Files {
+            if let Some(cache) = cache.as_deref_mut() {
+                cache.insert(file, &formatted_content, &formatters[&config_dir]);
+            }
+        }
+    }
+
+    Ok((contains_edits, failed_files))
+}
+
+/// Format a file, given its path.
+fn format_file(
     file: PathBuf,
-    manifest_file: Option<PathBuf>,
     formatter: &mut Formatter,
-) -> Result<bool> {
-    let file = file.canonicalize()?;
+    cache: Option<&FormatCache>,
+) -> Result<FormattedFile> {
     if is_file_dirty(&file) {
         bail!(
             "The below file is open in an editor and contains unsaved changes.\n       \
@@ -157,97 +403,45 @@ fn format_file(
             file.display()
         );
     }
-    if let Ok(file_content) = fs::read_to_string(&file) {
-        let mut edited = false;
-        let file_content: Arc<str> = Arc::from(file_content);
-        let build_config = manifest_file.map(|f| {
-            BuildConfig::root_from_file_name_and_manifest_path(
-                file.clone(),
-                f,
-                BuildTarget::default(),
-            )
+    let Ok(file_content) = fs::read_to_string(&file) else {
+        bail!("Could not read file: {:?}", file)
+    };
+    if cache.is_some_and(|cache| cache.is_formatted(&file, &file_content, formatter)) {
+        return Ok(FormattedFile {
+            file,
+            content: Arc::from(file_content),
+            formatted_content: None,
+            warnings: vec![],
         });
-        match Formatter::format(formatter, file_content.clone(), build_config.as_ref()) {
-            Ok(formatted_content) => {
-                if app.check {
-                    if *file_content != formatted_content {
-                        info!("File was edited by formatter: \n{:?}\n", file);
-                        display_file_diff(&file_content, &formatted_content)?;
-                        edited = true;
-                    }
-                } else {
-                    write_file_formatted(&file, &formatted_content)?;
-                }
-
-                return Ok(edited);
-            }
-            Err(err) => {
-                // TODO: Support formatting for incomplete/invalid sway code.
-                // https://github.com/FuelLabs/sway/issues/5012
-                debug!("{}", err);
-                if let Some(file) = file.to_str() {
-                    bail!("Failed to compile {}\n{}", file, err);
-                } else {
-                    bail!("Failed to compile.\n{}", err);
-                }
-            }
-        }
-    }
-
-    bail!("Could not read file: {:?}", file)
-}
-
-/// Format the workspace at the given directory.
-fn format_workspace_at_dir(app: &App, workspace: &WorkspaceManifestFile, dir: &Path) -> Result<()> {
-    let mut contains_edits = false;
-    let mut formatter = Formatter::from_dir(dir)?;
-    let mut members = vec![];
-
-    for member_path in workspace.member_paths()? {
-        members.push(member_path)
     }
 
-    // Format files at the root - we do not want to start calling format_pkg_at_dir() here,
-    // since this would mean we format twice on each subdirectory.
-    if let Ok(read_dir) = fs::read_dir(dir) {
-        for entry in read_dir.filter_map(|res| res.ok()) {
-            let path = entry.path();
-            if is_sway_file(&path) {
-                format_file(
-                    app,
-                    path,
-                    Some(workspace.dir().to_path_buf()),
-                    &mut formatter,
-                )?;
+    let file_content: Arc<str> = Arc::from(file_content);
+    // If the file is within a project, provide its manifest. Otherwise, we simply provide 'None'.
+    let build_config = find_parent_manifest_dir(&file).map(|manifest_dir| {
+        BuildConfig::root_from_file_name_and_manifest_path(
+            file.clone(),
+            manifest_dir.join(constants::MANIFEST_FILE_NAME),
+            BuildTarget::default(),
+        )
+    });
+    match Formatter::format(formatter, file_content.clone(), build_config.as_ref()) {
+        Ok(formatted_content) => Ok(FormattedFile {
+            file,
+            content: file_content,
+            formatted_content: Some(formatted_content),
+            warnings: std::mem::take(&mut formatter.warnings),
+        }),
+        Err(err) => {
+            // TODO: Support formatting for incomplete/invalid sway code.
+            // https://github.com/FuelLabs/sway/issues/5012
+            debug!("{}", err);
+            if let Some(file) = file.to_str() {
+                bail!("Failed to compile {}\n{}", file, err);
+            } else {
+                bail!("Failed to compile.\n{}", err);
             }
         }
     }
-
-    // Format subdirectories. We do not call format on members directly here, since
-    // in workspaces, it is perfectly valid to have subdirectories containing Sway files,
-    // yet not be a member of the workspace.
-    for sub_dir in get_sway_dirs(dir.to_path_buf()) {
-        if sub_dir.join(constants::MANIFEST_FILE_NAME).exists() {
-            // Here, we cannot simply call Formatter::from_dir() and rely on defaults
-            // if there is no swayfmt.toml in the sub directory because we still want
-            // to use the swayfmt.toml at the workspace root (if any).
-            // In order of priority: member > workspace > default.
-            formatter = Formatter::from_dir(&sub_dir)?;
-        }
-        format_pkg_at_dir(app, &sub_dir, &mut formatter)?;
-    }
-
-    let manifest_file = dir.join(constants::MANIFEST_FILE_NAME);
-
-    // Finally, format the root manifest using taplo formatter
-    contains_edits |= format_manifest(app, manifest_file)?;
-
-    if app.check && contains_edits {
-        // One or more files are not formatted, exit with error
-        bail!("Files contain formatting violations.");
-    }
-
-    Ok(())
 }
 
 /// Format the given manifest at a path.
@@ -255,24 +449,31 @@ fn format_workspace_at_dir(app: &App, workspace: &WorkspaceManifestFile, dir: &P
 /// - Ok(true) if executed successfully and formatted,
 /// - Ok(false) if executed successfully and not formatted,
 /// - Err if it fails to execute at all.
-fn format_manifest(app: &App, manifest_file: PathBuf) -> Result<bool> {
+fn format_manifest(
+    app: &App,
+    manifest_file: PathBuf,
+    file_edits: &mut Vec<FileEdits>,
+) -> Result<bool> {
     if let Ok(manifest_content) = fs::read_to_string(&manifest_file) {
-        let mut edited = false;
         let taplo_alphabetize = taplo_fmt::Options {
             reorder_keys: true,
             ..Default::default()
         };
         let formatted_content = taplo_fmt::format(&manifest_content, taplo_alphabetize);
-        if !app.check {
-            write_file_formatted(&manifest_file, &formatted_content)?;
-        } else if formatted_content != manifest_content {
-            edited = true;
+        if app.emit_mode() == EmitMode::Diff && formatted_content != manifest_content {
             println_error(&format!(
                 "Improperly formatted manifest file: {}",
                 manifest_file.display()
             ));
-            display_file_diff(&manifest_content, &formatted_content)?;
-        } else {
+        }
+        let edited = emit(
+            app,
+            &manifest_file,
+            &manifest_content,
+            &formatted_content,
+            file_edits,
+        )?;
+        if !edited {
             info!(
                 "Manifest Forc.toml formatted correctly: {}",
                 manifest_file.display()
@@ -285,30 +486,43 @@ fn format_manifest(app: &App, manifest_file: PathBuf) -> Result<bool> {
     bail!("failed to format manifest: {:?}", manifest_file)
 }
 
-/// Format the package at the given directory.
-fn format_pkg_at_dir(app: &App, dir: &Path, formatter: &mut Formatter) -> Result<()> {
-    match find_parent_manifest_dir(dir) {
-        Some(path) => {
-            let manifest_path = path.clone();
-            let manifest_file = manifest_path.join(constants::MANIFEST_FILE_NAME);
-            let files = get_sway_files(path);
-            let mut contains_edits = false;
-
-            for file in files {
-                contains_edits |= format_file(app, file, Some(manifest_file.clone()), formatter)?;
+/// Emits the formatted content of a file according to the emit mode.
+///
+/// Returns whether the formatted content differs from the content of the file.
+fn emit(
+    app: &App,
+    file: &Path,
+    content: &str,
+    formatted_content: &str,
+    file_edits: &mut Vec<FileEdits>,
+) -> Result<bool> {
+    let edited = content != formatted_content;
+    match app.emit_mode() {
+        EmitMode::Files => {
+            if edited {
+                write_file_formatted(file, formatted_content)?;
+                // Only list the written files when asked to, to keep the default output quiet.
+                if app.emit.is_some() {
+                    println!("{}", file.display());
+                }
             }
-            // format manifest using taplo formatter
-            contains_edits |= format_manifest(app, manifest_file)?;
-
-            if app.check && contains_edits {
-                // One or more files are not formatted, exit with error
-                bail!("Files contain formatting violations.");
+        }
+        EmitMode::Diff => {
+            if edited {
+                display_file_diff(content, formatted_content)?;
+            }
+        }
+        EmitMode::Json => {
+            if edited {
+                file_edits.push(FileEdits {
+                    file: file.to_path_buf(),
+                    edits: text_edits(content, formatted_content),
+                });
             }
-
-            Ok(())
         }
-        _ => bail!("Manifest file does not exist"),
+        EmitMode::Stdout => print!("{formatted_content}"),
     }
+    Ok(edited)
 }
 
 fn display_file_diff(file_content: &str, formatted_content: &str) -> Result<()> {
@@ -359,8 +573,114 @@ fn write_file_formatted(file: &Path, formatted_content: &str) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::taplo_fmt;
-    use std::default::Default;
+    use super::{changed_files, format_files, read_paths, split_files, taplo_fmt, App};
+    use std::{default::Default, fs, io::Cursor};
+    use tempfile::tempdir;
+
+    fn file_names(paths: &[std::path::PathBuf]) -> Vec<String> {
+        let mut names = paths
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_read_and_split_paths() {
+        let dir = tempdir().unwrap();
+        for file in ["main.sw", "Forc.toml", "README.md"] {
+            fs::write(dir.path().join(file), "").unwrap();
+        }
+        let path = |file: &str| dir.path().join(file).display().to_string();
+        let input = format!(
+            "{}\n\n  {}  \n{}\n{}\n",
+            path("main.sw"),
+            path("Forc.toml"),
+            path("README.md"),
+            path("deleted.sw")
+        );
+        let paths = read_paths(Cursor::new(input)).unwrap();
+        assert_eq!(paths.len(), 4);
+        // Blank lines are skipped and paths are trimmed.
+        assert_eq!(paths[1], dir.path().join("Forc.toml"));
+
+        let (files, manifests) = split_files(paths);
+        assert_eq!(file_names(&files), ["main.sw"]);
+        assert_eq!(file_names(&manifests), ["Forc.toml"]);
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let pkg_dir = root.join("pkg");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        for file in [
+            "pkg/unchanged.sw",
+            "pkg/changed.sw",
+            "pkg/deleted.sw",
+            "other.sw",
+        ] {
+            fs::write(root.join(file), "library;\n").unwrap();
+        }
+        let repo = git2::Repository::init(root).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("forc-fmt", "forc-fmt@fuel.sh").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+
+        fs::write(pkg_dir.join("changed.sw"), "library;\nfn foo() {}\n").unwrap();
+        fs::remove_file(pkg_dir.join("deleted.sw")).unwrap();
+        fs::write(pkg_dir.join("untracked.sw"), "library;\n").unwrap();
+        // Changes outside of the given directory are ignored.
+        fs::write(root.join("other.sw"), "library;\nfn foo() {}\n").unwrap();
+
+        let files = changed_files(&pkg_dir, "HEAD").unwrap();
+        assert_eq!(file_names(&files), ["changed.sw", "untracked.sw"]);
+    }
+
+    #[test]
+    fn test_format_files_reports_failures() {
+        let dir = tempdir().unwrap();
+        let valid = dir.path().join("valid.sw");
+        let invalid = dir.path().join("invalid.sw");
+        fs::write(&valid, "library;\nfn  foo() {}\n").unwrap();
+        fs::write(&invalid, "library;\nfn foo( {\n").unwrap();
+        let app = App {
+            check: false,
+            path: None,
+            file: None,
+            changed_since: None,
+            stdin: false,
+            no_cache: true,
+            emit: None,
+        };
+
+        let (contains_edits, failed_files) = format_files(
+            &app,
+            vec![invalid.clone(), valid.clone()],
+            None,
+            &mut vec![],
+        )
+        .unwrap();
+        assert!(contains_edits);
+        assert_eq!(failed_files, 1);
+        // The files that could be formatted are still written.
+        assert_eq!(
+            fs::read_to_string(&valid).unwrap(),
+            "library;\nfn foo() {}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&invalid).unwrap(),
+            "library;\nfn foo( {\n"
+        );
+    }
 
     #[test]
     fn test_forc_indentation() {
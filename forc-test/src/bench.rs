@@ -0,0 +1,383 @@
+//! Benchmarks for functions decorated with the `#[bench]` attribute.
+//!
+//! Each benchmark is executed repeatedly, every iteration with a different input generated from a
+//! seed. The input is passed as the script data of the transaction, which benchmarks can read with
+//! `std::tx::tx_script_data::<u64>()`. The gas used and the number of instructions executed are
+//! recorded for every iteration. The results can be saved as a [BenchBaseline] so that later runs
+//! can report the change of each benchmark.
+
+use crate::{
+    execute::TestExecutor, setup::TestSetup, BuiltTests, ModuleEntries, PackageTests, TestFilter,
+    TestLimits,
+};
+use anyhow::Context;
+use forc_pkg as pkg;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use sway_types::Span;
+
+/// The number of times each benchmark is executed unless specified otherwise.
+pub const DEFAULT_BENCH_ITERATIONS: usize = 10;
+
+/// The seed of the benchmark inputs unless specified otherwise.
+pub const DEFAULT_BENCH_SEED: u64 = 0;
+
+/// How many times each benchmark is executed, and the seed of the inputs it is executed with.
+#[derive(Copy, Clone, Debug)]
+pub struct BenchRuns {
+    /// The number of times each benchmark is executed.
+    pub iterations: usize,
+    /// The seed of the inputs of the iterations. The same seed yields the same inputs.
+    pub seed: u64,
+}
+
+/// The result of benchmarking a package or a workspace.
+#[derive(Debug)]
+pub struct Benched {
+    pub packages: Vec<BenchedPackage>,
+}
+
+/// The result of benchmarking a specific package.
+#[derive(Debug)]
+pub struct BenchedPackage {
+    /// The name of the package.
+    pub name: String,
+    pub benches: Vec<BenchResult>,
+}
+
+/// The result of executing a single benchmark repeatedly.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// The name of the function.
+    pub name: String,
+    /// The span for the function declaring this benchmark.
+    pub span: Span,
+    /// The file path for the function declaring this benchmark.
+    pub file_path: Arc<PathBuf>,
+    /// The number of times the benchmark was executed.
+    pub iterations: usize,
+    /// The gas used by each iteration.
+    pub gas: BenchStats,
+    /// The number of instructions executed by each iteration.
+    pub instructions: BenchStats,
+    /// Set if any iteration reverted or was aborted, describing the first such failure.
+    pub failure: Option<String>,
+}
+
+/// The minimum, median and maximum of a measurement across the iterations of a benchmark.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct BenchStats {
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+}
+
+/// The median measurements of each benchmark, saved to be compared against by later runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BenchBaseline {
+    /// The baseline of each benchmark, by package name and benchmark name.
+    pub packages: BTreeMap<String, BTreeMap<String, BaselineEntry>>,
+}
+
+/// The median measurements of a single benchmark within a [BenchBaseline].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub gas: u64,
+    pub instructions: u64,
+}
+
+/// The change of a benchmark's median measurements compared to its baseline.
+#[derive(Copy, Clone, Debug)]
+pub struct BenchDelta {
+    pub gas: Delta,
+    pub instructions: Delta,
+}
+
+/// The change of a single measurement compared to its baseline.
+#[derive(Copy, Clone, Debug)]
+pub struct Delta {
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl Default for BenchRuns {
+    fn default() -> Self {
+        Self {
+            iterations: DEFAULT_BENCH_ITERATIONS,
+            seed: DEFAULT_BENCH_SEED,
+        }
+    }
+}
+
+impl BenchRuns {
+    /// The input of each iteration, generated from the seed.
+    pub fn inputs(&self) -> Vec<u64> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        (0..self.iterations).map(|_| rng.gen()).collect()
+    }
+}
+
+impl BuiltTests {
+    /// Run all benchmarks matching the filter once per input of the given runs, and collect their
+    /// statistics.
+    ///
+    /// Benchmarks are run on top of the `#[test(setup)]` fixtures of the module declaring them.
+    pub fn bench(
+        self,
+        test_filter: Option<TestFilter>,
+        runs: BenchRuns,
+        limits: TestLimits,
+    ) -> anyhow::Result<Benched> {
+        let pkgs = match self {
+            BuiltTests::Package(pkg) => vec![pkg],
+            BuiltTests::Workspace(workspace) => workspace,
+        };
+        let inputs = runs.inputs();
+        let packages = pkgs
+            .iter()
+            .map(|pkg| pkg.run_benches(test_filter.as_ref(), &inputs, &limits))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Benched { packages })
+    }
+}
+
+impl PackageTests {
+    /// Run all benchmarks of this package after applying the provided filter.
+    fn run_benches(
+        &self,
+        test_filter: Option<&TestFilter>,
+        inputs: &[u64],
+        limits: &TestLimits,
+    ) -> anyhow::Result<BenchedPackage> {
+        let pkg_with_tests = self.built_pkg_with_tests();
+        let bytecode = &pkg_with_tests.bytecode.bytes;
+        let modules = ModuleEntries::collect_with(
+            &pkg_with_tests.bytecode.entries,
+            test_filter,
+            pkg::PkgEntryKind::bench,
+        );
+        let test_setup = self.setup()?;
+        let mut benches = vec![];
+        for module in &modules {
+            let module_setup = module.setup(bytecode, &test_setup, limits)?;
+            for (entry, bench_entry) in &module.tests {
+                let bench = run_bench(bytecode, entry, bench_entry, &module_setup, inputs, limits)?;
+                benches.push(bench);
+            }
+        }
+        Ok(BenchedPackage {
+            name: pkg_with_tests.descriptor.name.clone(),
+            benches,
+        })
+    }
+}
+
+/// Execute the given benchmark once per input, counting the instructions it executes.
+fn run_bench(
+    bytecode: &[u8],
+    entry: &pkg::PkgEntry,
+    bench_entry: &pkg::PkgTestEntry,
+    test_setup: &TestSetup,
+    inputs: &[u64],
+    limits: &TestLimits,
+) -> anyhow::Result<BenchResult> {
+    let offset = u32::try_from(entry.finalized.imm).expect("bench instruction offset out of range");
+    let name = entry.finalized.fn_name.clone();
+    let mut gas = Vec::with_capacity(inputs.len());
+    let mut instructions = Vec::with_capacity(inputs.len());
+    let mut failure = None;
+    for input in inputs {
+        let mut executor = TestExecutor::build_with_script_data(
+            bytecode,
+            offset,
+            test_setup.clone(),
+            bench_entry,
+            name.clone(),
+            limits,
+            input.to_be_bytes().to_vec(),
+        )?;
+        let (result, executed) = executor.execute_counting_instructions()?;
+        if failure.is_none() {
+            failure = match (result.aborted, result.revert_code()) {
+                (Some(reason), _) => Some(format!("{reason}, input: {input}")),
+                (None, Some(revert_code)) => {
+                    Some(format!("revert code: {revert_code:x}, input: {input}"))
+                }
+                (None, None) => None,
+            };
+        }
+        gas.push(result.gas_used);
+        instructions.push(executed);
+    }
+    Ok(BenchResult {
+        name,
+        span: bench_entry.span.clone(),
+        file_path: bench_entry.file_path.clone(),
+        iterations: inputs.len(),
+        gas: BenchStats::from_samples(gas),
+        instructions: BenchStats::from_samples(instructions),
+        failure,
+    })
+}
+
+impl BenchResult {
+    /// Compare the median measurements of this benchmark against the given baseline.
+    pub fn delta(&self, baseline: &BaselineEntry) -> BenchDelta {
+        BenchDelta {
+            gas: Delta {
+                baseline: baseline.gas,
+                current: self.gas.median,
+            },
+            instructions: Delta {
+                baseline: baseline.instructions,
+                current: self.instructions.median,
+            },
+        }
+    }
+}
+
+impl BenchStats {
+    /// Compute the statistics of the given samples. Returns all zeros if there are no samples.
+    pub fn from_samples(mut samples: Vec<u64>) -> Self {
+        samples.sort_unstable();
+        let (Some(&min), Some(&max)) = (samples.first(), samples.last()) else {
+            return Self::default();
+        };
+        let mid = samples.len() / 2;
+        let median = match samples.len() % 2 {
+            0 => ((u128::from(samples[mid - 1]) + u128::from(samples[mid])) / 2) as u64,
+            _ => samples[mid],
+        };
+        Self { min, median, max }
+    }
+}
+
+impl BenchBaseline {
+    /// Construct a baseline from the median measurements of the given benchmarks.
+    pub fn from_benched(benched: &Benched) -> Self {
+        let packages = benched
+            .packages
+            .iter()
+            .map(|pkg| {
+                let benches = pkg
+                    .benches
+                    .iter()
+                    .map(|bench| {
+                        let entry = BaselineEntry {
+                            gas: bench.gas.median,
+                            instructions: bench.instructions.median,
+                        };
+                        (bench.name.clone(), entry)
+                    })
+                    .collect();
+                (pkg.name.clone(), benches)
+            })
+            .collect();
+        Self { packages }
+    }
+
+    /// Read a baseline previously written with [BenchBaseline::save].
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse baseline {}", path.display()))
+    }
+
+    /// Write this baseline to the given path as JSON.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents)
+            .with_context(|| format!("failed to write baseline {}", path.display()))
+    }
+
+    /// The baseline for the benchmark with the given name within the given package, if any.
+    pub fn get(&self, package: &str, bench: &str) -> Option<&BaselineEntry> {
+        self.packages.get(package)?.get(bench)
+    }
+}
+
+impl Delta {
+    /// The change relative to the baseline, in percent.
+    pub fn percent(&self) -> f64 {
+        match self.baseline {
+            0 => 0.0,
+            baseline => (self.current as f64 - baseline as f64) / baseline as f64 * 100.0,
+        }
+    }
+}
+
+impl fmt::Display for Delta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let change = i128::from(self.current) - i128::from(self.baseline);
+        match change {
+            0 => write!(f, "no change"),
+            _ => write!(f, "{change:+} ({:+.2}%)", self.percent()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_stats() {
+        let stats = BenchStats::from_samples(vec![7, 1, 4]);
+        assert_eq!(
+            stats,
+            BenchStats {
+                min: 1,
+                median: 4,
+                max: 7
+            }
+        );
+        let stats = BenchStats::from_samples(vec![10, 1, 4, 7]);
+        assert_eq!(
+            stats,
+            BenchStats {
+                min: 1,
+                median: 5,
+                max: 10
+            }
+        );
+        assert_eq!(BenchStats::from_samples(vec![]), BenchStats::default());
+    }
+
+    #[test]
+    fn test_bench_inputs() {
+        let runs = BenchRuns {
+            iterations: 3,
+            seed: 7,
+        };
+        let inputs = runs.inputs();
+        assert_eq!(inputs.len(), 3);
+        assert_eq!(inputs, runs.inputs());
+        assert_ne!(inputs, BenchRuns { seed: 8, ..runs }.inputs());
+    }
+
+    #[test]
+    fn test_delta_display() {
+        let increase = Delta {
+            baseline: 200,
+            current: 210,
+        };
+        assert_eq!(increase.to_string(), "+10 (+5.00%)");
+        let decrease = Delta {
+            baseline: 200,
+            current: 150,
+        };
+        assert_eq!(decrease.to_string(), "-50 (-25.00%)");
+        let unchanged = Delta {
+            baseline: 200,
+            current: 200,
+        };
+        assert_eq!(unchanged.to_string(), "no change");
+    }
+}
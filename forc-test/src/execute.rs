@@ -1,18 +1,20 @@
 use crate::maxed_consensus_params;
 use crate::setup::TestSetup;
-use crate::TestResult;
 use crate::TEST_METADATA_SEED;
+use crate::{TestAbortReason, TestLimits, TestResult};
 use forc_pkg::PkgTestEntry;
 use fuel_tx::{self as tx, output::contract::Contract, Chargeable, Finalizable};
 use fuel_vm::error::InterpreterError;
 use fuel_vm::{
     self as vm,
     checked_transaction::builder::TransactionBuilderExt,
+    fuel_asm::{PanicReason, RegId},
     interpreter::{Interpreter, NotSupportedEcal},
     prelude::{Instruction, SecretKey},
     storage::MemoryStorage,
 };
 use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
 
 use tx::Receipt;
 
@@ -20,6 +22,10 @@ use vm::interpreter::{InterpreterParams, MemoryInstance};
 use vm::state::DebugEval;
 use vm::state::ProgramState;
 
+/// The number of instructions executed between two checks of the timeout while single-stepping,
+/// so that reading the clock does not dominate the execution time.
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
 /// An interface for executing a test within a VM [Interpreter] instance.
 #[derive(Debug, Clone)]
 pub struct TestExecutor {
@@ -27,6 +33,10 @@ pub struct TestExecutor {
     pub tx: vm::checked_transaction::Ready<tx::Script>,
     pub test_entry: PkgTestEntry,
     pub name: String,
+    /// The wall-clock time after which the test execution is aborted.
+    pub timeout: Option<Duration>,
+    /// The gas limit of the script executing the test.
+    pub script_gas_limit: u64,
 }
 
 /// The result of executing a test with breakpoints enabled.
@@ -45,6 +55,48 @@ impl TestExecutor {
         test_setup: TestSetup,
         test_entry: &PkgTestEntry,
         name: String,
+    ) -> anyhow::Result<Self> {
+        Self::build_with_limits(
+            bytecode,
+            test_offset,
+            test_setup,
+            test_entry,
+            name,
+            &TestLimits::default(),
+        )
+    }
+
+    /// Build a [TestExecutor] whose execution is restricted by the given limits, in addition to
+    /// the gas limit declared by the test itself.
+    pub fn build_with_limits(
+        bytecode: &[u8],
+        test_offset: u32,
+        test_setup: TestSetup,
+        test_entry: &PkgTestEntry,
+        name: String,
+        limits: &TestLimits,
+    ) -> anyhow::Result<Self> {
+        Self::build_with_script_data(
+            bytecode,
+            test_offset,
+            test_setup,
+            test_entry,
+            name,
+            limits,
+            vec![],
+        )
+    }
+
+    /// Build a [TestExecutor] whose transaction carries the given script data, which the test can
+    /// read with `std::tx::tx_script_data`.
+    pub fn build_with_script_data(
+        bytecode: &[u8],
+        test_offset: u32,
+        test_setup: TestSetup,
+        test_entry: &PkgTestEntry,
+        name: String,
+        limits: &TestLimits,
+        script_data: Vec<u8>,
     ) -> anyhow::Result<Self> {
         let storage = test_setup.storage().clone();
 
@@ -52,7 +104,6 @@ impl TestExecutor {
         let bytecode = patch_test_bytecode(bytecode, test_offset).into_owned();
 
         // Create a transaction to execute the test function.
-        let script_input_data = vec![];
         let rng = &mut rand::rngs::StdRng::seed_from_u64(TEST_METADATA_SEED);
 
         // Prepare the transaction metadata.
@@ -68,7 +119,7 @@ impl TestExecutor {
         let block_height = (u32::MAX >> 1).into();
         let gas_price = 0;
 
-        let mut tx_builder = tx::TransactionBuilder::script(bytecode, script_input_data);
+        let mut tx_builder = tx::TransactionBuilder::script(bytecode, script_data);
 
         let params = maxed_consensus_params();
 
@@ -102,8 +153,13 @@ impl TestExecutor {
         // Get `max_gas` used by everything except the script execution. Add `1` because of rounding.
         let max_gas =
             tmp_tx.max_gas(consensus_params.gas_costs(), consensus_params.fee_params()) + 1;
-        // Increase `script_gas_limit` to the maximum allowed value.
-        tx_builder.script_gas_limit(consensus_params.tx_params().max_gas_per_tx() - max_gas);
+        // Increase `script_gas_limit` to the maximum allowed value, unless the test or the test
+        // runner restricts it.
+        let script_gas_limit = test_entry.gas_limit.into_iter().chain(limits.max_gas).fold(
+            consensus_params.tx_params().max_gas_per_tx() - max_gas,
+            u64::min,
+        );
+        tx_builder.script_gas_limit(script_gas_limit);
 
         // We need to increase the tx size limit as the default is 110 * 1024 and for big tests
         // such as std and core this is not enough.
@@ -126,6 +182,8 @@ impl TestExecutor {
             tx,
             test_entry: test_entry.clone(),
             name,
+            timeout: limits.timeout,
+            script_gas_limit,
         })
     }
 
@@ -142,7 +200,7 @@ impl TestExecutor {
             return Ok(DebugResult::Breakpoint(breakpoint.pc()));
         }
         let duration = start.elapsed();
-        let (gas_used, logs) = Self::get_gas_and_receipts(transition.receipts().to_vec())?;
+        let (gas_used, logs, aborted) = Self::get_gas_and_receipts(transition.receipts().to_vec())?;
         let span = self.test_entry.span.clone();
         let file_path = self.test_entry.file_path.clone();
         let condition = self.test_entry.pass_condition.clone();
@@ -156,6 +214,7 @@ impl TestExecutor {
             condition,
             logs,
             gas_used,
+            aborted,
         }))
     }
 
@@ -173,7 +232,8 @@ impl TestExecutor {
             return Ok(DebugResult::Breakpoint(breakpoint.pc()));
         }
         let duration = start.elapsed();
-        let (gas_used, logs) = Self::get_gas_and_receipts(self.interpreter.receipts().to_vec())?; // TODO: calculate culumlative
+        let (gas_used, logs, aborted) =
+            Self::get_gas_and_receipts(self.interpreter.receipts().to_vec())?; // TODO: calculate culumlative
         let span = self.test_entry.span.clone();
         let file_path = self.test_entry.file_path.clone();
         let condition = self.test_entry.pass_condition.clone();
@@ -187,24 +247,64 @@ impl TestExecutor {
             condition,
             logs,
             gas_used,
+            aborted,
         }))
     }
 
     pub fn execute(&mut self) -> anyhow::Result<TestResult> {
-        let start = std::time::Instant::now();
-        let transition = self
-            .interpreter
-            .transact(self.tx.clone())
-            .map_err(|err: InterpreterError<_>| anyhow::anyhow!(err))?;
-        let state = *transition.state();
+        self.execute_inner(false).map(|(result, _)| result)
+    }
+
+    /// Execute the test one instruction at a time, and return the number of instructions executed
+    /// along with the result.
+    pub fn execute_counting_instructions(&mut self) -> anyhow::Result<(TestResult, u64)> {
+        let (result, instructions) = self.execute_inner(true)?;
+        let instructions = instructions.expect("instructions are counted when single-stepping");
+        Ok((result, instructions))
+    }
+
+    fn execute_inner(
+        &mut self,
+        count_instructions: bool,
+    ) -> anyhow::Result<(TestResult, Option<u64>)> {
+        let start = Instant::now();
+        let (state, receipts, timed_out, instructions) = match (count_instructions, self.timeout) {
+            (false, None) => {
+                let transition = self
+                    .interpreter
+                    .transact(self.tx.clone())
+                    .map_err(|err: InterpreterError<_>| anyhow::anyhow!(err))?;
+                (
+                    *transition.state(),
+                    transition.receipts().to_vec(),
+                    false,
+                    None,
+                )
+            }
+            (_, timeout) => {
+                let (state, receipts, timed_out, instructions) =
+                    self.execute_single_stepping(start, timeout)?;
+                (state, receipts, timed_out, Some(instructions))
+            }
+        };
 
         let duration = start.elapsed();
-        let (gas_used, logs) = Self::get_gas_and_receipts(transition.receipts().to_vec())?;
+        let (gas_used, logs, aborted) = if timed_out {
+            // The script never completed, so there is no `ScriptResult` receipt to read the used
+            // gas from.
+            let gas_used = self
+                .script_gas_limit
+                .saturating_sub(self.interpreter.remaining_gas());
+            let (_, logs, _) = Self::split_receipts(receipts);
+            (gas_used, logs, Some(TestAbortReason::Timeout))
+        } else {
+            Self::get_gas_and_receipts(receipts)?
+        };
         let span = self.test_entry.span.clone();
         let file_path = self.test_entry.file_path.clone();
         let condition = self.test_entry.pass_condition.clone();
         let name = self.name.clone();
-        Ok(TestResult {
+        let result = TestResult {
             name,
             file_path,
             duration,
@@ -213,17 +313,106 @@ impl TestExecutor {
             condition,
             logs,
             gas_used,
-        })
+            aborted,
+        };
+        Ok((result, instructions))
     }
 
-    fn get_gas_and_receipts(receipts: Vec<Receipt>) -> anyhow::Result<(u64, Vec<Receipt>)> {
-        let gas_used = *receipts
+    /// Execute the test one instruction at a time, so that the execution can be aborted once the
+    /// timeout is exceeded, and so that the executed instructions can be counted. The timeout is
+    /// checked every [TIMEOUT_CHECK_INTERVAL] instructions.
+    ///
+    /// Returns the last program state, the receipts emitted so far, whether the test timed out and
+    /// the number of instructions executed.
+    fn execute_single_stepping(
+        &mut self,
+        start: Instant,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<(ProgramState, Vec<Receipt>, bool, u64)> {
+        self.interpreter.set_single_stepping(true);
+        let mut state = *self
+            .interpreter
+            .transact(self.tx.clone())
+            .map_err(|err: InterpreterError<_>| anyhow::anyhow!(err))?
+            .state();
+        let mut timed_out = false;
+        let mut panic = None;
+        // The VM stops before executing each instruction while single-stepping.
+        let mut instructions = 0;
+        while let ProgramState::RunProgram(DebugEval::Breakpoint(_)) = state {
+            if instructions % TIMEOUT_CHECK_INTERVAL == 0
+                && timeout.is_some_and(|timeout| start.elapsed() > timeout)
+            {
+                timed_out = true;
+                break;
+            }
+            instructions += 1;
+            state = match self.interpreter.resume() {
+                Ok(state) => state,
+                Err(err) => match err.instruction_result() {
+                    Some(instruction_panic) => {
+                        panic = Some(instruction_panic);
+                        ProgramState::Revert(0)
+                    }
+                    None => anyhow::bail!("VM failed to resume. {:?}", err),
+                },
+            };
+        }
+        self.interpreter.set_single_stepping(false);
+        // The `ScriptResult` receipt is emitted when the VM first stops, so it is replaced with one
+        // for the completed execution. Resuming the VM also returns panics as errors rather than
+        // recording them, so they are recorded like a regular execution does.
+        let mut receipts: Vec<_> = self
+            .interpreter
+            .receipts()
             .iter()
-            .find_map(|receipt| match receipt {
-                tx::Receipt::ScriptResult { gas_used, .. } => Some(gas_used),
-                _ => None,
-            })
+            .filter(|receipt| !matches!(receipt, tx::Receipt::ScriptResult { .. }))
+            .cloned()
+            .collect();
+        if !timed_out {
+            let gas_used = self
+                .script_gas_limit
+                .saturating_sub(self.interpreter.remaining_gas());
+            let result = match (panic, state) {
+                (Some(panic), _) => {
+                    let registers = self.interpreter.registers();
+                    receipts.push(Receipt::panic(
+                        tx::ContractId::zeroed(),
+                        panic,
+                        registers[RegId::PC],
+                        registers[RegId::IS],
+                    ));
+                    tx::ScriptExecutionResult::Panic
+                }
+                (None, ProgramState::Revert(_)) => tx::ScriptExecutionResult::Revert,
+                (None, _) => tx::ScriptExecutionResult::Success,
+            };
+            receipts.push(Receipt::script_result(result, gas_used));
+        }
+        Ok((state, receipts, timed_out, instructions))
+    }
+
+    fn get_gas_and_receipts(
+        receipts: Vec<Receipt>,
+    ) -> anyhow::Result<(u64, Vec<Receipt>, Option<TestAbortReason>)> {
+        let (gas_used, logs, out_of_gas) = Self::split_receipts(receipts);
+        let gas_used = gas_used
             .ok_or_else(|| anyhow::anyhow!("missing used gas information from test execution"))?;
+        let aborted = out_of_gas.then_some(TestAbortReason::OutOfGas);
+        Ok((gas_used, logs, aborted))
+    }
+
+    /// Returns the used gas, the `Log` and `LogData` receipts, and whether the VM panicked due to
+    /// running out of gas.
+    fn split_receipts(receipts: Vec<Receipt>) -> (Option<u64>, Vec<Receipt>, bool) {
+        let gas_used = receipts.iter().find_map(|receipt| match receipt {
+            tx::Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        });
+        let out_of_gas = receipts.iter().any(|receipt| match receipt {
+            tx::Receipt::Panic { reason, .. } => *reason.reason() == PanicReason::OutOfGas,
+            _ => false,
+        });
 
         // Only retain `Log` and `LogData` receipts.
         let logs = receipts
@@ -233,7 +422,7 @@ impl TestExecutor {
                     || matches!(receipt, tx::Receipt::LogData { .. })
             })
             .collect();
-        Ok((gas_used, logs))
+        (gas_used, logs, out_of_gas)
     }
 }
 
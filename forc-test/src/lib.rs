@@ -1,4 +1,6 @@
+pub mod bench;
 pub mod execute;
+pub mod report;
 pub mod setup;
 
 use crate::execute::TestExecutor;
@@ -17,9 +19,14 @@ use pkg::TestPassCondition;
 use pkg::{Built, BuiltPackage};
 use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    sync::Arc,
+};
 use sway_core::asm_generation::ProgramABI;
-use sway_core::BuildTarget;
+use sway_core::{BuildTarget, Engines};
 use sway_types::Span;
 use tx::consensus_parameters::ConsensusParametersV1;
 use tx::{ConsensusParameters, ContractParameters, ScriptParameters, TxParameters};
@@ -78,6 +85,36 @@ pub struct TestResult {
     pub logs: Vec<fuel_tx::Receipt>,
     /// Gas used while executing this test.
     pub gas_used: u64,
+    /// Set if the test execution was aborted due to exceeding one of its limits.
+    pub aborted: Option<TestAbortReason>,
+}
+
+/// The reason for aborting the execution of a test.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TestAbortReason {
+    /// The test used up all of its gas.
+    OutOfGas,
+    /// The test did not complete within the allowed wall-clock time.
+    Timeout,
+}
+
+impl std::fmt::Display for TestAbortReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestAbortReason::OutOfGas => write!(f, "out of gas"),
+            TestAbortReason::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// Limits applied to the execution of every test.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TestLimits {
+    /// The maximum amount of gas a test may use. Tests declaring a lower `gas_limit` keep their
+    /// own limit.
+    pub max_gas: Option<u64>,
+    /// The wall-clock time after which a test is aborted.
+    pub timeout: Option<std::time::Duration>,
 }
 
 const TEST_METADATA_SEED: u64 = 0x7E57u64;
@@ -332,6 +369,21 @@ impl<'a> PackageTests {
         }
     }
 
+    /// Return a mutable reference to the underlying `BuiltPackage`, see
+    /// [PackageTests::built_pkg_with_tests].
+    fn built_pkg_with_tests_mut(&mut self) -> &mut Arc<BuiltPackage> {
+        match self {
+            PackageTests::Contract(PackageWithDeploymentToTest::Contract(contract))
+            | PackageTests::Script(PackageWithDeploymentToTest::Contract(contract)) => {
+                &mut contract.pkg
+            }
+            PackageTests::Contract(PackageWithDeploymentToTest::Script(script))
+            | PackageTests::Script(PackageWithDeploymentToTest::Script(script)) => &mut script.pkg,
+            PackageTests::Predicate(predicate) => predicate,
+            PackageTests::Library(library) => library,
+        }
+    }
+
     /// Construct a `PackageTests` from `BuiltPackage`.
     fn from_built_pkg(
         built_pkg: Arc<BuiltPackage>,
@@ -369,52 +421,37 @@ impl<'a> PackageTests {
     }
 
     /// Run all tests after applying the provided filter and collect their results.
+    ///
+    /// The package is deployed once, and each module's `#[test(setup)]` fixtures are executed
+    /// once on top of that deployment. Depending on `isolation`, the tests of a module either
+    /// start from a clone of the resulting storage, or run sequentially on shared storage.
     pub(crate) fn run_tests(
         &self,
         test_runners: &rayon::ThreadPool,
         test_filter: Option<&TestFilter>,
+        isolation: TestIsolation,
+        limits: &TestLimits,
     ) -> anyhow::Result<TestedPackage> {
         let pkg_with_tests = self.built_pkg_with_tests();
+        let modules = ModuleEntries::collect(&pkg_with_tests.bytecode.entries, test_filter);
+        let test_setup = self.setup()?;
         let tests = test_runners.install(|| {
-            pkg_with_tests
-                .bytecode
-                .entries
+            modules
                 .par_iter()
-                .filter_map(|entry| {
-                    if let Some(test_entry) = entry.kind.test() {
-                        // If a test filter is specified, only the tests containing the filter phrase in
-                        // their name are going to be executed.
-                        let name = entry.finalized.fn_name.clone();
-                        if let Some(filter) = test_filter {
-                            if !filter.filter(&name) {
-                                return None;
-                            }
-                        }
-                        return Some((entry, test_entry));
-                    }
-                    None
-                })
-                .map(|(entry, test_entry)| {
-                    // Execute the test and return the result.
-                    let offset = u32::try_from(entry.finalized.imm)
-                        .expect("test instruction offset out of range");
-                    let name = entry.finalized.fn_name.clone();
-                    let test_setup = self.setup()?;
-                    TestExecutor::build(
+                .map(|module| {
+                    module.run(
                         &pkg_with_tests.bytecode.bytes,
-                        offset,
-                        test_setup,
-                        test_entry,
-                        name,
-                    )?
-                    .execute()
+                        &test_setup,
+                        isolation,
+                        limits,
+                    )
                 })
-                .collect::<anyhow::Result<_>>()
+                .collect::<anyhow::Result<Vec<_>>>()
         })?;
 
         Ok(TestedPackage {
             built: Box::new(pkg_with_tests.clone()),
-            tests,
+            tests: tests.into_iter().flatten().collect(),
         })
     }
 
@@ -439,6 +476,145 @@ impl<'a> PackageTests {
     }
 }
 
+/// The `#[test(setup)]` fixtures and tests declared within a single module.
+pub(crate) struct ModuleEntries<'a> {
+    pub(crate) setups: Vec<(&'a pkg::PkgEntry, &'a pkg::PkgTestEntry)>,
+    pub(crate) tests: Vec<(&'a pkg::PkgEntry, &'a pkg::PkgTestEntry)>,
+}
+
+impl<'a> ModuleEntries<'a> {
+    /// Group the fixtures and the tests matching the filter by the module declaring them.
+    ///
+    /// Modules without any tests left after filtering are omitted.
+    fn collect(entries: &'a [pkg::PkgEntry], test_filter: Option<&TestFilter>) -> Vec<Self> {
+        Self::collect_with(entries, test_filter, pkg::PkgEntryKind::test)
+    }
+
+    /// Group the fixtures and the entries selected by `select` that match the filter by the
+    /// module declaring them, e.g. to collect the `#[bench]` functions instead of the tests.
+    pub(crate) fn collect_with(
+        entries: &'a [pkg::PkgEntry],
+        test_filter: Option<&TestFilter>,
+        select: fn(&pkg::PkgEntryKind) -> Option<&pkg::PkgTestEntry>,
+    ) -> Vec<Self> {
+        let mut modules: BTreeMap<&Arc<PathBuf>, ModuleEntries> = BTreeMap::new();
+        for entry in entries {
+            if let Some(setup_entry) = entry.kind.test_setup() {
+                modules
+                    .entry(&setup_entry.file_path)
+                    .or_insert_with(ModuleEntries::new)
+                    .setups
+                    .push((entry, setup_entry));
+            } else if let Some(test_entry) = select(&entry.kind) {
+                // If a test filter is specified, only the tests containing the filter phrase in
+                // their name are going to be executed.
+                if let Some(filter) = test_filter {
+                    if !filter.filter(&entry.finalized.fn_name) {
+                        continue;
+                    }
+                }
+                modules
+                    .entry(&test_entry.file_path)
+                    .or_insert_with(ModuleEntries::new)
+                    .tests
+                    .push((entry, test_entry));
+            }
+        }
+        modules
+            .into_values()
+            .filter(|module| !module.tests.is_empty())
+            .collect()
+    }
+
+    fn new() -> Self {
+        Self {
+            setups: vec![],
+            tests: vec![],
+        }
+    }
+
+    /// Run the fixtures of this module on top of `test_setup`, followed by its tests.
+    fn run(
+        &self,
+        bytecode: &[u8],
+        test_setup: &TestSetup,
+        isolation: TestIsolation,
+        limits: &TestLimits,
+    ) -> anyhow::Result<Vec<TestResult>> {
+        let mut test_setup = self.setup(bytecode, test_setup, limits)?;
+
+        match isolation {
+            TestIsolation::Isolated => self
+                .tests
+                .par_iter()
+                .map(|(entry, test_entry)| {
+                    test_executor(bytecode, entry, test_entry, test_setup.clone(), limits)?
+                        .execute()
+                })
+                .collect(),
+            TestIsolation::SharedPerModule => {
+                let mut tests = self.tests.clone();
+                tests.sort_by_key(|(_, test_entry)| test_entry.span.start());
+                tests
+                    .into_iter()
+                    .map(|(entry, test_entry)| {
+                        let mut executor =
+                            test_executor(bytecode, entry, test_entry, test_setup.clone(), limits)?;
+                        let result = executor.execute()?;
+                        // A test aborted by the timeout never completed its transaction, so its
+                        // partial storage changes are not kept for the following tests.
+                        if result.aborted != Some(TestAbortReason::Timeout) {
+                            test_setup.set_storage(executor.interpreter.as_ref().clone());
+                        }
+                        Ok(result)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Run the fixtures of this module on top of `test_setup`, and return the resulting setup
+    /// for its tests.
+    pub(crate) fn setup(
+        &self,
+        bytecode: &[u8],
+        test_setup: &TestSetup,
+        limits: &TestLimits,
+    ) -> anyhow::Result<TestSetup> {
+        let mut test_setup = test_setup.clone();
+        for (entry, setup_entry) in &self.setups {
+            let mut executor =
+                test_executor(bytecode, entry, setup_entry, test_setup.clone(), limits)?;
+            let result = executor.execute()?;
+            if let Some(reason) = result.aborted {
+                anyhow::bail!("test setup function `{}` failed: {reason}", result.name);
+            }
+            if !result.passed() {
+                anyhow::bail!(
+                    "test setup function `{}` failed with state {:?}",
+                    result.name,
+                    result.state
+                );
+            }
+            test_setup.set_storage(executor.interpreter.as_ref().clone());
+        }
+        Ok(test_setup)
+    }
+}
+
+/// Build a [TestExecutor] for the given test or fixture entry.
+fn test_executor(
+    bytecode: &[u8],
+    entry: &pkg::PkgEntry,
+    test_entry: &pkg::PkgTestEntry,
+    test_setup: TestSetup,
+    limits: &TestLimits,
+) -> anyhow::Result<TestExecutor> {
+    let offset = u32::try_from(entry.finalized.imm).expect("test instruction offset out of range");
+    let name = entry.finalized.fn_name.clone();
+    TestExecutor::build_with_limits(bytecode, offset, test_setup, test_entry, name, limits)
+}
+
 impl From<TestOpts> for pkg::BuildOpts {
     fn from(val: TestOpts) -> Self {
         pkg::BuildOpts {
@@ -484,7 +660,12 @@ impl TestOpts {
 
 impl TestResult {
     /// Whether or not the test passed.
+    ///
+    /// Tests aborted due to exceeding their limits never pass.
     pub fn passed(&self) -> bool {
+        if self.aborted.is_some() {
+            return false;
+        }
         match &self.condition {
             TestPassCondition::ShouldRevert(revert_code) => match revert_code {
                 Some(revert_code) => self.state == vm::state::ProgramState::Revert(*revert_code),
@@ -527,6 +708,19 @@ impl TestResult {
     }
 }
 
+/// Controls whether tests within the same module share their storage.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TestIsolation {
+    /// Each test starts from a clone of the storage produced by deployment and the module's
+    /// `#[test(setup)]` fixtures.
+    #[default]
+    Isolated,
+    /// The tests of a module run sequentially in declaration order, each starting from the storage
+    /// left behind by the previous one. The storage changes of a test aborted by the timeout are
+    /// discarded.
+    SharedPerModule,
+}
+
 /// Used to control test runner count for forc-test. Number of runners to use can be specified using
 /// `Manual` or can be left forc-test to decide by using `Auto`.
 pub enum TestRunnerCount {
@@ -583,11 +777,32 @@ impl BuiltTests {
             })
     }
 
+    /// Removes the tests that are not declared within one of the given modules, so that only the
+    /// remaining tests are executed. `#[test(setup)]` fixtures are always retained.
+    pub fn retain_modules(&mut self, modules: &HashSet<PathBuf>) {
+        let pkgs: Vec<&mut PackageTests> = match self {
+            BuiltTests::Package(pkg) => vec![pkg],
+            BuiltTests::Workspace(workspace) => workspace.iter_mut().collect(),
+        };
+        for pkg in pkgs {
+            let built_pkg = Arc::make_mut(pkg.built_pkg_with_tests_mut());
+            built_pkg
+                .bytecode
+                .entries
+                .retain(|entry| match entry.kind.test() {
+                    Some(test) => modules.contains(&*test.file_path),
+                    None => true,
+                });
+        }
+    }
+
     /// Run all built tests, return the result.
     pub fn run(
         self,
         test_runner_count: TestRunnerCount,
         test_filter: Option<TestFilter>,
+        isolation: TestIsolation,
+        limits: TestLimits,
     ) -> anyhow::Result<Tested> {
         let test_runners = match test_runner_count {
             TestRunnerCount::Manual(runner_count) => rayon::ThreadPoolBuilder::new()
@@ -595,15 +810,23 @@ impl BuiltTests {
                 .build(),
             TestRunnerCount::Auto => rayon::ThreadPoolBuilder::new().build(),
         }?;
-        run_tests(self, &test_runners, test_filter)
+        run_tests(self, &test_runners, test_filter, isolation, &limits)
     }
 }
 
 /// First builds the package or workspace, ready for execution.
 pub fn build(opts: TestOpts) -> anyhow::Result<BuiltTests> {
+    build_with_engines(opts, &Engines::default())
+}
+
+/// Builds the package or workspace using the given `engines`, ready for execution.
+///
+/// Reusing the same `engines` allows the compiler to reuse the modules cached by previous builds,
+/// see [pkg::build_with_engines].
+pub fn build_with_engines(opts: TestOpts, engines: &Engines) -> anyhow::Result<BuiltTests> {
     let build_opts: BuildOpts = opts.into();
     let build_plan = pkg::BuildPlan::from_pkg_opts(&build_opts.pkg)?;
-    let built = pkg::build_with_options(&build_opts)?;
+    let built = pkg::build_with_options_and_engines(&build_opts, engines)?;
     BuiltTests::from_built(built, &build_plan)
 }
 
@@ -711,16 +934,19 @@ fn run_tests(
     built: BuiltTests,
     test_runners: &rayon::ThreadPool,
     test_filter: Option<TestFilter>,
+    isolation: TestIsolation,
+    limits: &TestLimits,
 ) -> anyhow::Result<Tested> {
     match built {
         BuiltTests::Package(pkg) => {
-            let tested_pkg = pkg.run_tests(test_runners, test_filter.as_ref())?;
+            let tested_pkg =
+                pkg.run_tests(test_runners, test_filter.as_ref(), isolation, limits)?;
             Ok(Tested::Package(Box::new(tested_pkg)))
         }
         BuiltTests::Workspace(workspace) => {
             let tested_pkgs = workspace
                 .into_iter()
-                .map(|pkg| pkg.run_tests(test_runners, test_filter.as_ref()))
+                .map(|pkg| pkg.run_tests(test_runners, test_filter.as_ref(), isolation, limits))
                 .collect::<anyhow::Result<Vec<TestedPackage>>>()?;
             Ok(Tested::Workspace(tested_pkgs))
         }
@@ -729,9 +955,12 @@ fn run_tests(
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{collections::HashSet, path::PathBuf};
 
-    use crate::{build, BuiltTests, TestFilter, TestOpts, TestResult};
+    use crate::{
+        bench::BenchRuns, build, BuiltTests, TestAbortReason, TestFilter, TestIsolation,
+        TestLimits, TestOpts, TestResult,
+    };
 
     /// Name of the folder containing required data for tests to run, such as an example forc
     /// project.
@@ -744,6 +973,18 @@ mod tests {
     const TEST_PREDICATE_PACKAGE_NAME: &str = "test_predicate";
     /// Name of the script package in the "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
     const TEST_SCRIPT_PACKAGE_NAME: &str = "test_script";
+    /// Name of the contract package with `#[test(setup)]` fixtures in the
+    /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
+    const TEST_CONTRACT_SETUP_PACKAGE_NAME: &str = "test_contract_setup";
+    /// Name of the library package with gas limited tests in the
+    /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
+    const TEST_LIMITS_PACKAGE_NAME: &str = "test_limits";
+    /// Name of the library package with benchmarks in the
+    /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
+    const TEST_BENCH_PACKAGE_NAME: &str = "test_bench";
+    /// Name of the library package with tests in several modules in the
+    /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
+    const TEST_MODULES_PACKAGE_NAME: &str = "test_modules";
 
     /// Build the tests in the test package with the given name located at
     /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME/TEST_LIBRARY_PACKAGE_NAME".
@@ -766,10 +1007,32 @@ mod tests {
     fn test_package_test_results(
         package_name: &str,
         test_filter: Option<TestFilter>,
+    ) -> anyhow::Result<Vec<TestResult>> {
+        test_package_test_results_with_isolation(package_name, test_filter, Default::default())
+    }
+
+    fn test_package_test_results_with_isolation(
+        package_name: &str,
+        test_filter: Option<TestFilter>,
+        isolation: TestIsolation,
+    ) -> anyhow::Result<Vec<TestResult>> {
+        test_package_test_results_with_opts(
+            package_name,
+            test_filter,
+            isolation,
+            Default::default(),
+        )
+    }
+
+    fn test_package_test_results_with_opts(
+        package_name: &str,
+        test_filter: Option<TestFilter>,
+        isolation: TestIsolation,
+        limits: TestLimits,
     ) -> anyhow::Result<Vec<TestResult>> {
         let built_tests = test_package_built_tests(package_name)?;
         let test_runner_count = crate::TestRunnerCount::Auto;
-        let tested = built_tests.run(test_runner_count, test_filter)?;
+        let tested = built_tests.run(test_runner_count, test_filter, isolation, limits)?;
         match tested {
             crate::Tested::Package(tested_pkg) => Ok(tested_pkg.tests),
             crate::Tested::Workspace(_) => {
@@ -968,4 +1231,160 @@ mod tests {
         assert_eq!(tested_predicate_test_count, 2);
         assert_eq!(tested_script_test_count, 2);
     }
+
+    #[test]
+    fn test_setup_isolated() {
+        let mut results = test_package_test_results_with_isolation(
+            TEST_CONTRACT_SETUP_PACKAGE_NAME,
+            None,
+            TestIsolation::Isolated,
+        )
+        .unwrap();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // The setup function is not reported as a test, and each test starts from its result.
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "test_counter_first");
+        assert!(results[0].passed());
+        assert_eq!(results[1].name, "test_counter_second");
+        assert!(!results[1].passed());
+    }
+
+    #[test]
+    fn test_setup_shared_per_module() {
+        let results = test_package_test_results_with_isolation(
+            TEST_CONTRACT_SETUP_PACKAGE_NAME,
+            None,
+            TestIsolation::SharedPerModule,
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.passed()));
+    }
+
+    #[test]
+    fn test_gas_limit() {
+        let test_filter = TestFilter {
+            filter_phrase: "gas",
+            exact_match: false,
+        };
+        let mut results =
+            test_package_test_results(TEST_LIMITS_PACKAGE_NAME, Some(test_filter)).unwrap();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "test_out_of_gas");
+        assert_eq!(results[0].aborted, Some(TestAbortReason::OutOfGas));
+        assert!(!results[0].passed());
+        assert_eq!(results[1].name, "test_within_gas_limit");
+        assert_eq!(results[1].aborted, None);
+        assert!(results[1].passed());
+    }
+
+    #[test]
+    fn test_max_gas() {
+        let limits = TestLimits {
+            max_gas: Some(1),
+            ..Default::default()
+        };
+        let results = test_package_test_results_with_opts(
+            TEST_LIBRARY_PACKAGE_NAME,
+            None,
+            Default::default(),
+            limits,
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|result| result.aborted == Some(TestAbortReason::OutOfGas)));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let limits = TestLimits {
+            timeout: Some(std::time::Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let mut results = test_package_test_results_with_opts(
+            TEST_LIMITS_PACKAGE_NAME,
+            None,
+            Default::default(),
+            limits,
+        )
+        .unwrap();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].name, "test_infinite_loop");
+        assert_eq!(results[0].aborted, Some(TestAbortReason::Timeout));
+        assert!(!results[0].passed());
+        assert_eq!(results[1].aborted, Some(TestAbortReason::OutOfGas));
+        assert_eq!(results[2].aborted, None);
+        assert!(results[2].passed());
+    }
+
+    #[test]
+    fn test_bench() {
+        let built_tests = test_package_built_tests(TEST_BENCH_PACKAGE_NAME).unwrap();
+        // Benchmarks are not counted, nor run, as tests.
+        assert_eq!(built_tests.test_count(None).total, 1);
+
+        let runs = BenchRuns {
+            iterations: 3,
+            ..Default::default()
+        };
+        let benched = built_tests.bench(None, runs, Default::default()).unwrap();
+        assert_eq!(benched.packages.len(), 1);
+        let mut benches = benched.packages[0].benches.clone();
+        benches.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(benches.len(), 3);
+        assert_eq!(benches[0].name, "bench_sum_input");
+        assert_eq!(benches[1].name, "bench_sum_large");
+        assert_eq!(benches[2].name, "bench_sum_small");
+        assert!(benches.iter().all(|bench| bench.iterations == 3));
+        assert!(benches.iter().all(|bench| bench.failure.is_none()));
+        // The cost of the benchmark depends on its input, which differs between iterations.
+        assert!(benches[0].gas.min < benches[0].gas.max);
+        assert_eq!(benches[1].gas.min, benches[1].gas.max);
+        assert!(benches[1].gas.median > benches[2].gas.median);
+        assert!(benches[1].instructions.median > benches[2].instructions.median);
+        assert!(benches[2].instructions.min > 0);
+    }
+
+    #[test]
+    fn test_retain_modules() {
+        let mut built_tests = test_package_built_tests(TEST_MODULES_PACKAGE_NAME).unwrap();
+        let src_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join(TEST_DATA_FOLDER_NAME)
+            .join(TEST_MODULES_PACKAGE_NAME)
+            .join("src");
+        built_tests.retain_modules(&HashSet::from([
+            src_dir.join("foo.sw"),
+            src_dir.join("bar.sw"),
+        ]));
+        assert_eq!(built_tests.test_count(None).total, 2);
+
+        let tested = built_tests
+            .run(
+                crate::TestRunnerCount::Auto,
+                None,
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        let crate::Tested::Package(tested_pkg) = tested else {
+            unreachable!("test_modules is a package, not a workspace.")
+        };
+        let mut names = tested_pkg
+            .tests
+            .iter()
+            .map(|test| test.name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["test_bar", "test_foo"]);
+    }
 }
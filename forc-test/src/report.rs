@@ -0,0 +1,310 @@
+//! Machine-readable reports for the result of a `forc test` invocation.
+//!
+//! Reports are built from a [Tested] value and can be rendered either as JSON or as JUnit XML so
+//! that CI systems can display test results and track flaky or slow tests.
+
+use crate::{decode_log_data, TestResult, Tested, TestedPackage};
+use fuel_tx::Receipt;
+use serde::Serialize;
+use std::{fmt::Write, path::PathBuf};
+
+/// The set of formats a [TestReport] can be rendered into.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TestReportFormat {
+    Json,
+    Junit,
+}
+
+/// A machine-readable report of all tested packages.
+#[derive(Debug, Serialize)]
+pub struct TestReport {
+    pub packages: Vec<PackageReport>,
+}
+
+/// The report for a single tested package.
+#[derive(Debug, Serialize)]
+pub struct PackageReport {
+    /// The name of the package.
+    pub name: String,
+    pub passed: usize,
+    pub failed: usize,
+    /// The sum of the durations of all tests within this package, in nanoseconds.
+    pub duration_ns: u128,
+    pub tests: Vec<TestCaseReport>,
+}
+
+/// The report for a single test within a package.
+#[derive(Debug, Serialize)]
+pub struct TestCaseReport {
+    /// The name of the test function.
+    pub name: String,
+    /// The file that contains the test function.
+    pub file_path: PathBuf,
+    /// The line number for the test declaration.
+    pub line_number: usize,
+    pub passed: bool,
+    /// Set to "out of gas" or "timeout" if the test was aborted due to exceeding its limits.
+    pub aborted: Option<String>,
+    /// The revert code, if the test reverted.
+    pub revert_code: Option<u64>,
+    /// The decoded [fuel_abi_types::error_codes::ErrorSignal] for the revert code, if known.
+    pub error_signal: Option<String>,
+    pub gas_used: u64,
+    /// The time taken for the test to execute, in nanoseconds.
+    pub duration_ns: u128,
+    /// The `LogData` receipts emitted by the test, decoded using the package ABI.
+    pub logs: Vec<DecodedLogReport>,
+}
+
+/// A decoded `LogData` receipt.
+#[derive(Debug, Serialize)]
+pub struct DecodedLogReport {
+    /// The log id (the `rb` register) of the logged value.
+    pub log_id: String,
+    pub value: String,
+}
+
+impl TestReport {
+    /// Construct a report from the result of a `forc test` invocation.
+    pub fn from_tested(tested: &Tested) -> anyhow::Result<Self> {
+        let packages = match tested {
+            Tested::Package(pkg) => vec![PackageReport::from_tested_pkg(pkg)?],
+            Tested::Workspace(pkgs) => pkgs
+                .iter()
+                .map(PackageReport::from_tested_pkg)
+                .collect::<anyhow::Result<_>>()?,
+        };
+        Ok(Self { packages })
+    }
+
+    /// Render this report in the given format.
+    pub fn render(&self, format: TestReportFormat) -> anyhow::Result<String> {
+        match format {
+            TestReportFormat::Json => self.to_json(),
+            TestReportFormat::Junit => Ok(self.to_junit_xml()),
+        }
+    }
+
+    /// Render this report as pretty-printed JSON.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render this report as JUnit XML, with one `testsuite` per package.
+    pub fn to_junit_xml(&self) -> String {
+        let tests: usize = self.packages.iter().map(|pkg| pkg.tests.len()).sum();
+        let failures: usize = self.packages.iter().map(|pkg| pkg.failed).sum();
+        let duration_ns: u128 = self.packages.iter().map(|pkg| pkg.duration_ns).sum();
+
+        let mut xml = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            xml,
+            r#"<testsuites name="forc test" tests="{tests}" failures="{failures}" time="{}">"#,
+            secs(duration_ns),
+        );
+        for pkg in &self.packages {
+            let _ = writeln!(
+                xml,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" time="{}">"#,
+                xml_escape(&pkg.name),
+                pkg.tests.len(),
+                pkg.failed,
+                secs(pkg.duration_ns),
+            );
+            for test in &pkg.tests {
+                let _ = write!(
+                    xml,
+                    r#"    <testcase name="{}" classname="{}" file="{}" line="{}" time="{}""#,
+                    xml_escape(&test.name),
+                    xml_escape(&pkg.name),
+                    xml_escape(&test.file_path.display().to_string()),
+                    test.line_number,
+                    secs(test.duration_ns),
+                );
+                if test.passed && test.logs.is_empty() {
+                    let _ = writeln!(xml, "/>");
+                    continue;
+                }
+                let _ = writeln!(xml, ">");
+                if !test.passed {
+                    let message = match (&test.aborted, test.revert_code, &test.error_signal) {
+                        (Some(reason), _, _) => reason.clone(),
+                        (None, Some(code), Some(signal)) => {
+                            format!("revert code: {code:x} -- {signal}")
+                        }
+                        (None, Some(code), None) => format!("revert code: {code:x}"),
+                        (None, None, _) => "test did not revert as expected".to_string(),
+                    };
+                    let _ = writeln!(
+                        xml,
+                        r#"      <failure message="{}"/>"#,
+                        xml_escape(&message)
+                    );
+                }
+                let _ = writeln!(
+                    xml,
+                    r#"      <properties><property name="gas_used" value="{}"/></properties>"#,
+                    test.gas_used
+                );
+                if !test.logs.is_empty() {
+                    let logs = test
+                        .logs
+                        .iter()
+                        .map(|log| format!("{}: {}", log.log_id, log.value))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let _ = writeln!(xml, "      <system-out>{}</system-out>", xml_escape(&logs));
+                }
+                let _ = writeln!(xml, "    </testcase>");
+            }
+            let _ = writeln!(xml, "  </testsuite>");
+        }
+        let _ = writeln!(xml, "</testsuites>");
+        xml
+    }
+}
+
+impl PackageReport {
+    fn from_tested_pkg(pkg: &TestedPackage) -> anyhow::Result<Self> {
+        let tests = pkg
+            .tests
+            .iter()
+            .map(|test| TestCaseReport::from_test_result(test, pkg))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let passed = tests.iter().filter(|test| test.passed).count();
+        Ok(Self {
+            name: pkg.built.descriptor.name.clone(),
+            passed,
+            failed: tests.len() - passed,
+            duration_ns: tests.iter().map(|test| test.duration_ns).sum(),
+            tests,
+        })
+    }
+}
+
+impl TestCaseReport {
+    fn from_test_result(test: &TestResult, pkg: &TestedPackage) -> anyhow::Result<Self> {
+        let details = test.details()?;
+        let logs = test
+            .logs
+            .iter()
+            .filter_map(|log| match log {
+                Receipt::LogData {
+                    rb,
+                    data: Some(data),
+                    ..
+                } => {
+                    let log_id = rb.to_string();
+                    // Logs of types that are not part of the ABI can not be decoded, skip them.
+                    decode_log_data(&log_id, data, &pkg.built.program_abi)
+                        .ok()
+                        .map(|decoded| DecodedLogReport {
+                            log_id,
+                            value: decoded.value,
+                        })
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(Self {
+            name: test.name.clone(),
+            file_path: details.file_path.to_path_buf(),
+            line_number: details.line_number,
+            passed: test.passed(),
+            aborted: test.aborted.map(|reason| reason.to_string()),
+            revert_code: test.revert_code(),
+            error_signal: test.error_signal().ok().map(|signal| signal.to_string()),
+            gas_used: test.gas_used,
+            duration_ns: test.duration.as_nanos(),
+            logs,
+        })
+    }
+}
+
+/// Format a duration in nanoseconds as seconds, as expected by JUnit consumers.
+fn secs(duration_ns: u128) -> String {
+    format!("{:.6}", duration_ns as f64 / 1_000_000_000.0)
+}
+
+/// Escape the characters that are not allowed within XML attribute values and text.
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> TestReport {
+        TestReport {
+            packages: vec![PackageReport {
+                name: "my_pkg".to_string(),
+                passed: 1,
+                failed: 1,
+                duration_ns: 3_000,
+                tests: vec![
+                    TestCaseReport {
+                        name: "test_ok".to_string(),
+                        file_path: PathBuf::from("src/main.sw"),
+                        line_number: 3,
+                        passed: true,
+                        aborted: None,
+                        revert_code: None,
+                        error_signal: None,
+                        gas_used: 10,
+                        duration_ns: 1_000,
+                        logs: vec![],
+                    },
+                    TestCaseReport {
+                        name: "test_<fails>".to_string(),
+                        file_path: PathBuf::from("src/main.sw"),
+                        line_number: 8,
+                        passed: false,
+                        aborted: None,
+                        revert_code: Some(0xffff_ffff_ffff_0000),
+                        error_signal: None,
+                        gas_used: 20,
+                        duration_ns: 2_000,
+                        logs: vec![DecodedLogReport {
+                            log_id: "0".to_string(),
+                            value: "42".to_string(),
+                        }],
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_report_json() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json().unwrap()).unwrap();
+        let tests = &json["packages"][0]["tests"];
+        assert_eq!(json["packages"][0]["name"], "my_pkg");
+        assert_eq!(tests[0]["passed"], true);
+        assert_eq!(tests[1]["revert_code"], 0xffff_ffff_ffff_0000u64);
+        assert_eq!(tests[1]["logs"][0]["value"], "42");
+    }
+
+    #[test]
+    fn test_report_junit_xml() {
+        let xml = report().to_junit_xml();
+        assert!(xml.contains(r#"<testsuite name="my_pkg" tests="2" failures="1""#));
+        assert!(xml.contains(r#"<testcase name="test_ok" classname="my_pkg""#));
+        assert!(xml.contains(r#"name="test_&lt;fails&gt;""#));
+        assert!(xml.contains(r#"<failure message="revert code: ffffffffffff0000"/>"#));
+        assert!(xml.contains("<system-out>0: 42</system-out>"));
+    }
+}
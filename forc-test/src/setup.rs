@@ -45,6 +45,20 @@ impl TestSetup {
         }
     }
 
+    /// Replaces the storage for this test setup, e.g. with a snapshot taken after executing a
+    /// `#[test(setup)]` fixture.
+    pub fn set_storage(&mut self, storage: vm::storage::MemoryStorage) {
+        match self {
+            TestSetup::WithDeployment(DeploymentSetup::Script(script_setup)) => {
+                script_setup.storage = storage
+            }
+            TestSetup::WithDeployment(DeploymentSetup::Contract(contract_setup)) => {
+                contract_setup.storage = storage
+            }
+            TestSetup::WithoutDeployment(old_storage) => *old_storage = storage,
+        }
+    }
+
     /// Produces an iterator yielding contract ids of contract dependencies for this test setup.
     pub fn contract_dependency_ids(&self) -> impl Iterator<Item = &tx::ContractId> + '_ {
         match self {
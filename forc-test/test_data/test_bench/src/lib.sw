@@ -0,0 +1,34 @@
+library;
+
+use std::tx::tx_script_data;
+
+fn sum(n: u64) -> u64 {
+    let mut i = 0;
+    let mut total = 0;
+    while i < n {
+        total += i;
+        i += 1;
+    }
+    total
+}
+
+#[bench]
+fn bench_sum_small() {
+    assert(sum(10) == 45);
+}
+
+#[bench]
+fn bench_sum_large() {
+    assert(sum(1_000) == 499_500);
+}
+
+#[bench]
+fn bench_sum_input() {
+    let n = tx_script_data::<u64>().unwrap() % 1_000 + 1;
+    assert(sum(n) * 2 == n * (n - 1));
+}
+
+#[test]
+fn test_sum() {
+    assert(sum(4) == 6);
+}
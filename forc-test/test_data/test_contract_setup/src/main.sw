@@ -0,0 +1,37 @@
+contract;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
+
+storage {
+    counter: u64 = 0,
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        let value = storage.counter.read() + 1;
+        storage.counter.write(value);
+        value
+    }
+}
+
+#[test(setup)]
+fn setup_counter() {
+    let counter = abi(Counter, CONTRACT_ID);
+    let _ = counter.increment();
+}
+
+#[test]
+fn test_counter_first() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert_eq(counter.increment(), 2);
+}
+
+#[test]
+fn test_counter_second() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert_eq(counter.increment(), 3);
+}
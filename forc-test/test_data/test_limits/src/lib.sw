@@ -0,0 +1,22 @@
+library;
+
+#[test(gas_limit = 1_000)]
+fn test_out_of_gas() {
+    let mut i = 0;
+    while i < 1_000_000 {
+        i += 1;
+    }
+}
+
+#[test(gas_limit = 1_000)]
+fn test_within_gas_limit() {
+    assert(1 == 1)
+}
+
+#[test]
+fn test_infinite_loop() {
+    let mut i = 0;
+    while true {
+        i += 1;
+    }
+}
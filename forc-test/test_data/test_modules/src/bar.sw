@@ -0,0 +1,12 @@
+library;
+
+use ::foo::one;
+
+pub fn two() -> u64 {
+    one() + 1
+}
+
+#[test]
+fn test_bar() {
+    assert(two() == 2)
+}
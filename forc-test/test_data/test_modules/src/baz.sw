@@ -0,0 +1,6 @@
+library;
+
+#[test]
+fn test_baz() {
+    assert(true)
+}
@@ -0,0 +1,10 @@
+library;
+
+pub fn one() -> u64 {
+    1
+}
+
+#[test]
+fn test_foo() {
+    assert(one() == 1)
+}
@@ -0,0 +1,10 @@
+library;
+
+mod foo;
+mod bar;
+mod baz;
+
+#[test]
+fn test_lib() {
+    assert(foo::one() + bar::two() == 3)
+}
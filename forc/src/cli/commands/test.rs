@@ -2,19 +2,31 @@ use crate::cli;
 use ansi_term::Colour;
 use clap::Parser;
 use forc_pkg as pkg;
-use forc_test::{decode_log_data, TestFilter, TestRunnerCount, TestedPackage};
-use forc_tracing::println_action_green;
+use forc_test::{
+    bench::{BenchBaseline, BenchRuns, Benched, DEFAULT_BENCH_ITERATIONS, DEFAULT_BENCH_SEED},
+    decode_log_data,
+    report::{TestReport, TestReportFormat},
+    TestFilter, TestIsolation, TestLimits, TestRunnerCount, TestedPackage,
+};
+use forc_tracing::{println_action_green, println_action_red};
 use forc_util::{tx_utils::format_log_receipts, ForcError, ForcResult};
 use pkg::manifest::build_profile::ExperimentalFlags;
 use sway_core::fuel_prelude::fuel_tx::Receipt;
 use tracing::info;
 
+/// The exit code used when any of the tests or benchmarks failed.
+const FAILING_UNIT_TESTS_EXIT_CODE: u8 = 101;
+
 forc_util::cli_examples! {
     crate::cli::Opt {
         [ Run test => "forc test" ]
         [ Run test with a filter => "forc test $filter" ]
         [ Run test without any output => "forc test --silent" ]
         [ Run test without creating or update the lock file  => "forc test --locked" ]
+        [ Write a JUnit XML test report => "forc test --format junit --output report.xml" ]
+        [ Run test with a gas limit and a timeout for each test => "forc test --max-gas 1000000 --timeout 10" ]
+        [ Rerun the affected tests whenever the sources change => "forc test --watch" ]
+        [ Run benchmarks and compare them against a baseline => "forc test --bench --baseline bench.json" ]
     }
 }
 
@@ -51,6 +63,39 @@ pub struct Command {
     /// Number of threads to utilize when running the tests. By default, this is the number of
     /// threads available in your system.
     pub test_threads: Option<usize>,
+    #[clap(long)]
+    /// Run the tests within each module sequentially in declaration order, with each test
+    /// starting from the storage left behind by the previous one. By default, every test starts
+    /// from a fresh copy of the storage produced by the module's `#[test(setup)]` functions.
+    pub shared_state: bool,
+    #[clap(long)]
+    /// The maximum amount of gas each test may use. Tests exceeding it fail as "out of gas".
+    /// Tests declaring a lower limit with `#[test(gas_limit = N)]` keep their own limit.
+    pub max_gas: Option<u64>,
+    #[clap(long, value_name = "SECONDS")]
+    /// Abort and fail any test still running after the given number of seconds.
+    pub timeout: Option<u64>,
+    #[clap(long)]
+    /// Keep running, and rebuild and re-run the affected tests whenever a Sway source file or
+    /// manifest of the package or of its local dependencies changes.
+    pub watch: bool,
+    #[clap(long, conflicts_with = "watch")]
+    /// Run the `#[bench]` functions instead of the tests, and report the gas used and the number
+    /// of instructions executed by each of them.
+    pub bench: bool,
+    #[clap(long, requires = "bench", default_value_t = DEFAULT_BENCH_ITERATIONS)]
+    /// The number of times each benchmark is executed, each time with a different input.
+    pub bench_iterations: usize,
+    #[clap(long, requires = "bench", default_value_t = DEFAULT_BENCH_SEED)]
+    /// The seed of the inputs the benchmarks are executed with. The input of each iteration is
+    /// passed as the script data of the transaction, see `std::tx::tx_script_data`.
+    pub bench_seed: u64,
+    #[clap(long, requires = "bench", value_name = "PATH")]
+    /// Compare the benchmarks against the baseline previously saved at the given path.
+    pub baseline: Option<std::path::PathBuf>,
+    #[clap(long, requires = "bench", value_name = "PATH")]
+    /// Save the results of the benchmarks as a baseline at the given path.
+    pub save_baseline: Option<std::path::PathBuf>,
 
     /// Disable the "new encoding" feature
     #[clap(long)]
@@ -70,22 +115,119 @@ pub struct TestPrintOpts {
     /// Print the raw logs for tests.
     #[clap(long)]
     pub raw_logs: bool,
+    /// Emit a machine-readable test report in the given format.
+    ///
+    /// Unless `--output` is specified, the report is written to stdout instead of the
+    /// human-readable test output, and the logs are written to stderr.
+    #[clap(long, value_enum)]
+    pub format: Option<ReportFormat>,
+    /// The file to write the machine-readable test report to.
+    #[clap(long, requires = "format")]
+    pub output: Option<std::path::PathBuf>,
+}
+
+impl TestPrintOpts {
+    /// Whether the machine-readable test report is written to stdout.
+    pub(crate) fn report_to_stdout(&self) -> bool {
+        self.format.is_some() && self.output.is_none()
+    }
+}
+
+/// The formats supported for machine-readable test reports.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// A JSON document describing each tested package and test.
+    Json,
+    /// JUnit XML, as understood by most CI systems.
+    Junit,
+}
+
+impl From<ReportFormat> for TestReportFormat {
+    fn from(format: ReportFormat) -> Self {
+        match format {
+            ReportFormat::Json => TestReportFormat::Json,
+            ReportFormat::Junit => TestReportFormat::Junit,
+        }
+    }
 }
 
 pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
-    let test_runner_count = match cmd.test_threads {
-        Some(runner_count) => TestRunnerCount::Manual(runner_count),
-        None => TestRunnerCount::Auto,
+    let test_threads = cmd.test_threads;
+    let isolation = match cmd.shared_state {
+        true => TestIsolation::SharedPerModule,
+        false => TestIsolation::Isolated,
     };
-
+    let limits = TestLimits {
+        max_gas: cmd.max_gas,
+        timeout: cmd.timeout.map(std::time::Duration::from_secs),
+    };
+    let watch = cmd.watch;
+    let bench = cmd.bench.then(|| BenchOpts {
+        runs: BenchRuns {
+            iterations: cmd.bench_iterations,
+            seed: cmd.bench_seed,
+        },
+        baseline: cmd.baseline.clone(),
+        save_baseline: cmd.save_baseline.clone(),
+    });
     let test_print_opts = cmd.test_print.clone();
     let test_filter_phrase = cmd.filter.clone();
-    let test_filter = test_filter_phrase.as_ref().map(|filter_phrase| TestFilter {
-        filter_phrase,
-        exact_match: cmd.filter_exact,
-    });
+    let filter_exact = cmd.filter_exact;
+    let test_filter = || {
+        test_filter_phrase.as_ref().map(|filter_phrase| TestFilter {
+            filter_phrase,
+            exact_match: filter_exact,
+        })
+    };
     let opts = opts_from_cmd(cmd);
+
+    if watch {
+        crate::ops::forc_test::watch(opts, |built_tests| {
+            let tested = run_built_tests(
+                built_tests,
+                test_threads,
+                test_filter(),
+                isolation,
+                limits,
+                &test_print_opts,
+            )
+            .map_err(|err| anyhow::anyhow!("{err}"))?;
+            print_watch_summary(&tested);
+            Ok(())
+        })?;
+        return Ok(());
+    }
+
     let built_tests = forc_test::build(opts)?;
+    if let Some(bench_opts) = bench {
+        let benched = built_tests.bench(test_filter(), bench_opts.runs, limits)?;
+        return print_benched(&benched, &bench_opts);
+    }
+    let tested = run_built_tests(
+        built_tests,
+        test_threads,
+        test_filter(),
+        isolation,
+        limits,
+        &test_print_opts,
+    )?;
+    tests_passed_result(&tested)
+}
+
+/// Runs the built tests and prints their results, either as a machine-readable report or in the
+/// human-readable format.
+fn run_built_tests(
+    built_tests: forc_test::BuiltTests,
+    test_threads: Option<usize>,
+    test_filter: Option<TestFilter>,
+    isolation: TestIsolation,
+    limits: TestLimits,
+    test_print_opts: &TestPrintOpts,
+) -> ForcResult<forc_test::Tested> {
+    let test_runner_count = match test_threads {
+        Some(runner_count) => TestRunnerCount::Manual(runner_count),
+        None => TestRunnerCount::Auto,
+    };
     let start = std::time::Instant::now();
     let test_count = built_tests.test_count(test_filter.as_ref());
     let num_tests_running = test_count.total - test_count.ignored;
@@ -100,32 +242,146 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
             formatted_test_count_string(&num_tests_ignored)
         ),
     );
-    let tested = built_tests.run(test_runner_count, test_filter)?;
+    let tested = built_tests.run(test_runner_count, test_filter, isolation, limits)?;
     let duration = start.elapsed();
 
+    if let Some(format) = test_print_opts.format {
+        let report = TestReport::from_tested(&tested)?.render(format.into())?;
+        match &test_print_opts.output {
+            Some(output) => std::fs::write(output, report)?,
+            None => {
+                println!("{report}");
+                return Ok(tested);
+            }
+        }
+    }
+
     // Eventually we'll print this in a fancy manner, but this will do for testing.
-    let all_tests_passed = match tested {
+    match &tested {
         forc_test::Tested::Workspace(pkgs) => {
-            for pkg in &pkgs {
+            for pkg in pkgs {
                 let built = &pkg.built.descriptor.name;
                 info!("\ntested -- {built}\n");
-                print_tested_pkg(pkg, &test_print_opts)?;
+                print_tested_pkg(pkg, test_print_opts)?;
             }
             info!("");
             println_action_green("Finished", &format!("in {:?}", duration));
-            pkgs.iter().all(|pkg| pkg.tests_passed())
         }
-        forc_test::Tested::Package(pkg) => {
-            print_tested_pkg(&pkg, &test_print_opts)?;
-            pkg.tests_passed()
+        forc_test::Tested::Package(pkg) => print_tested_pkg(pkg, test_print_opts)?,
+    }
+
+    Ok(tested)
+}
+
+/// The options for running the `#[bench]` functions.
+struct BenchOpts {
+    runs: BenchRuns,
+    baseline: Option<std::path::PathBuf>,
+    save_baseline: Option<std::path::PathBuf>,
+}
+
+/// Prints the statistics of each benchmark along with their change compared to the baseline, and
+/// saves them as a new baseline if requested.
+fn print_benched(benched: &Benched, bench_opts: &BenchOpts) -> ForcResult<()> {
+    let baseline = bench_opts
+        .baseline
+        .as_deref()
+        .map(BenchBaseline::load)
+        .transpose()?;
+    for pkg in &benched.packages {
+        let num_benches = pkg.benches.len();
+        info!("\nbenched -- {}\n", pkg.name);
+        println_action_green(
+            "Running",
+            &format!(
+                "{num_benches} {}, {} {} each",
+                match num_benches {
+                    1 => "benchmark",
+                    _ => "benchmarks",
+                },
+                bench_opts.runs.iterations,
+                match bench_opts.runs.iterations {
+                    1 => "iteration",
+                    _ => "iterations",
+                }
+            ),
+        );
+        for bench in &pkg.benches {
+            let (state, color) = match &bench.failure {
+                Some(failure) => (format!("FAILED ({failure})"), Colour::Red),
+                None => ("ok".to_string(), Colour::Green),
+            };
+            info!("      bench {} ... {}", bench.name, color.paint(state));
+            info!(
+                "        gas: {} / {} / {}, instructions: {} / {} / {} (min / median / max)",
+                bench.gas.min,
+                bench.gas.median,
+                bench.gas.max,
+                bench.instructions.min,
+                bench.instructions.median,
+                bench.instructions.max,
+            );
+            let Some(baseline) = &baseline else {
+                continue;
+            };
+            match baseline.get(&pkg.name, &bench.name) {
+                Some(entry) => {
+                    let delta = bench.delta(entry);
+                    info!(
+                        "        change: gas {}, instructions {}",
+                        delta.gas, delta.instructions
+                    );
+                }
+                None => info!("        change: not in baseline"),
+            }
         }
+    }
+
+    if let Some(path) = &bench_opts.save_baseline {
+        BenchBaseline::from_benched(benched).save(path)?;
+        println_action_green("Saved", &format!("baseline to {}", path.display()));
+    }
+
+    let failed = benched
+        .packages
+        .iter()
+        .flat_map(|pkg| pkg.benches.iter())
+        .any(|bench| bench.failure.is_some());
+    if failed {
+        let forc_error: ForcError = "Some benchmarks failed.".into();
+        return Err(forc_error.exit_code(FAILING_UNIT_TESTS_EXIT_CODE));
+    }
+    Ok(())
+}
+
+/// Prints a single line summarizing the result of a test run in watch mode.
+fn print_watch_summary(tested: &forc_test::Tested) {
+    let pkgs = match tested {
+        forc_test::Tested::Workspace(pkgs) => pkgs.iter().collect::<Vec<_>>(),
+        forc_test::Tested::Package(pkg) => vec![&**pkg],
+    };
+    let tests = pkgs.iter().flat_map(|pkg| pkg.tests.iter());
+    let total = tests.clone().count();
+    let passed = tests.filter(|test| test.passed()).count();
+    let failed = total - passed;
+    let summary = format!("{passed} passed; {failed} failed");
+    match failed {
+        0 => println_action_green("Tested", &summary),
+        _ => println_action_red("Tested", &summary),
+    }
+}
+
+/// Returns an error with the failing unit tests exit code if any of the tests failed.
+fn tests_passed_result(tested: &forc_test::Tested) -> ForcResult<()> {
+    let all_tests_passed = match tested {
+        forc_test::Tested::Workspace(pkgs) => pkgs.iter().all(|pkg| pkg.tests_passed()),
+        forc_test::Tested::Package(pkg) => pkg.tests_passed(),
     };
 
     if all_tests_passed {
         Ok(())
     } else {
         let forc_error: ForcError = "Some tests failed.".into();
-        const FAILING_UNIT_TESTS_EXIT_CODE: u8 = 101;
         Err(forc_error.exit_code(FAILING_UNIT_TESTS_EXIT_CODE))
     }
 }
@@ -136,9 +392,10 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
     let mut failed_tests = Vec::new();
     for test in &pkg.tests {
         let test_passed = test.passed();
-        let (state, color) = match test_passed {
-            true => ("ok", Colour::Green),
-            false => ("FAILED", Colour::Red),
+        let (state, color) = match (test_passed, test.aborted) {
+            (true, _) => ("ok".to_string(), Colour::Green),
+            (false, Some(reason)) => (format!("FAILED ({reason})"), Colour::Red),
+            (false, None) => ("FAILED".to_string(), Colour::Red),
         };
         info!(
             "      test {} ... {} ({:?}, {} gas)",
@@ -193,7 +450,9 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
                 "      - test {}, {:?}:{} ",
                 failed_test_name, path, line_number
             );
-            if let Some(revert_code) = failed_test.revert_code() {
+            if let Some(reason) = failed_test.aborted {
+                info!("        aborted: {reason}");
+            } else if let Some(revert_code) = failed_test.revert_code() {
                 // If we have a revert_code, try to get a known error signal
                 let mut failed_info_str = format!("        revert code: {revert_code:x}");
                 let error_signal = failed_test.error_signal().ok();
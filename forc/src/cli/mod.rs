@@ -10,7 +10,7 @@ use clap::{Parser, Subcommand};
 pub use clean::Command as CleanCommand;
 pub use completions::Command as CompletionsCommand;
 pub(crate) use contract_id::Command as ContractIdCommand;
-use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions};
+use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
 use forc_util::ForcResult;
 pub use init::Command as InitCommand;
 pub use new::Command as NewCommand;
@@ -117,11 +117,18 @@ impl Forc {
 
 pub async fn run_cli() -> ForcResult<()> {
     let opt = Opt::parse();
+    // The test reports printed to stdout are not to be mixed with the logs.
+    let writer_mode = match &opt.command {
+        Forc::Test(command) if command.test_print.report_to_stdout() => {
+            Some(TracingWriterMode::Stderr)
+        }
+        _ => None,
+    };
     let tracing_options = TracingSubscriberOptions {
         verbosity: Some(opt.verbose),
         silent: Some(opt.silent),
         log_level: opt.log_level,
-        ..Default::default()
+        writer_mode,
     };
 
     init_tracing_subscriber(tracing_options);
@@ -0,0 +1,371 @@
+use anyhow::Result;
+use forc_pkg::{self as pkg, manifest::GenericManifestFile};
+use forc_test::{BuiltTests, TestOpts};
+use forc_tracing::{println_action_green, println_red_err};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::mpsc,
+    time::Duration,
+};
+use sway_core::{
+    language::ty::{TyAstNodeContent, TyModule, TySideEffect, TySideEffectVariant},
+    Engines,
+};
+use sway_utils::{
+    constants::{MANIFEST_FILE_NAME, SWAY_EXTENSION},
+    find_parent_manifest_dir,
+};
+
+/// The time to wait for further file system events before rebuilding.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Builds the tests and runs them with `run_tests`, then keeps rebuilding and re-running them
+/// whenever the sources of the package, or of its local dependencies, change.
+///
+/// The compiler engines are reused between builds, so that only the modules of the changed
+/// packages and of the packages depending on them are type-checked again, while the declarations
+/// and types of the changed modules are garbage-collected. After a change that is limited to
+/// member packages, only the tests within the changed modules and the modules depending on them,
+/// according to the module graph of the last build, are re-run.
+pub fn watch(opts: TestOpts, mut run_tests: impl FnMut(BuiltTests) -> Result<()>) -> Result<()> {
+    // The watcher lives for the whole session, so that changes made during a build are not missed.
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, tx)?;
+    let mut watched_dirs = HashSet::new();
+    let mut engines = Engines::default();
+    let mut affected_modules: Option<HashSet<PathBuf>> = None;
+    loop {
+        // The build plan is recreated on every iteration as the manifests may have changed.
+        let plan = match pkg::BuildPlan::from_pkg_opts(&opts.pkg) {
+            Ok(plan) => plan,
+            Err(err) => {
+                // Wait for the manifests to be fixed, watching at least the package itself.
+                println_red_err(&format!("{err}"));
+                let pkg_dir = match &opts.pkg.path {
+                    Some(path) => PathBuf::from(path),
+                    None => std::env::current_dir()?,
+                };
+                let pkg_dir = find_parent_manifest_dir(&pkg_dir).unwrap_or(pkg_dir);
+                watched_dirs = update_watched_dirs(&mut debouncer, watched_dirs, [pkg_dir])?;
+                wait_for_changes(&rx)?;
+                engines = Engines::default();
+                affected_modules = None;
+                continue;
+            }
+        };
+        let built = match forc_test::build_with_engines(opts.clone(), &engines) {
+            Ok(mut built_tests) => {
+                if let Some(modules) = &affected_modules {
+                    built_tests.retain_modules(modules);
+                }
+                if let Err(err) = run_tests(built_tests) {
+                    println_red_err(&format!("{err}"));
+                }
+                true
+            }
+            Err(err) => {
+                println_red_err(&format!("{err}"));
+                false
+            }
+        };
+
+        let local_pkgs = local_packages(&plan);
+        watched_dirs = update_watched_dirs(
+            &mut debouncer,
+            watched_dirs,
+            local_pkgs.iter().map(|(_, dir)| dir.clone()),
+        )?;
+        let changed = wait_for_changes(&rx)?;
+        if changed
+            .iter()
+            .any(|path| path.ends_with(MANIFEST_FILE_NAME))
+        {
+            // Dependencies may have changed, start over with fresh caches and all tests.
+            engines = Engines::default();
+            affected_modules = None;
+            continue;
+        }
+
+        let changed_pkgs: HashSet<pkg::NodeIx> = local_pkgs
+            .iter()
+            .filter(|(_, dir)| changed.iter().any(|path| path.starts_with(dir)))
+            .map(|(node, _)| *node)
+            .collect();
+        // Tests that could not be run due to a failed build are re-run in full. The modules
+        // depending on the changed ones are found before their typed modules are invalidated.
+        let only_members_changed = changed_pkgs
+            .iter()
+            .all(|node| plan.member_nodes().any(|member| member == *node));
+        affected_modules = (built && only_members_changed)
+            .then(|| module_dependencies(&engines, &plan))
+            .flatten()
+            .map(|dependencies| dependent_modules(&dependencies, changed.clone()));
+
+        // The changed modules are parsed again, their declarations and types are garbage.
+        let stale_modules: Vec<PathBuf> = {
+            let module_cache = engines.qe().module_cache.read();
+            changed
+                .into_iter()
+                .filter(|path| module_cache.keys().any(|key| *key.path == *path))
+                .collect()
+        };
+        for path in &stale_modules {
+            let source_id = engines.se().get_source_id(path);
+            engines.clear_module(&source_id);
+            engines.qe().remove_module_cache_entries(path);
+        }
+
+        let invalidated_dirs: Vec<&PathBuf> = local_pkgs
+            .iter()
+            .filter(|(node, _)| plan.node_deps(*node).any(|dep| changed_pkgs.contains(&dep)))
+            .map(|(_, dir)| dir)
+            .collect();
+        engines.qe().invalidate_typed_caches(|path| {
+            invalidated_dirs.iter().any(|dir| path.starts_with(dir))
+        });
+    }
+}
+
+/// Returns the node and the manifest directory of each package within the build plan that is
+/// located on the local file system, i.e. the members and path dependencies.
+fn local_packages(plan: &pkg::BuildPlan) -> Vec<(pkg::NodeIx, PathBuf)> {
+    let graph = plan.graph();
+    plan.compilation_order()
+        .iter()
+        .filter(|&&node| {
+            matches!(
+                graph[node].source,
+                pkg::source::Pinned::Member(_) | pkg::source::Pinned::Path(_)
+            )
+        })
+        .filter_map(|&node| {
+            let manifest = plan.manifest_map().get(&graph[node].id())?;
+            Some((node, manifest.dir().to_path_buf()))
+        })
+        .collect()
+}
+
+/// Watches the given directories, and stops watching the previously watched directories that are
+/// not among them. Returns the watched directories.
+fn update_watched_dirs(
+    debouncer: &mut Debouncer<RecommendedWatcher>,
+    watched_dirs: HashSet<PathBuf>,
+    dirs: impl IntoIterator<Item = PathBuf>,
+) -> Result<HashSet<PathBuf>> {
+    let dirs: HashSet<PathBuf> = dirs.into_iter().collect();
+    for dir in watched_dirs.difference(&dirs) {
+        // The directory may have been removed along with its watch.
+        let _ = debouncer.watcher().unwatch(dir);
+    }
+    for dir in dirs.difference(&watched_dirs) {
+        debouncer.watcher().watch(dir, RecursiveMode::Recursive)?;
+    }
+    Ok(dirs)
+}
+
+/// Blocks until at least one Sway source or manifest within the watched directories changes, and
+/// returns the paths of the changed files. Changes made since the last call are returned
+/// immediately.
+fn wait_for_changes(rx: &mpsc::Receiver<DebounceEventResult>) -> Result<HashSet<PathBuf>> {
+    println_action_green("Watching", "for changes...");
+    loop {
+        let Ok(events) = rx.recv()? else {
+            continue;
+        };
+        // Build artifacts are written within the watched directories, only consider sources.
+        let changed: HashSet<PathBuf> = events
+            .into_iter()
+            .map(|event| event.path.canonicalize().unwrap_or(event.path))
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == SWAY_EXTENSION)
+                    || path.ends_with(MANIFEST_FILE_NAME)
+            })
+            .collect();
+        if !changed.is_empty() {
+            return Ok(changed);
+        }
+    }
+}
+
+/// Returns the modules of the member packages along with the modules each of them depends on, i.e.
+/// its submodules and the modules it imports items from, according to the typed modules of the
+/// last build. Returns `None` if a member package was not type-checked.
+fn module_dependencies(
+    engines: &Engines,
+    plan: &pkg::BuildPlan,
+) -> Option<HashMap<PathBuf, HashSet<PathBuf>>> {
+    let module_cache = engines.qe().module_cache.read();
+    let mut dependencies = HashMap::new();
+    for node in plan.member_nodes() {
+        let entry_path = plan
+            .manifest_map()
+            .get(&plan.graph()[node].id())?
+            .entry_path();
+        let root = &module_cache
+            .values()
+            .find(|entry| entry.common.include_tests && *entry.common.path == entry_path)?
+            .typed
+            .as_ref()?
+            .module;
+        let mut modules = vec![];
+        collect_modules(root, vec![], &mut modules);
+        let module_paths: HashMap<&[String], PathBuf> = modules
+            .iter()
+            .filter_map(|(mod_path, module)| {
+                let source_id = module.span.source_id()?;
+                Some((mod_path.as_slice(), engines.se().get_path(source_id)))
+            })
+            .collect();
+        for (mod_path, module) in &modules {
+            let Some(path) = module_paths.get(mod_path.as_slice()) else {
+                continue;
+            };
+            let submodules = module.submodules.iter().filter_map(|(name, _)| {
+                let mut submodule_path = mod_path.clone();
+                submodule_path.push(name.to_string());
+                module_paths.get(submodule_path.as_slice()).cloned()
+            });
+            let imports = module
+                .all_nodes
+                .iter()
+                .filter_map(|node| match &node.content {
+                    TyAstNodeContent::SideEffect(TySideEffect {
+                        side_effect: TySideEffectVariant::UseStatement(use_statement),
+                    }) => {
+                        let call_path = use_statement
+                            .call_path
+                            .iter()
+                            .map(|ident| ident.to_string());
+                        let import_path: Vec<String> = if use_statement.is_absolute {
+                            call_path.collect()
+                        } else {
+                            mod_path.iter().cloned().chain(call_path).collect()
+                        };
+                        // The items are declared within the deepest module of the path, paths
+                        // that do not start with a module of the package are external.
+                        (1..=import_path.len())
+                            .rev()
+                            .find_map(|len| module_paths.get(&import_path[..len]))
+                            .cloned()
+                    }
+                    _ => None,
+                });
+            dependencies
+                .entry(path.clone())
+                .or_insert_with(HashSet::new)
+                .extend(submodules.chain(imports).filter(|dep| dep != path));
+        }
+    }
+    Some(dependencies)
+}
+
+/// Collects the given typed module and all of its submodules, along with their module paths
+/// relative to the root module of the package.
+fn collect_modules<'a>(
+    module: &'a TyModule,
+    mod_path: Vec<String>,
+    modules: &mut Vec<(Vec<String>, &'a TyModule)>,
+) {
+    for (name, submodule) in &module.submodules {
+        let mut submodule_path = mod_path.clone();
+        submodule_path.push(name.to_string());
+        collect_modules(&submodule.module, submodule_path, modules);
+    }
+    modules.push((mod_path, module));
+}
+
+/// Returns the given modules along with all of the modules that transitively depend on them
+/// according to the given module dependencies.
+fn dependent_modules(
+    dependencies: &HashMap<PathBuf, HashSet<PathBuf>>,
+    mut modules: HashSet<PathBuf>,
+) -> HashSet<PathBuf> {
+    loop {
+        let dependents: Vec<PathBuf> = dependencies
+            .iter()
+            .filter(|(module, deps)| {
+                !modules.contains(*module) && deps.iter().any(|dep| modules.contains(dep))
+            })
+            .map(|(module, _)| module.clone())
+            .collect();
+        if dependents.is_empty() {
+            return modules;
+        }
+        modules.extend(dependents);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the tests of the `test_modules` package of the `forc-test` test data with the given
+    /// engines, and returns the build plan and the directory of the package.
+    fn build_test_modules(engines: &Engines) -> (pkg::BuildPlan, PathBuf) {
+        let pkg_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("forc-test")
+            .join("test_data")
+            .join("test_modules")
+            .canonicalize()
+            .unwrap();
+        let opts = TestOpts {
+            pkg: pkg::PkgOpts {
+                path: Some(pkg_dir.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let plan = pkg::BuildPlan::from_pkg_opts(&opts.pkg).unwrap();
+        forc_test::build_with_engines(opts, engines).unwrap();
+        (plan, pkg_dir)
+    }
+
+    #[test]
+    fn dependent_modules_include_parents_and_importers() {
+        let engines = Engines::default();
+        let (plan, pkg_dir) = build_test_modules(&engines);
+        let src_dir = pkg_dir.join("src");
+        let dependencies = module_dependencies(&engines, &plan).unwrap();
+        assert_eq!(
+            dependent_modules(&dependencies, HashSet::from([src_dir.join("foo.sw")])),
+            HashSet::from([
+                src_dir.join("foo.sw"),
+                src_dir.join("bar.sw"),
+                src_dir.join("lib.sw"),
+            ])
+        );
+        assert_eq!(
+            dependent_modules(&dependencies, HashSet::from([src_dir.join("baz.sw")])),
+            HashSet::from([src_dir.join("baz.sw"), src_dir.join("lib.sw")])
+        );
+    }
+
+    #[test]
+    fn invalidate_typed_caches_of_matching_modules() {
+        let engines = Engines::default();
+        let (_, pkg_dir) = build_test_modules(&engines);
+        let src_dir = pkg_dir.join("src");
+        engines
+            .qe()
+            .invalidate_typed_caches(|path| path == src_dir.join("foo.sw"));
+        let module_cache = engines.qe().module_cache.read();
+        let typed_modules: HashSet<PathBuf> = module_cache
+            .values()
+            .filter(|entry| entry.common.path.starts_with(&src_dir))
+            .filter(|entry| entry.is_typed())
+            .map(|entry| entry.common.path.to_path_buf())
+            .collect();
+        assert_eq!(
+            typed_modules,
+            HashSet::from([
+                src_dir.join("bar.sw"),
+                src_dir.join("baz.sw"),
+                src_dir.join("lib.sw"),
+            ])
+        );
+    }
+}
@@ -186,6 +186,7 @@ impl TyAstNode {
                 let fn_decl = decl_engine.get_function(decl_id);
                 let TyFunctionDecl { attributes, .. } = &*fn_decl;
                 attributes.contains_key(&AttributeKind::Test)
+                    || attributes.contains_key(&AttributeKind::Bench)
             }
             _ => false,
         }
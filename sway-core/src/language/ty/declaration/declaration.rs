@@ -726,7 +726,7 @@ impl TyDecl {
         }
     }
 
-    pub(crate) fn return_type(
+    pub fn return_type(
         &self,
         handler: &Handler,
         engines: &Engines,
@@ -783,7 +783,7 @@ impl TyDecl {
         Ok(type_id)
     }
 
-    pub(crate) fn visibility(&self, decl_engine: &DeclEngine) -> Visibility {
+    pub fn visibility(&self, decl_engine: &DeclEngine) -> Visibility {
         match self {
             TyDecl::TraitDecl(TraitDecl { decl_id, .. }) => {
                 decl_engine.get_trait(decl_id).visibility
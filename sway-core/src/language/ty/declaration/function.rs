@@ -425,10 +425,20 @@ impl TyFunctionDecl {
     }
 
     /// Whether or not this function is a unit test, i.e. decorated with `#[test]`.
+    ///
+    /// Benchmarks are compiled in the same way as unit tests, so this also holds for functions
+    /// decorated with `#[bench]`.
     pub fn is_test(&self) -> bool {
         //TODO match kind to Test
         self.attributes
             .contains_key(&transform::AttributeKind::Test)
+            || self.is_bench()
+    }
+
+    /// Whether or not this function is a benchmark, i.e. decorated with `#[bench]`.
+    pub fn is_bench(&self) -> bool {
+        self.attributes
+            .contains_key(&transform::AttributeKind::Bench)
     }
 
     pub fn inline(&self) -> Option<Inline> {
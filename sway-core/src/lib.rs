@@ -265,8 +265,8 @@ fn parse_submodules(
         // Read the source code from the dependency.
         // If we cannot, record as an error, but continue with other files.
         let submod_path = Arc::new(module_path(module_dir, module_name, submod));
-        let submod_str: Arc<str> = match std::fs::read_to_string(&*submod_path) {
-            Ok(s) => Arc::from(s),
+        let submod_str: Arc<str> = match engines.se().read_source(&submod_path) {
+            Ok(s) => s,
             Err(e) => {
                 handler.emit_err(CompileError::FileCouldNotBeRead {
                     span: submod.name.span(),
@@ -488,13 +488,15 @@ pub(crate) fn is_parse_module_cache_up_to_date(
                     let modified_time = std::fs::metadata(path.as_path())
                         .ok()
                         .and_then(|m| m.modified().ok());
-                    // Check if modification time matches, or if not, compare file content hash
-                    entry.parsed.modified_time == modified_time || {
-                        let src = std::fs::read_to_string(path.as_path()).unwrap();
-                        let mut hasher = DefaultHasher::new();
-                        src.hash(&mut hasher);
-                        hasher.finish() == entry.common.hash
-                    }
+                    // Check if modification time matches, or if not, compare file content hash.
+                    // The modification time says nothing about in-memory contents of the file.
+                    (!engines.se().has_overlay(path) && entry.parsed.modified_time == modified_time)
+                        || {
+                            let src = engines.se().read_source(path).unwrap();
+                            let mut hasher = DefaultHasher::new();
+                            src.hash(&mut hasher);
+                            hasher.finish() == entry.common.hash
+                        }
                 },
                 |version| {
                     // Determine if the parse cache is up-to-date in LSP mode:
@@ -516,7 +518,9 @@ pub(crate) fn is_parse_module_cache_up_to_date(
     })
 }
 
-fn module_path(
+/// Returns the path to the file of the given submodule, declared in the module with the given
+/// name and directory. The entry module of a package has no name.
+pub fn module_path(
     parent_module_dir: &Path,
     parent_module_name: Option<&str>,
     submod: &sway_ast::Submodule,
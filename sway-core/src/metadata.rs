@@ -219,8 +219,8 @@ impl MetadataManager {
                     .unwrap_source_id()
                     .and_then(|source_id| {
                         let path_buf = context.source_engine.get_path(source_id);
-                        let src = std::fs::read_to_string(&path_buf).ok()?;
-                        let path_and_src = (Arc::new(path_buf), Arc::from(src));
+                        let src = context.source_engine.read_source(&path_buf).ok()?;
+                        let path_and_src = (Arc::new(path_buf), src);
 
                         self.md_file_loc_cache.insert(md_idx, path_and_src.clone());
 
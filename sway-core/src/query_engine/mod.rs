@@ -2,7 +2,7 @@ use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::{
     collections::HashMap,
     ops::{Deref, DerefMut},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     time::SystemTime,
 };
@@ -224,6 +224,36 @@ impl QueryEngine {
         });
     }
 
+    /// Removes the typed modules and programs whose paths match `predicate` from the caches, so
+    /// that they are type-checked again by the next compilation even if their sources did not
+    /// change.
+    ///
+    /// Outside of the language server the typed module cache is not versioned, so this must be
+    /// called for the modules affected by a change when reusing the caches between compilations.
+    pub fn invalidate_typed_caches(&self, predicate: impl Fn(&Path) -> bool) {
+        self.module_cache
+            .write()
+            .values_mut()
+            .filter(|entry| predicate(&entry.common.path))
+            .for_each(|entry| entry.typed = None);
+        self.programs_cache
+            .write()
+            .retain(|path, _| !predicate(path));
+        self.commit();
+    }
+
+    /// Removes the module at `path` from the module cache, so that it is parsed and type-checked
+    /// again by the next compilation.
+    ///
+    /// This is needed when the in-memory contents of a file are discarded, as neither its file
+    /// version nor its modification time tell that the cached module is stale.
+    pub fn remove_module_cache_entries(&self, path: &Path) {
+        self.module_cache
+            .write()
+            .retain(|key, _| key.path.as_path() != path);
+        self.commit();
+    }
+
     ///  Commits all changes to their respective caches.
     pub fn commit(&self) {
         self.programs_cache.commit();
@@ -3,7 +3,7 @@ use crate::{
     engine_threading::{Engines, PartialEqWithEngines, PartialEqWithEnginesContext},
     language::{
         parsed::{Declaration, FunctionDeclaration},
-        ty::{self, StructAccessInfo, TyDecl, TyStorageDecl},
+        ty::{self, StructAccessInfo, TyDecl, TyImplItem, TyStorageDecl},
         CallPath, Visibility,
     },
     namespace::*,
@@ -156,6 +156,20 @@ impl Items {
         self.symbols().keys()
     }
 
+    /// Returns the symbols imported into this scope by item and star imports.
+    ///
+    /// If a symbol is star imported from more than one module, only the first import is returned.
+    pub fn get_all_imported_symbols(&self) -> impl Iterator<Item = (&Ident, &ResolvedDeclaration)> {
+        self.use_item_synonyms
+            .iter()
+            .map(|(name, (_, _, decl, _))| (name, decl))
+            .chain(
+                self.use_glob_synonyms
+                    .iter()
+                    .filter_map(|(name, imports)| imports.first().map(|(_, decl, _)| (name, decl))),
+            )
+    }
+
     pub(crate) fn insert_parsed_symbol(
         &mut self,
         handler: &Handler,
@@ -753,6 +767,35 @@ impl Items {
             .get_impl_spans_for_trait_name(trait_name)
     }
 
+    pub fn get_impls_for_trait_decl_span(&self, trait_decl_span: &Span) -> Vec<(TypeId, Span)> {
+        self.implemented_traits
+            .get_impls_for_trait_decl_span(trait_decl_span)
+    }
+
+    pub fn get_impl_items_for_trait_decl_span(
+        &self,
+        trait_decl_span: &Span,
+        item_name: &str,
+    ) -> Vec<TyImplItem> {
+        self.implemented_traits
+            .get_impl_items_for_trait_decl_span(trait_decl_span, item_name)
+    }
+
+    pub fn get_trait_decl_spans_for_decl(
+        &self,
+        engines: &Engines,
+        ty_decl: &TyDecl,
+    ) -> Vec<(Span, Span)> {
+        let handler = Handler::default();
+        ty_decl
+            .return_type(&handler, engines)
+            .map(|type_id| {
+                self.implemented_traits
+                    .get_trait_decl_spans_for_type(engines, type_id)
+            })
+            .unwrap_or_default()
+    }
+
     pub fn get_methods_for_type(
         &self,
         engines: &Engines,
@@ -1102,6 +1102,69 @@ impl TraitMap {
             .concat()
     }
 
+    /// Find the entries in `self` implementing the trait declared at
+    /// `trait_decl_span` and return the implementing types together with the
+    /// spans of the impls.
+    pub(crate) fn get_impls_for_trait_decl_span(
+        &self,
+        trait_decl_span: &Span,
+    ) -> Vec<(TypeId, Span)> {
+        self.trait_impls
+            .values()
+            .flat_map(|impls| impls.iter())
+            .filter(|entry| entry.key.trait_decl_span.as_ref() == Some(trait_decl_span))
+            .map(|entry| (entry.key.type_id, entry.value.impl_span.clone()))
+            .collect()
+    }
+
+    /// Find the entries in `self` implementing the trait declared at
+    /// `trait_decl_span` and return their type-checked items named `item_name`.
+    pub(crate) fn get_impl_items_for_trait_decl_span(
+        &self,
+        trait_decl_span: &Span,
+        item_name: &str,
+    ) -> Vec<TyImplItem> {
+        self.trait_impls
+            .values()
+            .flat_map(|impls| impls.iter())
+            .filter(|entry| entry.key.trait_decl_span.as_ref() == Some(trait_decl_span))
+            .filter_map(|entry| match entry.value.trait_items.get(item_name) {
+                Some(ResolvedTraitImplItem::Typed(item)) => Some(item.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Find the entries in `self` that are equivalent to `type_id` and return
+    /// the spans of the declarations of the implemented traits together with
+    /// the spans of the impls.
+    ///
+    /// Notes:
+    /// - "impl self" blocks are not included, as they do not implement a
+    ///   declared trait
+    pub(crate) fn get_trait_decl_spans_for_type(
+        &self,
+        engines: &Engines,
+        type_id: TypeId,
+    ) -> Vec<(Span, Span)> {
+        let type_engine = engines.te();
+        let unify_check = UnifyCheck::non_dynamic_equality(engines);
+        let mut spans = vec![];
+        // small performance gain in bad case
+        if matches!(&*type_engine.get(type_id), TypeInfo::ErrorRecovery(_)) {
+            return spans;
+        }
+        let impls = self.get_impls(engines, type_id);
+        for entry in impls.iter() {
+            if let Some(trait_decl_span) = &entry.key.trait_decl_span {
+                if unify_check.check(type_id, entry.key.type_id) {
+                    spans.push((trait_decl_span.clone(), entry.value.impl_span.clone()));
+                }
+            }
+        }
+        spans
+    }
+
     /// Find the entries in `self` that are equivalent to `type_id` with trait
     /// name `trait_name` and with trait type arguments.
     ///
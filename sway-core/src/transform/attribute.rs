@@ -63,6 +63,7 @@ pub enum AttributeKind {
     Storage,
     Inline,
     Test,
+    Bench,
     Payable,
     Allow,
     Cfg,
@@ -76,9 +77,8 @@ impl AttributeKind {
     pub fn expected_args_len_min_max(self) -> (usize, Option<usize>) {
         use AttributeKind::*;
         match self {
-            Doc | DocComment | Storage | Inline | Test | Payable | Deprecated | Fallback => {
-                (0, None)
-            }
+            Doc | DocComment | Storage | Inline | Test | Bench | Payable | Deprecated
+            | Fallback => (0, None),
             Allow | Cfg => (1, Some(1)),
         }
     }
@@ -87,7 +87,8 @@ impl AttributeKind {
     pub fn expected_args_values(self, _arg_index: usize) -> Option<Vec<String>> {
         use AttributeKind::*;
         match self {
-            Deprecated | Doc | DocComment | Storage | Inline | Test | Payable | Fallback => None,
+            Deprecated | Doc | DocComment | Storage | Inline | Test | Bench | Payable
+            | Fallback => None,
             Allow => Some(vec![
                 ALLOW_DEAD_CODE_NAME.to_string(),
                 ALLOW_DEPRECATED_NAME.to_string(),
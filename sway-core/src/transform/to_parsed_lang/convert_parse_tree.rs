@@ -31,12 +31,12 @@ use sway_error::warning::{CompileWarning, Warning};
 use sway_error::{convert_parse_tree_error::ConvertParseTreeError, error::CompileError};
 use sway_types::{
     constants::{
-        ALLOW_ATTRIBUTE_NAME, CFG_ATTRIBUTE_NAME, CFG_EXPERIMENTAL_NEW_ENCODING,
-        CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME, DEPRECATED_ATTRIBUTE_NAME,
-        DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME, FALLBACK_ATTRIBUTE_NAME,
-        INLINE_ATTRIBUTE_NAME, PAYABLE_ATTRIBUTE_NAME, STORAGE_PURITY_ATTRIBUTE_NAME,
-        STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME, TEST_ATTRIBUTE_NAME,
-        VALID_ATTRIBUTE_NAMES,
+        ALLOW_ATTRIBUTE_NAME, BENCH_ATTRIBUTE_NAME, CFG_ATTRIBUTE_NAME,
+        CFG_EXPERIMENTAL_NEW_ENCODING, CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME,
+        DEPRECATED_ATTRIBUTE_NAME, DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME,
+        FALLBACK_ATTRIBUTE_NAME, INLINE_ATTRIBUTE_NAME, PAYABLE_ATTRIBUTE_NAME,
+        STORAGE_PURITY_ATTRIBUTE_NAME, STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME,
+        TEST_ATTRIBUTE_NAME, VALID_ATTRIBUTE_NAMES,
     },
     integer_bits::IntegerBits,
     BaseIdent,
@@ -100,7 +100,9 @@ pub fn module_to_sway_parse_tree(
 fn ast_node_is_test_fn(engines: &Engines, node: &AstNode) -> bool {
     if let AstNodeContent::Declaration(Declaration::FunctionDeclaration(decl_id)) = node.content {
         let decl = engines.pe().get_function(&decl_id);
-        if decl.attributes.contains_key(&AttributeKind::Test) {
+        if decl.attributes.contains_key(&AttributeKind::Test)
+            || decl.attributes.contains_key(&AttributeKind::Bench)
+        {
             return true;
         }
     }
@@ -4776,6 +4778,7 @@ fn item_attrs_to_map(
                 STORAGE_PURITY_ATTRIBUTE_NAME => Some(AttributeKind::Storage),
                 INLINE_ATTRIBUTE_NAME => Some(AttributeKind::Inline),
                 TEST_ATTRIBUTE_NAME => Some(AttributeKind::Test),
+                BENCH_ATTRIBUTE_NAME => Some(AttributeKind::Bench),
                 PAYABLE_ATTRIBUTE_NAME => Some(AttributeKind::Payable),
                 ALLOW_ATTRIBUTE_NAME => Some(AttributeKind::Allow),
                 CFG_ATTRIBUTE_NAME => Some(AttributeKind::Cfg),
@@ -31,10 +31,15 @@ fn benchmarks(c: &mut Criterion) {
 
     c.bench_function("completion", |b| {
         let position = Position::new(1698, 28);
+        let document = documents.get_text_document(&uri).unwrap();
         b.iter(|| {
-            session
-                .completion_items(&uri, position, ".")
-                .map(CompletionResponse::Array)
+            capabilities::completion::completion_items(
+                session.clone(),
+                &uri,
+                position,
+                document.get_text(),
+            )
+            .map(CompletionResponse::Array)
         })
     });
 
@@ -55,7 +60,7 @@ fn benchmarks(c: &mut Criterion) {
     });
 
     c.bench_function("find_all_references", |b| {
-        b.iter(|| session.token_references(&uri, position))
+        b.iter(|| session.token_references(&uri, position, &[]))
     });
 
     c.bench_function("goto_definition", |b| {
@@ -81,6 +86,7 @@ fn benchmarks(c: &mut Criterion) {
         b.iter(|| {
             capabilities::rename::rename(
                 session.clone(),
+                &[],
                 "new_token_name".to_string(),
                 &uri,
                 position,
@@ -90,8 +96,15 @@ fn benchmarks(c: &mut Criterion) {
 
     c.bench_function("code_action", |b| {
         let range = Range::new(Position::new(4, 10), Position::new(4, 10));
+        let document = documents.get_text_document(&uri).unwrap();
         b.iter(|| {
-            capabilities::code_actions::code_actions(session.clone(), &range, &uri, &uri, &vec![])
+            capabilities::code_actions::code_actions(
+                session.clone(),
+                &range,
+                &uri,
+                &vec![],
+                document.get_text(),
+            )
         })
     });
 
@@ -0,0 +1,310 @@
+use crate::{
+    capabilities::workspace_symbol::fn_container_name,
+    core::{
+        session::Session,
+        token::{get_range_from_span, Token, TypedAstToken},
+    },
+};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, Position, Range,
+    SymbolKind, Url,
+};
+use std::{collections::HashSet, sync::Arc};
+use sway_core::{
+    decl_engine::InterfaceDeclId,
+    language::ty::{self, TyFunctionDecl, TyTraitFn},
+    Engines,
+};
+use sway_types::{Ident, Span, Spanned};
+
+/// A function that can be the subject of a call hierarchy.
+enum Callable {
+    /// A function or method with a body.
+    Fn(Arc<TyFunctionDecl>),
+    /// A function declared in the interface of a trait or ABI.
+    TraitFn(Arc<TyTraitFn>),
+}
+
+/// Returns the function declared or called at the given position.
+pub fn prepare_call_hierarchy(
+    session: Arc<Session>,
+    uri: &Url,
+    position: Position,
+) -> Option<Vec<CallHierarchyItem>> {
+    let _p = tracing::trace_span!("prepare_call_hierarchy").entered();
+    let engines = session.engines.read();
+    let callable = callable_at_position(&session, &engines, uri, position)?;
+    let item = match callable {
+        Callable::Fn(fn_decl) => fn_item(&engines, &fn_decl),
+        Callable::TraitFn(trait_fn) => trait_fn_item(&engines, &trait_fn),
+    }?;
+    Some(vec![item])
+}
+
+/// Returns the functions calling the function described by the given item, grouped by caller.
+///
+/// The calls are collected from all of the given sessions, so that calls made from other
+/// workspace members, including calls to a contract through its ABI, are found as well.
+/// `session` is the session owning the item, and `uri` is the item's location within it.
+pub fn incoming_calls(
+    session: Arc<Session>,
+    sessions: &[Arc<Session>],
+    uri: &Url,
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyIncomingCall> {
+    let _p = tracing::trace_span!("incoming_calls").entered();
+    let mut targets = vec![(item.uri.clone(), item.selection_range)];
+    // Contract calls resolve to the function declared in the ABI rather than the one
+    // implementing it, so calls to the ABI function are calls to the implementation.
+    {
+        let engines = session.engines.read();
+        if let Some(Callable::Fn(fn_decl)) =
+            callable_at_position(&session, &engines, uri, item.selection_range.start)
+        {
+            if let Some(abi_fn_name) = abi_interface_fn_name(&session, &engines, &fn_decl) {
+                if let Some(location) = span_location(&engines, &abi_fn_name.span()) {
+                    targets.push(location);
+                }
+            }
+        }
+    }
+
+    let mut incoming: Vec<CallHierarchyIncomingCall> = vec![];
+    let mut seen = HashSet::new();
+    for session in sessions {
+        let engines = session.engines.read();
+        let call_sites = session
+            .token_map()
+            .iter()
+            .filter_map(|entry| {
+                let (ident, token) = entry.pair();
+                let callee = callee_of_token(&engines, token)?;
+                let callee_location = span_location(&engines, &callee.name.span())?;
+                if !targets.contains(&callee_location) {
+                    return None;
+                }
+                let call_url = Url::from_file_path(ident.path.as_ref()?).ok()?;
+                Some((call_url, ident.range))
+            })
+            .collect::<Vec<_>>();
+        for (call_url, call_range) in call_sites {
+            if !seen.insert((
+                call_url.clone(),
+                call_range.start.line,
+                call_range.start.character,
+            )) {
+                continue;
+            }
+            let Some(caller) = session
+                .token_map()
+                .tokens_at_position(&engines, &call_url, call_range.start, Some(true))
+                .into_iter()
+                .find_map(|entry| match &entry.value().typed {
+                    Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => {
+                        fn_item(&engines, fn_decl)
+                    }
+                    _ => None,
+                })
+            else {
+                continue;
+            };
+            match incoming
+                .iter_mut()
+                .find(|call| same_item(&call.from, &caller))
+            {
+                Some(call) => call.from_ranges.push(call_range),
+                None => incoming.push(CallHierarchyIncomingCall {
+                    from: caller,
+                    from_ranges: vec![call_range],
+                }),
+            }
+        }
+    }
+    incoming
+}
+
+/// Returns the functions called by the function described by the given item, grouped by callee.
+pub fn outgoing_calls(
+    session: Arc<Session>,
+    uri: &Url,
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyOutgoingCall> {
+    let _p = tracing::trace_span!("outgoing_calls").entered();
+    let engines = session.engines.read();
+    let Some(Callable::Fn(fn_decl)) =
+        callable_at_position(&session, &engines, uri, item.selection_range.start)
+    else {
+        return vec![];
+    };
+    let body_range = get_range_from_span(&fn_decl.span);
+
+    let mut outgoing: Vec<CallHierarchyOutgoingCall> = vec![];
+    for entry in session.token_map().tokens_for_file(uri) {
+        let (ident, token) = entry.pair();
+        if ident.range.start < body_range.start || ident.range.end > body_range.end {
+            continue;
+        }
+        let Some(callee) = callee_of_token(&engines, token) else {
+            continue;
+        };
+        let Some(callee_item) = fn_item(&engines, &callee) else {
+            continue;
+        };
+        match outgoing
+            .iter_mut()
+            .find(|call| same_item(&call.to, &callee_item))
+        {
+            Some(call) => call.from_ranges.push(ident.range),
+            None => outgoing.push(CallHierarchyOutgoingCall {
+                to: callee_item,
+                from_ranges: vec![ident.range],
+            }),
+        }
+    }
+    outgoing.iter_mut().for_each(|call| {
+        call.from_ranges
+            .sort_by_key(|range| (range.start.line, range.start.character))
+    });
+    outgoing
+}
+
+/// Resolves the token at the given position to the function it declares or calls.
+fn callable_at_position(
+    session: &Session,
+    engines: &Engines,
+    uri: &Url,
+    position: Position,
+) -> Option<Callable> {
+    let token = session.token_map().token_at_position(uri, position)?;
+    let declaration = token
+        .value()
+        .declared_token_ident(engines)
+        .and_then(|decl_ident| session.token_map().try_get(&decl_ident).try_unwrap())
+        .map(|item| item.value().clone());
+    let callable = |token: &Token| match &token.typed {
+        Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => {
+            Some(Callable::Fn(Arc::new(fn_decl.clone())))
+        }
+        Some(TypedAstToken::TypedDeclaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl {
+            decl_id,
+        }))) => Some(Callable::Fn(engines.de().get_function(decl_id))),
+        Some(TypedAstToken::TypedTraitFn(trait_fn)) => {
+            Some(Callable::TraitFn(Arc::new(trait_fn.clone())))
+        }
+        _ => None,
+    };
+    declaration
+        .as_ref()
+        .and_then(callable)
+        .or_else(|| callee_of_token(engines, token.value()).map(Callable::Fn))
+}
+
+/// Returns the function called by the given token, if it is a function application.
+fn callee_of_token(engines: &Engines, token: &Token) -> Option<Arc<TyFunctionDecl>> {
+    match &token.typed {
+        Some(TypedAstToken::TypedExpression(ty::TyExpression {
+            expression: ty::TyExpressionVariant::FunctionApplication { fn_ref, .. },
+            ..
+        })) => Some(engines.de().get_function(fn_ref)),
+        _ => None,
+    }
+}
+
+/// If the given function implements an ABI function for a contract, returns the name of the
+/// function as declared in the ABI.
+fn abi_interface_fn_name(
+    session: &Session,
+    engines: &Engines,
+    fn_decl: &TyFunctionDecl,
+) -> Option<Ident> {
+    let compiled_program = session.compiled_program.read();
+    let program = compiled_program.typed.as_ref()?;
+    program
+        .root
+        .all_nodes
+        .iter()
+        .chain(
+            program
+                .root
+                .submodules_recursive()
+                .flat_map(|(_, submodule)| &submodule.module.all_nodes),
+        )
+        .find_map(|node| {
+            let ty::TyAstNodeContent::Declaration(ty::TyDecl::ImplSelfOrTrait(impl_decl)) =
+                &node.content
+            else {
+                return None;
+            };
+            let impl_trait = engines.de().get_impl_self_or_trait(&impl_decl.decl_id);
+            let InterfaceDeclId::Abi(abi_id) = impl_trait.trait_decl_ref.as_ref()?.id() else {
+                return None;
+            };
+            let implements_fn = impl_trait.items.iter().any(|item| match item {
+                ty::TyTraitItem::Fn(decl_ref) => {
+                    engines.de().get_function(decl_ref).span == fn_decl.span
+                }
+                _ => false,
+            });
+            if !implements_fn {
+                return None;
+            }
+            engines
+                .de()
+                .get_abi(abi_id)
+                .interface_surface
+                .iter()
+                .find_map(|item| match item {
+                    ty::TyTraitInterfaceItem::TraitFn(decl_ref)
+                        if decl_ref.name() == &fn_decl.name =>
+                    {
+                        Some(engines.de().get_trait_fn(decl_ref).name.clone())
+                    }
+                    _ => None,
+                })
+        })
+}
+
+fn fn_item(engines: &Engines, fn_decl: &TyFunctionDecl) -> Option<CallHierarchyItem> {
+    let (uri, selection_range) = span_location(engines, &fn_decl.name.span())?;
+    let is_method =
+        fn_decl.implementing_type.is_some() || fn_decl.implementing_for_typeid.is_some();
+    Some(CallHierarchyItem {
+        name: fn_decl.name.to_string(),
+        kind: if is_method {
+            SymbolKind::METHOD
+        } else {
+            SymbolKind::FUNCTION
+        },
+        tags: None,
+        detail: fn_container_name(engines, fn_decl),
+        uri,
+        range: get_range_from_span(&fn_decl.span),
+        selection_range,
+        data: None,
+    })
+}
+
+fn trait_fn_item(engines: &Engines, trait_fn: &TyTraitFn) -> Option<CallHierarchyItem> {
+    let (uri, selection_range) = span_location(engines, &trait_fn.name.span())?;
+    Some(CallHierarchyItem {
+        name: trait_fn.name.to_string(),
+        kind: SymbolKind::METHOD,
+        tags: None,
+        detail: None,
+        uri,
+        range: selection_range,
+        selection_range,
+        data: None,
+    })
+}
+
+/// Returns the workspace [Url] and the [Range] of the given span.
+pub(crate) fn span_location(engines: &Engines, span: &Span) -> Option<(Url, Range)> {
+    let path = engines.se().get_path(span.source_id()?);
+    let url = Url::from_file_path(path).ok()?;
+    Some((url, get_range_from_span(span)))
+}
+
+fn same_item(a: &CallHierarchyItem, b: &CallHierarchyItem) -> bool {
+    a.uri == b.uri && a.selection_range == b.selection_range
+}
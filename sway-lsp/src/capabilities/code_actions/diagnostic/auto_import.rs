@@ -3,11 +3,14 @@ use crate::{
         code_actions::{CodeActionContext, CODE_ACTION_IMPORT_TITLE},
         diagnostic::DiagnosticData,
     },
-    core::token::{get_range_from_span, AstToken, SymbolKind, TypedAstToken},
+    core::{
+        token::{get_range_from_span, AstToken, SymbolKind, TypedAstToken},
+        token_map::TokenMap,
+    },
 };
 use lsp_types::{
     CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit,
-    WorkspaceEdit,
+    Url, WorkspaceEdit,
 };
 use serde_json::Value;
 use std::{
@@ -15,12 +18,17 @@ use std::{
     collections::{BTreeSet, HashMap},
     iter,
 };
-use sway_core::language::{
-    parsed::ImportType,
-    ty::{
-        TyConstantDecl, TyDecl, TyFunctionDecl, TyIncludeStatement, TyTypeAliasDecl, TyUseStatement,
+use sway_core::{
+    language::{
+        parsed::ImportType,
+        ty::{
+            TyConstantDecl, TyDecl, TyFunctionDecl, TyIncludeStatement, TyTypeAliasDecl,
+            TyUseStatement,
+        },
+        CallPath,
     },
-    CallPath,
+    namespace::Namespace,
+    Engines,
 };
 use sway_types::{Ident, Spanned};
 
@@ -36,32 +44,12 @@ pub(crate) fn import_code_action(
     let call_paths = get_call_paths_for_name(ctx, &symbol_name)?;
 
     // Collect the tokens we need to determine where to insert the import statement.
-    let mut use_statements = Vec::<TyUseStatement>::new();
-    let mut include_statements = Vec::<TyIncludeStatement>::new();
-    let mut program_type_keyword = None;
-
-    ctx.tokens.tokens_for_file(ctx.temp_uri).for_each(|item| {
-        if let Some(TypedAstToken::TypedUseStatement(use_stmt)) = &item.value().typed {
-            use_statements.push(use_stmt.clone());
-        } else if let Some(TypedAstToken::TypedIncludeStatement(include_stmt)) = &item.value().typed
-        {
-            include_statements.push(include_stmt.clone());
-        } else if item.value().kind == SymbolKind::ProgramTypeKeyword {
-            if let AstToken::Keyword(ident) = &item.value().parsed {
-                program_type_keyword = Some(ident.clone());
-            }
-        }
-    });
+    let imports = FileImports::collect(ctx.tokens, ctx.uri);
 
     // Create a list of code actions, one for each potential call path.
     let actions = call_paths
         .map(|call_path| {
-            let text_edit = get_text_edit(
-                &call_path,
-                &use_statements,
-                &include_statements,
-                &program_type_keyword,
-            );
+            let text_edit = imports.text_edit(&call_path);
             let changes = HashMap::from([(ctx.uri.clone(), vec![text_edit])]);
 
             CodeActionOrCommand::CodeAction(LspCodeAction {
@@ -95,74 +83,96 @@ pub(crate) fn get_call_paths_for_name<'s>(
         .tokens
         .tokens_for_name(symbol_name)
         .filter_map(move |item| {
-            // If the typed token is a declaration, then we can import it.
-            match item.value().typed.as_ref() {
-                Some(TypedAstToken::TypedDeclaration(ty_decl)) => {
-                    return match ty_decl {
-                        TyDecl::StructDecl(decl) => {
-                            let struct_decl = ctx.engines.de().get_struct(&decl.decl_id);
-                            let call_path = struct_decl
-                                .call_path
-                                .to_import_path(ctx.engines, &namespace);
-                            Some(call_path)
-                        }
-                        TyDecl::EnumDecl(decl) => {
-                            let enum_decl = ctx.engines.de().get_enum(&decl.decl_id);
-                            let call_path =
-                                enum_decl.call_path.to_import_path(ctx.engines, &namespace);
-                            Some(call_path)
-                        }
-                        TyDecl::TraitDecl(decl) => {
-                            let trait_decl = ctx.engines.de().get_trait(&decl.decl_id);
-                            let call_path =
-                                trait_decl.call_path.to_import_path(ctx.engines, &namespace);
-                            Some(call_path)
-                        }
-                        TyDecl::FunctionDecl(decl) => {
-                            let function_decl = ctx.engines.de().get_function(&decl.decl_id);
-                            let call_path = function_decl
-                                .call_path
-                                .to_import_path(ctx.engines, &namespace);
-                            Some(call_path)
-                        }
-                        TyDecl::ConstantDecl(decl) => {
-                            let constant_decl = ctx.engines.de().get_constant(&decl.decl_id);
-                            let call_path = constant_decl
-                                .call_path
-                                .to_import_path(ctx.engines, &namespace);
-                            Some(call_path)
-                        }
-                        TyDecl::TypeAliasDecl(decl) => {
-                            let type_alias_decl = ctx.engines.de().get_type_alias(&decl.decl_id);
-                            let call_path = type_alias_decl
-                                .call_path
-                                .to_import_path(ctx.engines, &namespace);
-                            Some(call_path)
-                        }
-                        _ => None,
-                    };
-                }
-                Some(TypedAstToken::TypedFunctionDeclaration(TyFunctionDecl {
-                    call_path, ..
-                }))
-                | Some(TypedAstToken::TypedConstantDeclaration(TyConstantDecl {
-                    call_path, ..
-                }))
-                | Some(TypedAstToken::TypedTypeAliasDeclaration(TyTypeAliasDecl {
-                    call_path,
-                    ..
-                })) => {
-                    let call_path = call_path.to_import_path(ctx.engines, &namespace);
-                    Some(call_path)
-                }
-                _ => None,
-            }
+            import_call_path(ctx.engines, &namespace, item.value().typed.as_ref()?)
         })
         .collect::<Vec<_>>();
     call_paths.sort();
     Some(call_paths.into_iter())
 }
 
+/// Returns the [CallPath] to import the declaration represented by the given typed token, if it
+/// can be imported.
+pub(crate) fn import_call_path(
+    engines: &Engines,
+    namespace: &Namespace,
+    typed_token: &TypedAstToken,
+) -> Option<CallPath> {
+    match typed_token {
+        TypedAstToken::TypedDeclaration(ty_decl) => match ty_decl {
+            TyDecl::StructDecl(decl) => {
+                let struct_decl = engines.de().get_struct(&decl.decl_id);
+                Some(struct_decl.call_path.to_import_path(engines, namespace))
+            }
+            TyDecl::EnumDecl(decl) => {
+                let enum_decl = engines.de().get_enum(&decl.decl_id);
+                Some(enum_decl.call_path.to_import_path(engines, namespace))
+            }
+            TyDecl::TraitDecl(decl) => {
+                let trait_decl = engines.de().get_trait(&decl.decl_id);
+                Some(trait_decl.call_path.to_import_path(engines, namespace))
+            }
+            TyDecl::FunctionDecl(decl) => {
+                let function_decl = engines.de().get_function(&decl.decl_id);
+                Some(function_decl.call_path.to_import_path(engines, namespace))
+            }
+            TyDecl::ConstantDecl(decl) => {
+                let constant_decl = engines.de().get_constant(&decl.decl_id);
+                Some(constant_decl.call_path.to_import_path(engines, namespace))
+            }
+            TyDecl::TypeAliasDecl(decl) => {
+                let type_alias_decl = engines.de().get_type_alias(&decl.decl_id);
+                Some(type_alias_decl.call_path.to_import_path(engines, namespace))
+            }
+            _ => None,
+        },
+        TypedAstToken::TypedFunctionDeclaration(TyFunctionDecl { call_path, .. })
+        | TypedAstToken::TypedConstantDeclaration(TyConstantDecl { call_path, .. })
+        | TypedAstToken::TypedTypeAliasDeclaration(TyTypeAliasDecl { call_path, .. }) => {
+            Some(call_path.to_import_path(engines, namespace))
+        }
+        _ => None,
+    }
+}
+
+/// The statements of a file that determine where an import statement is inserted.
+#[derive(Debug, Default)]
+pub(crate) struct FileImports {
+    use_statements: Vec<TyUseStatement>,
+    include_statements: Vec<TyIncludeStatement>,
+    program_type_keyword: Option<Ident>,
+}
+
+impl FileImports {
+    /// Collects the use statements, mod statements and program type keyword of the given file.
+    pub(crate) fn collect(tokens: &TokenMap, uri: &Url) -> Self {
+        let mut imports = FileImports::default();
+        tokens.tokens_for_file(uri).for_each(|item| {
+            if let Some(TypedAstToken::TypedUseStatement(use_stmt)) = &item.value().typed {
+                imports.use_statements.push(use_stmt.clone());
+            } else if let Some(TypedAstToken::TypedIncludeStatement(include_stmt)) =
+                &item.value().typed
+            {
+                imports.include_statements.push(include_stmt.clone());
+            } else if item.value().kind == SymbolKind::ProgramTypeKeyword {
+                if let AstToken::Keyword(ident) = &item.value().parsed {
+                    imports.program_type_keyword = Some(ident.clone());
+                }
+            }
+        });
+        imports
+    }
+
+    /// Returns a [TextEdit] to insert an import statement for the given [CallPath].
+    pub(crate) fn text_edit(&self, call_path: &CallPath) -> TextEdit {
+        get_text_edit(
+            call_path,
+            &self.use_statements,
+            &self.include_statements,
+            &self.program_type_keyword,
+        )
+    }
+}
+
 /// Returns a [TextEdit] to insert an import statement for the given [CallPath] in the appropriate location in the file.
 ///
 /// To determine where to insert the import statement in the file, we try these options and do
@@ -0,0 +1,165 @@
+use super::{annotated_span, module_fns, quick_fix};
+use crate::{
+    capabilities::{
+        code_actions::{
+            CodeActionContext, CODE_ACTION_REMOVE_UNUSED_TITLE, CODE_ACTION_RENAME_TITLE,
+        },
+        diagnostic::DiagnosticData,
+    },
+    utils::document::{offset_of_position, position_of_offset},
+};
+use lsp_types::{CodeActionOrCommand, Range, TextEdit};
+use std::ops;
+use sway_ast::{ItemImplItem, ItemKind, Module};
+use sway_types::{Ident, Span, Spanned};
+
+/// Returns a list of [CodeActionOrCommand] suggestions for removing a declaration that is never
+/// used, or for prefixing an unused variable with an underscore.
+pub(crate) fn dead_code_code_action(
+    ctx: &CodeActionContext,
+    module: &Module,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    let actions = diagnostics
+        .filter_map(|(range, diag)| {
+            if !diag.dead_code {
+                return None;
+            }
+            let offsets = offset_of_position(ctx.text, range.start)?
+                ..offset_of_position(ctx.text, range.end)?;
+
+            if let Some(item_span) = dead_item_span(module, &offsets) {
+                let removed = removed_lines(ctx.text, item_span.start()..item_span.end());
+                let text_edit = TextEdit {
+                    range: Range::new(
+                        position_of_offset(ctx.text, removed.start),
+                        position_of_offset(ctx.text, removed.end),
+                    ),
+                    new_text: String::new(),
+                };
+                return Some(quick_fix(
+                    ctx,
+                    CODE_ACTION_REMOVE_UNUSED_TITLE.to_string(),
+                    vec![text_edit],
+                ));
+            }
+
+            // Otherwise the declaration is a variable or a parameter of a function.
+            let name = &ctx.text[offsets.clone()];
+            let is_local = module_fns(module).any(|(_, item_fn)| {
+                let span = item_fn.span();
+                span.start() <= offsets.start && offsets.end <= span.end()
+            });
+            if !is_local || name.starts_with('_') || !is_ident(name) {
+                return None;
+            }
+            let text_edit = TextEdit {
+                range: Range::new(range.start, range.start),
+                new_text: "_".to_string(),
+            };
+            Some(quick_fix(
+                ctx,
+                format!("{CODE_ACTION_RENAME_TITLE} `_{name}`"),
+                vec![text_edit],
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if !actions.is_empty() {
+        return Some(actions);
+    }
+
+    None
+}
+
+/// Returns the span, including attributes, of the item of the module or of its `impl` blocks that
+/// the diagnostic with the given byte offsets points to, either by its name or as a whole.
+fn dead_item_span(module: &Module, offsets: &ops::Range<usize>) -> Option<Span> {
+    let points_to = |name: Option<&Ident>, span: &Span| {
+        name.map(Ident::span)
+            .into_iter()
+            .chain([span.clone()])
+            .any(|span| span.start() == offsets.start && span.end() == offsets.end)
+    };
+    module.items.iter().find_map(|item| {
+        if points_to(item_name(&item.value), &item.value.span()) {
+            return Some(annotated_span(item));
+        }
+        let ItemKind::Impl(item_impl) = &item.value else {
+            return None;
+        };
+        item_impl.contents.get().iter().find_map(|impl_item| {
+            let name = match &impl_item.value {
+                ItemImplItem::Fn(item_fn) => Some(&item_fn.fn_signature.name),
+                ItemImplItem::Const(item_const) => Some(&item_const.name),
+                ItemImplItem::Type(_) => None,
+            };
+            points_to(name, &impl_item.value.span()).then(|| annotated_span(impl_item))
+        })
+    })
+}
+
+fn item_name(item: &ItemKind) -> Option<&Ident> {
+    match item {
+        ItemKind::Struct(item_struct) => Some(&item_struct.name),
+        ItemKind::Enum(item_enum) => Some(&item_enum.name),
+        ItemKind::Fn(item_fn) => Some(&item_fn.fn_signature.name),
+        ItemKind::Trait(item_trait) => Some(&item_trait.name),
+        ItemKind::Abi(item_abi) => Some(&item_abi.name),
+        ItemKind::Const(item_const) => Some(&item_const.name),
+        ItemKind::TypeAlias(item_type_alias) => Some(&item_type_alias.name),
+        _ => None,
+    }
+}
+
+/// Returns the byte offsets of the text to remove along with the given byte offsets: the rest of
+/// their lines if nothing else is on them, and one of the blank lines around them.
+fn removed_lines(text: &str, offsets: ops::Range<usize>) -> ops::Range<usize> {
+    let line_start = text[..offsets.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[offsets.end..]
+        .find('\n')
+        .map_or(text.len(), |i| offsets.end + i + 1);
+    if !text[line_start..offsets.start].trim().is_empty()
+        || !text[offsets.end..line_end].trim().is_empty()
+    {
+        return offsets;
+    }
+    let next_line_end = text[line_end..]
+        .find('\n')
+        .map_or(text.len(), |i| line_end + i + 1);
+    if line_end < text.len() && text[line_end..next_line_end].trim().is_empty() {
+        return line_start..next_line_end;
+    }
+    let previous_line_start = text[..line_start.saturating_sub(1)]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    if line_start > 0 && text[previous_line_start..line_start].trim().is_empty() {
+        return previous_line_start..line_end;
+    }
+    line_start..line_end
+}
+
+fn is_ident(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_lines_of_items() {
+        let text = "library;\n\nfn a() {}\n\nfn b() {}\n";
+        let start = text.find("fn a").unwrap();
+        assert_eq!(
+            &text[removed_lines(text, start..start + 9)],
+            "fn a() {}\n\n"
+        );
+        let start = text.find("fn b").unwrap();
+        assert_eq!(
+            &text[removed_lines(text, start..start + 9)],
+            "\nfn b() {}\n"
+        );
+    }
+}
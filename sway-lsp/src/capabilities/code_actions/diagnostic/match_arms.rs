@@ -0,0 +1,119 @@
+use super::{insert_before_closing_brace, module_fns, quick_fix};
+use crate::{
+    capabilities::{
+        code_actions::{
+            refactor::{indentation, visit_exprs},
+            CodeActionContext, CODE_ACTION_ADD_MATCH_ARMS_TITLE,
+        },
+        diagnostic::DiagnosticData,
+    },
+    utils::document::offset_of_position,
+};
+use lsp_types::{CodeActionOrCommand, Range};
+use std::sync::Arc;
+use sway_ast::{Expr, Module};
+use sway_error::handler::Handler;
+use sway_types::Spanned;
+
+const WILDCARD: &str = "_";
+
+/// Returns a list of [CodeActionOrCommand] suggestions for adding the arms missing from a
+/// non-exhaustive `match` expression.
+pub(crate) fn match_arms_code_action(
+    ctx: &CodeActionContext,
+    module: &Module,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    let actions = diagnostics
+        .filter_map(|(range, diag)| {
+            let missing_patterns = diag.missing_patterns?;
+            let match_start = offset_of_position(ctx.text, range.start)?;
+            let mut match_expr = None;
+            for (_, item_fn) in module_fns(module) {
+                visit_exprs(item_fn.body.get(), &mut |expr| {
+                    if match_expr.is_none()
+                        && matches!(expr, Expr::Match { .. })
+                        && expr.span().start() == match_start
+                    {
+                        match_expr = Some(expr);
+                    }
+                });
+            }
+            let Some(Expr::Match { branches, .. }) = match_expr else {
+                return None;
+            };
+
+            let arm_indentation = match branches.get().first() {
+                Some(branch) => indentation(ctx.text, branch.span().start()),
+                None => indentation(ctx.text, match_start) + 4,
+            };
+            let arms = patterns(&missing_patterns)
+                .into_iter()
+                .map(|pattern| format!("{pattern} => {{}},"))
+                .collect::<Vec<_>>();
+            let text_edit =
+                insert_before_closing_brace(ctx.text, &branches.span(), arm_indentation, &arms);
+
+            Some(quick_fix(
+                ctx,
+                CODE_ACTION_ADD_MATCH_ARMS_TITLE.to_string(),
+                vec![text_edit],
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if !actions.is_empty() {
+        return Some(actions);
+    }
+
+    None
+}
+
+/// Returns the patterns of the arms to add for the missing patterns reported by the compiler,
+/// such as "`Color::Red`, `Color::Green`".
+///
+/// The compiler reports some patterns, like ranges of numbers, in a form that is not valid Sway,
+/// so these are covered by a single wildcard pattern instead.
+fn patterns(missing_patterns: &str) -> Vec<String> {
+    let mut patterns = vec![];
+    for pattern in missing_patterns.trim().trim_matches('`').split("`, `") {
+        let pattern = if is_valid_pattern(pattern) {
+            pattern
+        } else {
+            WILDCARD
+        };
+        if !patterns.iter().any(|existing| existing == pattern) {
+            patterns.push(pattern.to_string());
+        }
+    }
+    // A wildcard pattern covers the other patterns, which would be unreachable after it.
+    if patterns.iter().any(|pattern| pattern == WILDCARD) {
+        patterns.retain(|pattern| pattern == WILDCARD);
+    }
+    patterns
+}
+
+fn is_valid_pattern(pattern: &str) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+    let src =
+        format!("library;\n\nfn f() {{\n    match x {{\n        {pattern} => {{}},\n    }}\n}}\n");
+    let handler = Handler::default();
+    sway_parse::parse_file(&handler, Arc::from(src), None).is_ok() && !handler.has_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_patterns() {
+        assert_eq!(
+            patterns("`Color::Red(_)`, `Color::Green(_)`"),
+            vec!["Color::Red(_)", "Color::Green(_)"]
+        );
+        assert_eq!(patterns("`(true, _)`"), vec!["(true, _)"]);
+        assert_eq!(patterns("`[0...3]`, `Color::Red(_)`"), vec!["_"]);
+    }
+}
@@ -1,11 +1,34 @@
-mod auto_import;
+pub(crate) mod auto_import;
+mod dead_code;
+mod match_arms;
+mod naming_convention;
 mod qualify;
+mod storage_attributes;
+mod trait_items;
 
-use crate::capabilities::{code_actions::CodeActionContext, diagnostic::DiagnosticData};
-use lsp_types::CodeActionOrCommand;
+use crate::{
+    capabilities::{code_actions::CodeActionContext, diagnostic::DiagnosticData},
+    core::token::get_range_from_span,
+};
+use lsp_types::{
+    CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit,
+    WorkspaceEdit,
+};
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc};
+use sway_ast::{
+    attribute::Annotated, AttributeDecl, ItemFn, ItemImpl, ItemImplItem, ItemKind, Module,
+};
+use sway_error::handler::Handler;
+use sway_types::{Span, Spanned};
 
 use self::auto_import::import_code_action;
+use self::dead_code::dead_code_code_action;
+use self::match_arms::match_arms_code_action;
+use self::naming_convention::naming_convention_code_action;
 use self::qualify::qualify_code_action;
+use self::storage_attributes::storage_attributes_code_action;
+use self::trait_items::trait_items_code_action;
 
 /// Returns a list of [CodeActionOrCommand] based on the relevant compiler diagnostics.
 pub(crate) fn code_actions(ctx: &CodeActionContext) -> Option<Vec<CodeActionOrCommand>> {
@@ -19,11 +42,112 @@ pub(crate) fn code_actions(ctx: &CodeActionContext) -> Option<Vec<CodeActionOrCo
         None
     });
 
+    // The quick-fixes editing the document locate the code to edit in its syntax tree.
+    let module = sway_parse::parse_file(&Handler::default(), Arc::from(ctx.text), None)
+        .ok()
+        .map(|module| module.value);
+    let module_code_actions = module.iter().flat_map(|module| {
+        [
+            storage_attributes_code_action(ctx, module, &mut diagnostics_with_data.clone()),
+            match_arms_code_action(ctx, module, &mut diagnostics_with_data.clone()),
+            trait_items_code_action(ctx, module, &mut diagnostics_with_data.clone()),
+            dead_code_code_action(ctx, module, &mut diagnostics_with_data.clone()),
+        ]
+    });
+
     import_code_action(ctx, &mut diagnostics_with_data.clone())
         .into_iter()
         .chain(qualify_code_action(ctx, &mut diagnostics_with_data.clone()))
+        .chain(module_code_actions.flatten())
+        .chain(naming_convention_code_action(
+            ctx,
+            &mut diagnostics_with_data.clone(),
+        ))
         .reduce(|mut combined, mut curr| {
             combined.append(&mut curr);
             combined
         })
 }
+
+/// Returns a quick-fix [CodeActionOrCommand] applying the given edits of the document.
+fn quick_fix(
+    ctx: &CodeActionContext,
+    title: String,
+    text_edits: Vec<TextEdit>,
+) -> CodeActionOrCommand {
+    let changes = HashMap::from([(ctx.uri.clone(), text_edits)]);
+    CodeActionOrCommand::CodeAction(LspCodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        data: Some(Value::String(ctx.uri.to_string())),
+        ..Default::default()
+    })
+}
+
+/// Returns the functions declared at the top level of the module and in its `impl` blocks, along
+/// with their attributes.
+fn module_fns(module: &Module) -> impl Iterator<Item = (&[AttributeDecl], &ItemFn)> {
+    module.items.iter().flat_map(|item| {
+        let fns: Vec<(&[AttributeDecl], &ItemFn)> = match &item.value {
+            ItemKind::Fn(item_fn) => vec![(item.attribute_list.as_slice(), item_fn)],
+            ItemKind::Impl(item_impl) => impl_fns(item_impl).collect(),
+            _ => vec![],
+        };
+        fns
+    })
+}
+
+fn impl_fns(item_impl: &ItemImpl) -> impl Iterator<Item = (&[AttributeDecl], &ItemFn)> {
+    item_impl
+        .contents
+        .get()
+        .iter()
+        .filter_map(|impl_item| match &impl_item.value {
+            ItemImplItem::Fn(item_fn) => Some((impl_item.attribute_list.as_slice(), item_fn)),
+            _ => None,
+        })
+}
+
+/// Returns the span of the annotated item, including its attributes and doc comments.
+fn annotated_span<T: Spanned>(annotated: &Annotated<T>) -> Span {
+    match annotated.attribute_list.first() {
+        Some(attribute) => Span::join(attribute.span(), &annotated.value.span()),
+        None => annotated.value.span(),
+    }
+}
+
+/// Returns the [TextEdit] inserting the lines of text before the closing brace of the block with
+/// the given span, whose contents are indented by `indentation` spaces.
+fn insert_before_closing_brace(
+    text: &str,
+    block: &Span,
+    indentation: usize,
+    lines: &[String],
+) -> TextEdit {
+    let closing_brace = block.end() - 1;
+    let line_start = text[..closing_brace].rfind('\n').map_or(0, |i| i + 1);
+    let lines = lines
+        .iter()
+        .map(|line| format!("{}{line}\n", " ".repeat(indentation)))
+        .collect::<String>();
+    let position = get_range_from_span(block).end;
+    let closing_brace_position = Position::new(position.line, position.character - 1);
+    if text[line_start..closing_brace].trim().is_empty() {
+        // The closing brace starts its line, so the lines go right before that line.
+        let position = Position::new(closing_brace_position.line, 0);
+        TextEdit {
+            range: Range::new(position, position),
+            new_text: lines,
+        }
+    } else {
+        let brace_indentation = indentation.saturating_sub(4);
+        TextEdit {
+            range: Range::new(closing_brace_position, closing_brace_position),
+            new_text: format!("\n{lines}{}", " ".repeat(brace_indentation)),
+        }
+    }
+}
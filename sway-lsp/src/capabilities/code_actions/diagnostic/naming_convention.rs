@@ -0,0 +1,42 @@
+use crate::capabilities::{
+    code_actions::{CodeActionContext, CODE_ACTION_RENAME_TITLE},
+    diagnostic::DiagnosticData,
+    rename::rename,
+};
+use lsp_types::{CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Range};
+use serde_json::Value;
+
+/// Returns a list of [CodeActionOrCommand] suggestions for renaming a declaration whose name does
+/// not follow the naming conventions, along with all of its references.
+pub(crate) fn naming_convention_code_action(
+    ctx: &CodeActionContext,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    let actions = diagnostics
+        .filter_map(|(range, diag)| {
+            let suggested_name = diag.suggested_name?;
+            let edit = rename(
+                ctx.session.clone(),
+                &[],
+                suggested_name.clone(),
+                ctx.uri,
+                range.start,
+            )
+            .ok()?;
+
+            Some(CodeActionOrCommand::CodeAction(LspCodeAction {
+                title: format!("{CODE_ACTION_RENAME_TITLE} `{suggested_name}`"),
+                kind: Some(CodeActionKind::QUICKFIX),
+                edit: Some(edit),
+                data: Some(Value::String(ctx.uri.to_string())),
+                ..Default::default()
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    if !actions.is_empty() {
+        return Some(actions);
+    }
+
+    None
+}
@@ -0,0 +1,74 @@
+use super::{module_fns, quick_fix};
+use crate::{
+    capabilities::{
+        code_actions::{refactor::indentation, CodeActionContext, CODE_ACTION_ADD_ATTRIBUTE_TITLE},
+        diagnostic::DiagnosticData,
+    },
+    core::token::get_range_from_span,
+    utils::document::offset_of_position,
+};
+use lsp_types::{CodeActionOrCommand, Range, TextEdit};
+use sway_ast::Module;
+use sway_types::{constants::STORAGE_PURITY_ATTRIBUTE_NAME, Spanned};
+
+/// Returns a list of [CodeActionOrCommand] suggestions for declaring the storage attributes a
+/// function needs for the storage accesses in its body.
+pub(crate) fn storage_attributes_code_action(
+    ctx: &CodeActionContext,
+    module: &Module,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    let actions = diagnostics
+        .filter_map(|(range, diag)| {
+            let storage_attributes = diag.storage_attributes?;
+            // The diagnostic points to the name of the function.
+            let name_start = offset_of_position(ctx.text, range.start)?;
+            let (attribute_list, item_fn) = module_fns(module)
+                .find(|(_, item_fn)| item_fn.fn_signature.name.span().start() == name_start)?;
+
+            let attribute = format!("#[{STORAGE_PURITY_ATTRIBUTE_NAME}({storage_attributes})]");
+            let existing_attribute = attribute_list.iter().find(|attribute_decl| {
+                attribute_decl
+                    .attribute
+                    .get()
+                    .into_iter()
+                    .any(|attribute| attribute.name.as_str() == STORAGE_PURITY_ATTRIBUTE_NAME)
+            });
+            let text_edit = match existing_attribute {
+                // Replace the storage attribute, unless other attributes are declared with it.
+                Some(attribute_decl) => {
+                    if attribute_decl.attribute.get().into_iter().count() > 1 {
+                        return None;
+                    }
+                    TextEdit {
+                        range: get_range_from_span(&attribute_decl.span()),
+                        new_text: attribute.clone(),
+                    }
+                }
+                None => {
+                    let fn_span = item_fn.span();
+                    let position = get_range_from_span(&fn_span).start;
+                    TextEdit {
+                        range: Range::new(position, position),
+                        new_text: format!(
+                            "{attribute}\n{}",
+                            " ".repeat(indentation(ctx.text, fn_span.start()))
+                        ),
+                    }
+                }
+            };
+
+            Some(quick_fix(
+                ctx,
+                format!("{CODE_ACTION_ADD_ATTRIBUTE_TITLE} `{attribute}`"),
+                vec![text_edit],
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if !actions.is_empty() {
+        return Some(actions);
+    }
+
+    None
+}
@@ -0,0 +1,141 @@
+use super::{insert_before_closing_brace, quick_fix};
+use crate::{
+    capabilities::{
+        code_actions::{
+            refactor::indentation, CodeActionContext, CODE_ACTION_IMPLEMENT_MISSING_TITLE,
+        },
+        diagnostic::DiagnosticData,
+    },
+    core::token::TypedAstToken,
+    utils::document::offset_of_position,
+};
+use lsp_types::{CodeActionOrCommand, Range};
+use sway_ast::{ItemKind, Module};
+use sway_core::{
+    language::ty::{self, TyTraitFn, TyTraitInterfaceItem},
+    transform::AttributeKind,
+};
+use sway_types::Spanned;
+
+/// Returns a list of [CodeActionOrCommand] suggestions for implementing the functions missing from
+/// a trait or ABI implementation.
+pub(crate) fn trait_items_code_action(
+    ctx: &CodeActionContext,
+    module: &Module,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    let actions = diagnostics
+        .filter_map(|(range, diag)| {
+            let missing_functions = diag.missing_functions?;
+            let offset = offset_of_position(ctx.text, range.start)?;
+            let (item_impl, item_span) =
+                module.items.iter().find_map(|item| match &item.value {
+                    ItemKind::Impl(item_impl)
+                        if item_impl.trait_opt.is_some()
+                            && item.value.span().start() <= offset
+                            && offset < item.value.span().end() =>
+                    {
+                        Some((item_impl, item.value.span()))
+                    }
+                    _ => None,
+                })?;
+            let (trait_path, _) = item_impl.trait_opt.as_ref()?;
+            let trait_name = trait_path
+                .suffix
+                .last()
+                .map_or(&trait_path.prefix, |(_, segment)| segment)
+                .name
+                .to_string();
+
+            let interface_surface = ctx.tokens.tokens_for_name(&trait_name).find_map(|item| {
+                match &item.value().typed {
+                    Some(TypedAstToken::TypedDeclaration(ty::TyDecl::TraitDecl(
+                        ty::TraitDecl { decl_id, .. },
+                    ))) => Some(
+                        ctx.engines
+                            .de()
+                            .get_trait(decl_id)
+                            .interface_surface
+                            .clone(),
+                    ),
+                    Some(TypedAstToken::TypedDeclaration(ty::TyDecl::AbiDecl(ty::AbiDecl {
+                        decl_id,
+                        ..
+                    }))) => Some(ctx.engines.de().get_abi(decl_id).interface_surface.clone()),
+                    _ => None,
+                }
+            })?;
+            let fn_stubs = interface_surface
+                .iter()
+                .filter_map(|item| match item {
+                    TyTraitInterfaceItem::TraitFn(fn_ref) => {
+                        let trait_fn = ctx.engines.de().get_trait_fn(fn_ref);
+                        missing_functions
+                            .contains(&trait_fn.name.to_string())
+                            .then(|| fn_stub(ctx, &trait_fn))
+                    }
+                    _ => None,
+                })
+                .flatten()
+                .collect::<Vec<_>>();
+            if fn_stubs.is_empty() {
+                return None;
+            }
+
+            let fn_indentation = indentation(ctx.text, item_span.start()) + 4;
+            let text_edit = insert_before_closing_brace(
+                ctx.text,
+                &item_impl.contents.span(),
+                fn_indentation,
+                &fn_stubs,
+            );
+
+            Some(quick_fix(
+                ctx,
+                format!("{CODE_ACTION_IMPLEMENT_MISSING_TITLE} `{trait_name}`"),
+                vec![text_edit],
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if !actions.is_empty() {
+        return Some(actions);
+    }
+
+    None
+}
+
+/// Returns the lines of the function implementing the trait function, with an empty body.
+fn fn_stub(ctx: &CodeActionContext, trait_fn: &TyTraitFn) -> Vec<String> {
+    let mut lines = trait_fn
+        .attributes
+        .get(&AttributeKind::Storage)
+        .into_iter()
+        .flatten()
+        .map(|attribute| attribute.span.as_str().to_string())
+        .collect::<Vec<_>>();
+    let params = trait_fn
+        .parameters
+        .iter()
+        .map(|param| {
+            if param.is_self() {
+                let ref_mut = if param.is_reference && param.is_mutable {
+                    "ref mut "
+                } else {
+                    ""
+                };
+                format!("{ref_mut}{}", param.name)
+            } else {
+                format!("{}: {}", param.name, param.type_argument.span.as_str())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let return_type = if ctx.engines.te().get(trait_fn.return_type.type_id).is_unit() {
+        String::new()
+    } else {
+        format!(" -> {}", trait_fn.return_type.span.as_str())
+    };
+    lines.push(format!("fn {}({params}){return_type} {{}}", trait_fn.name));
+    lines
+}
@@ -5,6 +5,7 @@ pub mod diagnostic;
 pub mod enum_decl;
 pub mod enum_variant;
 pub mod function_decl;
+pub(crate) mod refactor;
 pub mod storage_field;
 pub mod struct_decl;
 pub mod struct_field;
@@ -30,43 +31,53 @@ pub(crate) const CODE_ACTION_NEW_TITLE: &str = "Generate `new`";
 pub(crate) const CODE_ACTION_DOC_TITLE: &str = "Generate a documentation template";
 pub(crate) const CODE_ACTION_IMPORT_TITLE: &str = "Import";
 pub(crate) const CODE_ACTION_QUALIFY_TITLE: &str = "Qualify as";
+pub(crate) const CODE_ACTION_ADD_ATTRIBUTE_TITLE: &str = "Add";
+pub(crate) const CODE_ACTION_ADD_MATCH_ARMS_TITLE: &str = "Add missing match arms";
+pub(crate) const CODE_ACTION_IMPLEMENT_MISSING_TITLE: &str = "Implement missing functions of";
+pub(crate) const CODE_ACTION_REMOVE_UNUSED_TITLE: &str = "Remove unused declaration";
+pub(crate) const CODE_ACTION_RENAME_TITLE: &str = "Rename to";
+pub(crate) const CODE_ACTION_EXTRACT_VARIABLE_TITLE: &str = "Extract into variable";
+pub(crate) const CODE_ACTION_EXTRACT_FUNCTION_TITLE: &str = "Extract into function";
+pub(crate) const CODE_ACTION_INLINE_VARIABLE_TITLE: &str = "Inline variable";
+pub(crate) const CODE_ACTION_IF_LET_TO_MATCH_TITLE: &str = "Convert `if let` to `match`";
+pub(crate) const CODE_ACTION_MATCH_TO_IF_LET_TITLE: &str = "Convert `match` to `if let`";
 
 #[derive(Clone)]
 pub(crate) struct CodeActionContext<'a> {
     engines: &'a Engines,
     tokens: &'a TokenMap,
-    token: &'a Token,
+    token: Option<&'a Token>,
+    session: &'a Arc<Session>,
     uri: &'a Url,
-    temp_uri: &'a Url,
     diagnostics: &'a Vec<Diagnostic>,
     namespace: &'a Option<Namespace>,
+    text: &'a str,
 }
 
 pub fn code_actions(
     session: Arc<Session>,
     range: &Range,
     uri: &Url,
-    temp_uri: &Url,
     diagnostics: &Vec<Diagnostic>,
+    text: &str,
 ) -> Option<CodeActionResponse> {
-    let t = session
-        .token_map()
-        .token_at_position(temp_uri, range.start)?;
-    let token = t.value();
+    let refactor_actions = refactor::code_actions(&session, range, uri, text);
+    let t = session.token_map().token_at_position(uri, range.start);
+    let token = t.as_ref().map(|t| t.value());
 
     let ctx = CodeActionContext {
         engines: &session.engines.read(),
         tokens: session.token_map(),
         token,
+        session: &session,
         uri,
-        temp_uri,
         diagnostics,
         namespace: &session.namespace(),
+        text,
     };
 
     let actions_by_type = token
-        .typed
-        .as_ref()
+        .and_then(|token| token.typed.as_ref())
         .map(|typed_token| match typed_token {
             TypedAstToken::TypedDeclaration(decl) => match decl {
                 ty::TyDecl::AbiDecl(ty::AbiDecl { decl_id, .. }) => {
@@ -96,7 +107,9 @@ pub fn code_actions(
 
     let actions_by_diagnostic = diagnostic::code_actions(&ctx).unwrap_or_default();
 
-    Some([actions_by_type, actions_by_diagnostic].concat())
+    let actions = [actions_by_type, actions_by_diagnostic, refactor_actions].concat();
+    // Quick-fixes and refactorings apply anywhere, not only on tokens.
+    (token.is_some() || !actions.is_empty()).then_some(actions)
 }
 
 pub(crate) trait CodeAction<'a, T: Spanned> {
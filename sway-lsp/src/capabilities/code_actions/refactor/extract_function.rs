@@ -0,0 +1,220 @@
+use super::{
+    contains_word, enclosing_fn, indentation, overlaps, reindent, unused_name, visit_expr, FnBody,
+    RefactorContext,
+};
+use crate::{
+    capabilities::code_actions::CODE_ACTION_EXTRACT_FUNCTION_TITLE, core::token::TypedAstToken,
+};
+use lsp_types::{CodeActionKind, CodeActionOrCommand, TextEdit};
+use sway_ast::{Expr, Statement};
+use sway_core::language::ty::{
+    TyDecl, TyExpression, TyExpressionVariant, TyVariableDecl, VariableMutability,
+};
+use sway_types::Spanned;
+
+/// A variable passed to or returned from the extracted function.
+#[derive(Clone)]
+struct Variable {
+    name: String,
+    ty: String,
+    mutable: bool,
+}
+
+/// Returns a [CodeActionOrCommand] moving the selected statements of a function body into a new
+/// function, and replacing them with a call to it.
+///
+/// The variables used by the statements but declared before them become the parameters of the new
+/// function, and those declared by the statements and used after them are returned from it.
+pub(crate) fn code_action(ctx: &RefactorContext, fns: &[FnBody]) -> Option<CodeActionOrCommand> {
+    if ctx.selection.is_empty() {
+        return None;
+    }
+    let body = enclosing_fn(fns, &ctx.selection)?;
+    let block = body.item_fn.body.get();
+    let selected = block
+        .statements
+        .iter()
+        .filter(|statement| overlaps(&statement.span(), &ctx.selection))
+        .collect::<Vec<_>>();
+    let offsets = selected.first()?.span().start()..selected.last()?.span().end();
+    // Only whole statements can be extracted, and the final expression of the body cannot.
+    let final_expr_selected = block
+        .final_expr_opt
+        .as_ref()
+        .is_some_and(|expr| overlaps(&expr.span(), &ctx.selection));
+    if final_expr_selected
+        || offsets.start < ctx.selection.start
+        || ctx.selection.end < offsets.end
+        || selected
+            .iter()
+            .any(|statement| matches!(statement, Statement::Item(_) | Statement::Error(..)))
+    {
+        return None;
+    }
+    // A free function can neither return from the enclosing function nor use its `self`.
+    let statements_text = &ctx.text[offsets.clone()];
+    if ["return", "self", "Self"]
+        .iter()
+        .any(|word| contains_word(statements_text, word))
+    {
+        return None;
+    }
+
+    let engines = ctx.session.engines.read();
+    let mut declarations = vec![];
+    let mut uses = vec![];
+    for item in ctx.session.token_map().tokens_for_file(ctx.uri) {
+        let Some(offset) = ctx.offset(&item.key().range) else {
+            continue;
+        };
+        match &item.value().typed {
+            Some(TypedAstToken::TypedDeclaration(TyDecl::VariableDecl(var_decl)))
+                if offsets.contains(&offset) =>
+            {
+                let TyVariableDecl {
+                    name,
+                    mutability,
+                    return_type,
+                    ..
+                } = var_decl.as_ref();
+                declarations.push((
+                    offset,
+                    Variable {
+                        name: name.to_string(),
+                        ty: engines.help_out(*return_type).to_string(),
+                        mutable: *mutability != VariableMutability::Immutable,
+                    },
+                ));
+            }
+            Some(TypedAstToken::TypedExpression(TyExpression {
+                expression:
+                    TyExpressionVariant::VariableExpression {
+                        name, mutability, ..
+                    },
+                return_type,
+                ..
+            })) => uses.push((
+                offset,
+                name.span().start(),
+                Variable {
+                    name: name.to_string(),
+                    ty: engines.help_out(*return_type).to_string(),
+                    mutable: *mutability != VariableMutability::Immutable,
+                },
+            )),
+            _ => {}
+        }
+    }
+    uses.sort_by_key(|(offset, _, _)| *offset);
+    declarations.sort_by_key(|(offset, _)| *offset);
+
+    // The variables declared before the statements and used within them are passed by value, so
+    // the statements must not reassign them.
+    let mut params: Vec<(usize, Variable)> = vec![];
+    for (offset, declaration, variable) in &uses {
+        if offsets.contains(offset)
+            && *declaration < offsets.start
+            && !params.iter().any(|(param, _)| param == declaration)
+        {
+            params.push((*declaration, variable.clone()));
+        }
+    }
+    if params.iter().any(|(_, param)| param.mutable) {
+        return None;
+    }
+    let returned = declarations
+        .into_iter()
+        .filter(|(declaration, _)| {
+            uses.iter()
+                .any(|(offset, used, _)| *offset >= offsets.end && used == declaration)
+        })
+        .map(|(_, variable)| variable)
+        .collect::<Vec<_>>();
+    if has_reassignment(&selected) {
+        return None;
+    }
+
+    let fn_name = unused_name(ctx.text, "extracted_function");
+    let params_text = params
+        .iter()
+        .map(|(_, param)| format!("{}: {}", param.name, param.ty))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let args_text = params
+        .iter()
+        .map(|(_, param)| param.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let binding = |variable: &Variable| {
+        let mut_token = if variable.mutable { "mut " } else { "" };
+        format!("{mut_token}{}", variable.name)
+    };
+    let join =
+        |f: &dyn Fn(&Variable) -> String| returned.iter().map(f).collect::<Vec<_>>().join(", ");
+    let (return_type, call, tail) = match returned.as_slice() {
+        [] => (String::new(), format!("{fn_name}({args_text});"), None),
+        [variable] => (
+            format!(" -> {}", variable.ty),
+            format!("let {} = {fn_name}({args_text});", binding(variable)),
+            Some(variable.name.clone()),
+        ),
+        _ => (
+            format!(" -> ({})", join(&|variable| variable.ty.clone())),
+            format!("let ({}) = {fn_name}({args_text});", join(&binding)),
+            Some(format!("({})", join(&|variable| variable.name.clone()))),
+        ),
+    };
+
+    let mut fn_body = format!(
+        "    {}",
+        reindent(statements_text, indentation(ctx.text, offsets.start), 4)
+    );
+    if let Some(tail) = tail {
+        fn_body.push_str(&format!("\n    {tail}"));
+    }
+    // The new function accesses storage like the function it is extracted from.
+    let attributes = if contains_word(statements_text, "storage") {
+        body.attributes
+            .iter()
+            .map(|attribute| attribute.span().as_str().to_string())
+            .filter(|attribute| attribute.starts_with("#[storage"))
+            .map(|attribute| format!("{attribute}\n"))
+            .collect::<String>()
+    } else {
+        String::new()
+    };
+    let item_end = body.item_span.end();
+    let text_edits = vec![
+        TextEdit {
+            range: ctx.range(offsets),
+            new_text: call,
+        },
+        TextEdit {
+            range: ctx.range(item_end..item_end),
+            new_text: format!(
+                "\n\n{attributes}fn {fn_name}({params_text}){return_type} {{\n{fn_body}\n}}"
+            ),
+        },
+    ];
+    Some(ctx.code_action(
+        CODE_ACTION_EXTRACT_FUNCTION_TITLE,
+        CodeActionKind::REFACTOR_EXTRACT,
+        text_edits,
+    ))
+}
+
+/// Returns true if any of the statements reassigns a variable.
+fn has_reassignment(statements: &[&Statement]) -> bool {
+    let mut has_reassignment = false;
+    for statement in statements {
+        let expr = match statement {
+            Statement::Let(statement_let) => &statement_let.expr,
+            Statement::Expr { expr, .. } => expr,
+            Statement::Item(_) | Statement::Error(..) => continue,
+        };
+        visit_expr(expr, &mut |expr| {
+            has_reassignment |= matches!(expr, Expr::Reassignment { .. });
+        });
+    }
+    has_reassignment
+}
@@ -0,0 +1,178 @@
+use super::{
+    children, contains, enclosing_fn, indentation, reindent, unused_name, Child, FnBody,
+    RefactorContext,
+};
+use crate::capabilities::code_actions::CODE_ACTION_EXTRACT_VARIABLE_TITLE;
+use lsp_types::{CodeActionKind, CodeActionOrCommand, TextEdit};
+use std::ops;
+use sway_ast::{CodeBlockContents, Expr, Statement};
+use sway_parse::RESERVED_KEYWORDS;
+use sway_types::Spanned;
+
+/// Returns a [CodeActionOrCommand] declaring the selected expression as a variable before the
+/// statement containing it, and replacing the expression with the variable.
+pub(crate) fn code_action(ctx: &RefactorContext, fns: &[FnBody]) -> Option<CodeActionOrCommand> {
+    if ctx.selection.is_empty() {
+        return None;
+    }
+    let body = enclosing_fn(fns, &ctx.selection)?;
+    let (statement_start, expr) = find_expr(body.item_fn.body.get(), &ctx.selection)?;
+    // Naming a path again gains nothing.
+    if matches!(expr, Expr::Path(_)) {
+        return None;
+    }
+
+    let name = unused_name(ctx.text, &suggested_name(expr));
+    let indent = indentation(ctx.text, statement_start);
+    let expr_text = reindent(
+        expr.span().as_str(),
+        indentation(ctx.text, expr.span().start()),
+        indent,
+    );
+    let text_edits = vec![
+        TextEdit {
+            range: ctx.range(statement_start..statement_start),
+            new_text: format!("let {name} = {expr_text};\n{}", " ".repeat(indent)),
+        },
+        TextEdit {
+            range: ctx.range(ctx.selection.clone()),
+            new_text: name,
+        },
+    ];
+    Some(ctx.code_action(
+        CODE_ACTION_EXTRACT_VARIABLE_TITLE,
+        CodeActionKind::REFACTOR_EXTRACT,
+        text_edits,
+    ))
+}
+
+/// Returns the expression spanning exactly the given byte offsets within the block, along with the
+/// start of the innermost statement containing it, if the expression can be evaluated before the
+/// statement.
+fn find_expr<'a>(
+    block: &'a CodeBlockContents,
+    offsets: &ops::Range<usize>,
+) -> Option<(usize, &'a Expr)> {
+    let statements = block
+        .statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Let(statement_let) => Some((statement.span(), &statement_let.expr, true)),
+            Statement::Expr { expr, .. } => Some((statement.span(), expr, false)),
+            Statement::Item(_) | Statement::Error(..) => None,
+        });
+    let final_expr = block
+        .final_expr_opt
+        .as_deref()
+        .map(|expr| (expr.span(), expr, false));
+    statements
+        .chain(final_expr)
+        .find(|(span, _, _)| contains(span, offsets))
+        .and_then(|(span, expr, extractable)| {
+            find_in_expr(expr, offsets, span.start(), true, extractable)
+        })
+}
+
+/// Searches the expression for the one spanning exactly the given byte offsets.
+///
+/// `movable` is whether the expression can be evaluated before the statement starting at
+/// `statement_start`, and `extractable` whether the expression itself may be extracted, which it
+/// cannot if it makes up the whole statement.
+fn find_in_expr<'a>(
+    expr: &'a Expr,
+    offsets: &ops::Range<usize>,
+    statement_start: usize,
+    movable: bool,
+    extractable: bool,
+) -> Option<(usize, &'a Expr)> {
+    let span = expr.span();
+    if !contains(&span, offsets) {
+        return None;
+    }
+    if span.start() == offsets.start && span.end() == offsets.end {
+        return (movable && extractable).then_some((statement_start, expr));
+    }
+    children(expr).into_iter().find_map(|child| match child {
+        Child::Expr(child, evaluated) => {
+            find_in_expr(child, offsets, statement_start, movable && evaluated, true)
+        }
+        Child::Block(block) if contains(&block.span(), offsets) => find_expr(block, offsets),
+        Child::Block(_) => None,
+    })
+}
+
+/// Returns a name for the variable holding the value of the expression.
+fn suggested_name(expr: &Expr) -> String {
+    let name = match expr {
+        Expr::MethodCall { path_seg, .. } => path_seg.name.as_str(),
+        Expr::FuncApp { func, .. } => match func.as_ref() {
+            Expr::Path(path) => path
+                .suffix
+                .last()
+                .map_or(&path.prefix, |(_, segment)| segment)
+                .name
+                .as_str(),
+            _ => "var",
+        },
+        Expr::FieldProjection { name, .. } => name.as_str(),
+        _ => "var",
+    };
+    let name = name.strip_prefix("get_").unwrap_or(name);
+    if name.is_empty() || RESERVED_KEYWORDS.contains(name) || name.starts_with(char::is_uppercase) {
+        "var".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use sway_error::handler::Handler;
+
+    /// Returns the offset of the statement preceded by the extracted variable and the expression
+    /// of the variable, if the selected text can be extracted.
+    fn extracted<'a>(text: &'a str, selected: &str) -> Option<(usize, &'a str)> {
+        let module = sway_parse::parse_file(&Handler::default(), Arc::from(text), None).unwrap();
+        let start = text.find(selected).unwrap();
+        let offsets = start..start + selected.len();
+        let fns = super::super::fn_bodies(&module.value);
+        let body = enclosing_fn(&fns, &offsets)?;
+        find_expr(body.item_fn.body.get(), &offsets).map(|(statement_start, expr)| {
+            (
+                statement_start,
+                &text[expr.span().start()..expr.span().end()],
+            )
+        })
+    }
+
+    #[test]
+    fn find_extractable_exprs() {
+        let text = "library;\n\nfn f(a: u64) -> u64 {\n    let b = foo(a + 1);\n    while a < b {\n        bar();\n    }\n    a * 2\n}\n";
+        assert_eq!(
+            extracted(text, "a + 1"),
+            Some((text.find("let b").unwrap(), "a + 1"))
+        );
+        // The statement itself, the function called and a loop condition cannot be extracted.
+        assert_eq!(extracted(text, "bar()"), None);
+        assert_eq!(extracted(text, "foo"), None);
+        assert_eq!(extracted(text, "a < b"), None);
+        assert_eq!(extracted(text, "a * 2"), None);
+        assert_eq!(
+            extracted(text, "2"),
+            Some((text.find("a * 2").unwrap(), "2"))
+        );
+    }
+
+    #[test]
+    fn suggested_names() {
+        let text = "library;\n\nfn f() {\n    let _ = x.get_value();\n    let _ = sha256(x);\n    let _ = x.field;\n}\n";
+        let module = sway_parse::parse_file(&Handler::default(), Arc::from(text), None).unwrap();
+        let fns = super::super::fn_bodies(&module.value);
+        let names = super::super::block_exprs(fns[0].item_fn.body.get())
+            .map(suggested_name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["value", "sha256", "field"]);
+    }
+}
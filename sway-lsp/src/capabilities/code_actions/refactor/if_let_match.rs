@@ -0,0 +1,197 @@
+use super::{contains, enclosing_fn, indentation, reindent, visit_exprs, FnBody, RefactorContext};
+use crate::capabilities::code_actions::{
+    CODE_ACTION_IF_LET_TO_MATCH_TITLE, CODE_ACTION_MATCH_TO_IF_LET_TITLE,
+};
+use lsp_types::{CodeActionKind, CodeActionOrCommand, TextEdit};
+use std::ops;
+use sway_ast::{
+    expr::LoopControlFlow, Expr, IfCondition, IfExpr, MatchBranch, MatchBranchKind, Pattern,
+};
+use sway_types::{Span, Spanned};
+
+/// Returns a [CodeActionOrCommand] converting the `if let` expression whose `if` keyword is under
+/// the cursor into a `match` expression, or the `match` expression whose `match` keyword is under
+/// the cursor into an `if let` expression.
+pub(crate) fn code_action(ctx: &RefactorContext, fns: &[FnBody]) -> Option<CodeActionOrCommand> {
+    let body = enclosing_fn(fns, &ctx.selection)?;
+    let mut conversion = None;
+    visit_exprs(body.item_fn.body.get(), &mut |expr| {
+        if conversion.is_some() {
+            return;
+        }
+        conversion = match expr {
+            Expr::If(if_expr) if contains(&if_expr.if_token.span(), &ctx.selection) => {
+                if_let_to_match(ctx.text, if_expr)
+                    .map(|edit| (CODE_ACTION_IF_LET_TO_MATCH_TITLE, edit))
+            }
+            Expr::Match {
+                match_token,
+                value,
+                branches,
+            } if contains(&match_token.span(), &ctx.selection) => {
+                match_to_if_let(ctx.text, &expr.span(), value, branches.get())
+                    .map(|edit| (CODE_ACTION_MATCH_TO_IF_LET_TITLE, edit))
+            }
+            _ => None,
+        };
+    });
+    let (title, (offsets, new_text)) = conversion?;
+    let text_edits = vec![TextEdit {
+        range: ctx.range(offsets),
+        new_text,
+    }];
+    Some(ctx.code_action(title, CodeActionKind::REFACTOR_REWRITE, text_edits))
+}
+
+/// Returns the byte offsets of the `if let` expression along with the `match` expression
+/// replacing it.
+///
+/// Expressions with `else if` branches are not converted.
+fn if_let_to_match(text: &str, if_expr: &IfExpr) -> Option<(ops::Range<usize>, String)> {
+    let IfCondition::Let { lhs, rhs, .. } = &if_expr.condition else {
+        return None;
+    };
+    let else_block = match &if_expr.else_opt {
+        Some((_, LoopControlFlow::Break(else_block))) => Some(else_block),
+        Some((_, LoopControlFlow::Continue(_))) => return None,
+        None => None,
+    };
+    let span = if_expr.span();
+    let indent = indentation(text, span.start());
+    let arm_indent = " ".repeat(indent + 4);
+    let then_text = reindent(if_expr.then_block.span().as_str(), indent, indent + 4);
+    let else_text = else_block.map_or("{}".to_string(), |else_block| {
+        reindent(else_block.span().as_str(), indent, indent + 4)
+    });
+    let match_text = format!(
+        "match {} {{\n{arm_indent}{} => {then_text},\n{arm_indent}_ => {else_text},\n{}}}",
+        rhs.span().as_str(),
+        lhs.span().as_str(),
+        " ".repeat(indent),
+    );
+    Some((span.start()..span.end(), match_text))
+}
+
+/// Returns the byte offsets of the `match` expression along with the `if let` expression
+/// replacing it.
+///
+/// Only expressions matching a single pattern besides the wildcard pattern are converted.
+fn match_to_if_let(
+    text: &str,
+    span: &Span,
+    value: &Expr,
+    branches: &[MatchBranch],
+) -> Option<(ops::Range<usize>, String)> {
+    let [branch, wildcard_branch] = branches else {
+        return None;
+    };
+    if matches!(
+        branch.pattern,
+        Pattern::Wildcard { .. } | Pattern::Or { .. }
+    ) || !matches!(wildcard_branch.pattern, Pattern::Wildcard { .. })
+    {
+        return None;
+    }
+    let indent = indentation(text, span.start());
+    let block_text = |kind: &MatchBranchKind| match kind {
+        MatchBranchKind::Block { block, .. } => {
+            let block = block.span();
+            reindent(
+                block.as_str(),
+                indentation(text, block.start()) + 4,
+                indent + 4,
+            )
+        }
+        MatchBranchKind::Expr { expr, .. } => {
+            let expr = expr.span();
+            format!(
+                "{{\n{}{}\n{}}}",
+                " ".repeat(indent + 4),
+                reindent(expr.as_str(), indentation(text, expr.start()), indent + 4),
+                " ".repeat(indent),
+            )
+        }
+    };
+    let else_text = match &wildcard_branch.kind {
+        MatchBranchKind::Block { block, .. }
+            if block.get().statements.is_empty() && block.get().final_expr_opt.is_none() =>
+        {
+            String::new()
+        }
+        kind => format!(" else {}", block_text(kind)),
+    };
+    let if_let_text = format!(
+        "if let {} = {} {}{else_text}",
+        branch.pattern.span().as_str(),
+        value.span().as_str(),
+        block_text(&branch.kind),
+    );
+    Some((span.start()..span.end(), if_let_text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use sway_error::handler::Handler;
+
+    /// Returns the text with the first expression found by `convert` converted.
+    fn convert(
+        text: &str,
+        convert: impl Fn(&Expr) -> Option<(ops::Range<usize>, String)>,
+    ) -> Option<String> {
+        let module = sway_parse::parse_file(&Handler::default(), Arc::from(text), None).unwrap();
+        let fns = super::super::fn_bodies(&module.value);
+        let mut converted = None;
+        visit_exprs(fns[0].item_fn.body.get(), &mut |expr| {
+            converted = converted.take().or_else(|| convert(expr));
+        });
+        converted.map(|(offsets, new_text)| {
+            format!(
+                "{}{new_text}{}",
+                &text[..offsets.start],
+                &text[offsets.end..]
+            )
+        })
+    }
+
+    #[test]
+    fn convert_if_let_to_match() {
+        let text = "library;\n\nfn f(x: Option<u64>) {\n    if let Some(y) = x {\n        foo(y);\n    } else {\n        bar();\n    }\n}\n";
+        let converted = convert(text, |expr| match expr {
+            Expr::If(if_expr) => if_let_to_match(text, if_expr),
+            _ => None,
+        });
+        assert_eq!(
+            converted.as_deref(),
+            Some("library;\n\nfn f(x: Option<u64>) {\n    match x {\n        Some(y) => {\n            foo(y);\n        },\n        _ => {\n            bar();\n        },\n    }\n}\n")
+        );
+    }
+
+    #[test]
+    fn convert_match_to_if_let() {
+        let text = "library;\n\nfn f(x: Option<u64>) {\n    match x {\n        Some(y) => foo(y),\n        _ => {},\n    }\n}\n";
+        let converted = convert(text, |expr| match expr {
+            Expr::Match {
+                value, branches, ..
+            } => match_to_if_let(text, &expr.span(), value, branches.get()),
+            _ => None,
+        });
+        assert_eq!(
+            converted.as_deref(),
+            Some("library;\n\nfn f(x: Option<u64>) {\n    if let Some(y) = x {\n        foo(y)\n    }\n}\n")
+        );
+    }
+
+    #[test]
+    fn convert_only_single_pattern_matches() {
+        let text = "library;\n\nfn f(x: u64) {\n    match x {\n        0 => foo(),\n        1 => bar(),\n        _ => {},\n    }\n}\n";
+        let converted = convert(text, |expr| match expr {
+            Expr::Match {
+                value, branches, ..
+            } => match_to_if_let(text, &expr.span(), value, branches.get()),
+            _ => None,
+        });
+        assert_eq!(converted, None);
+    }
+}
@@ -0,0 +1,195 @@
+use super::{
+    contains, enclosing_fn, visit_blocks, visit_expr, visit_exprs, FnBody, RefactorContext,
+};
+use crate::{
+    capabilities::code_actions::CODE_ACTION_INLINE_VARIABLE_TITLE, core::token::TypedAstToken,
+};
+use lsp_types::{CodeActionKind, CodeActionOrCommand, TextEdit};
+use std::ops;
+use sway_ast::{CodeBlockContents, Expr, Pattern, Statement, StatementLet};
+use sway_core::language::ty::{TyExpression, TyExpressionVariant};
+use sway_types::Spanned;
+
+/// Returns a [CodeActionOrCommand] replacing the uses of the variable declared under the cursor
+/// with the expression it is initialized with, and removing its declaration.
+pub(crate) fn code_action(ctx: &RefactorContext, fns: &[FnBody]) -> Option<CodeActionOrCommand> {
+    let body = enclosing_fn(fns, &ctx.selection)?;
+    let mut declaration = None;
+    visit_blocks(body.item_fn.body.get(), &mut |block| {
+        for statement in &block.statements {
+            if let Statement::Let(statement_let) = statement {
+                if declaration.is_none()
+                    && variable_name(statement_let)
+                        .is_some_and(|name| contains(&name.span(), &ctx.selection))
+                {
+                    declaration = Some((statement.span(), statement_let));
+                }
+            }
+        }
+    });
+    let (statement_span, statement_let) = declaration?;
+    let name = variable_name(statement_let)?;
+    let name_start = name.span().start();
+
+    let mut uses = ctx
+        .session
+        .token_map()
+        .tokens_for_file(ctx.uri)
+        // The name of a struct field initialized with the variable refers to the variable too, so
+        // the name of the token must match as well.
+        .filter_map(|item| match &item.value().typed {
+            Some(TypedAstToken::TypedExpression(TyExpression {
+                expression: TyExpressionVariant::VariableExpression { name, .. },
+                ..
+            })) if name.span().start() == name_start && item.key().name == name.as_str() => {
+                Some(item.key().range)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    uses.sort_by_key(|range| range.start);
+    let expr = &statement_let.expr;
+    // Inlining an expression in several places would evaluate its side effects several times.
+    if uses.is_empty() || (uses.len() > 1 && has_side_effects(expr)) {
+        return None;
+    }
+
+    let expr_text = if needs_parens(expr) {
+        format!("({})", expr.span().as_str())
+    } else {
+        expr.span().as_str().to_string()
+    };
+    let shorthand_fields = shorthand_fields(body.item_fn.body.get(), name.as_str());
+    let mut text_edits = vec![TextEdit {
+        range: ctx.range(statement_lines(
+            ctx.text,
+            statement_span.start()..statement_span.end(),
+        )),
+        new_text: String::new(),
+    }];
+    text_edits.extend(uses.into_iter().map(|range| {
+        // A struct field initialized with the variable of the same name keeps its name.
+        let new_text = if ctx
+            .offset(&range)
+            .is_some_and(|offset| shorthand_fields.contains(&offset))
+        {
+            format!("{name}: {}", expr.span().as_str())
+        } else {
+            expr_text.clone()
+        };
+        TextEdit { range, new_text }
+    }));
+    Some(ctx.code_action(
+        CODE_ACTION_INLINE_VARIABLE_TITLE,
+        CodeActionKind::REFACTOR_INLINE,
+        text_edits,
+    ))
+}
+
+/// Returns the name of the variable declared by the `let` statement, if it declares a single
+/// immutable variable.
+fn variable_name(statement_let: &StatementLet) -> Option<&sway_types::Ident> {
+    match &statement_let.pattern {
+        Pattern::AmbiguousSingleIdent(name)
+        | Pattern::Var {
+            reference: None,
+            mutable: None,
+            name,
+        } => Some(name),
+        _ => None,
+    }
+}
+
+/// Returns the byte offsets of the struct fields of the block initialized with the variable of the
+/// same name using the field init shorthand, as in `Foo { x }`.
+fn shorthand_fields(block: &CodeBlockContents, name: &str) -> Vec<usize> {
+    let mut offsets = vec![];
+    visit_exprs(block, &mut |expr| {
+        if let Expr::Struct { fields, .. } = expr {
+            offsets.extend(
+                fields
+                    .get()
+                    .into_iter()
+                    .filter(|field| field.expr_opt.is_none() && field.field_name.as_str() == name)
+                    .map(|field| field.field_name.span().start()),
+            );
+        }
+    });
+    offsets
+}
+
+/// Returns true if evaluating the expression may do more than compute a value, such as calling a
+/// function or changing a variable.
+fn has_side_effects(expr: &Expr) -> bool {
+    let mut has_side_effects = false;
+    visit_expr(expr, &mut |expr| {
+        has_side_effects |= matches!(
+            expr,
+            Expr::FuncApp { .. }
+                | Expr::MethodCall { .. }
+                | Expr::AbiCast { .. }
+                | Expr::Asm(_)
+                | Expr::Reassignment { .. }
+                | Expr::Return { .. }
+                | Expr::Break { .. }
+                | Expr::Continue { .. }
+                | Expr::While { .. }
+                | Expr::For { .. }
+        );
+    });
+    has_side_effects
+}
+
+/// Returns true if the expression must be parenthesized to keep its meaning wherever it replaces
+/// a variable.
+fn needs_parens(expr: &Expr) -> bool {
+    !matches!(
+        expr,
+        Expr::Path(_)
+            | Expr::Literal(_)
+            | Expr::FuncApp { .. }
+            | Expr::MethodCall { .. }
+            | Expr::FieldProjection { .. }
+            | Expr::TupleFieldProjection { .. }
+            | Expr::Index { .. }
+            | Expr::Parens(_)
+            | Expr::Tuple(_)
+            | Expr::Array(_)
+            | Expr::Struct { .. }
+    )
+}
+
+/// Returns the byte offsets of the statement, extended to its whole lines if it is alone on them.
+fn statement_lines(text: &str, offsets: ops::Range<usize>) -> ops::Range<usize> {
+    let line_start = text[..offsets.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[offsets.end..]
+        .find('\n')
+        .map_or(text.len(), |i| offsets.end + i + 1);
+    if text[line_start..offsets.start].trim().is_empty()
+        && text[offsets.end..line_end].trim().is_empty()
+    {
+        line_start..line_end
+    } else {
+        offsets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statement_lines_of_statements() {
+        let text = "fn f() {\n    let a = 1;\n    let b = 2; let c = 3;\n}\n";
+        let start = text.find("let a").unwrap();
+        assert_eq!(
+            &text[statement_lines(text, start..start + 10)],
+            "    let a = 1;\n"
+        );
+        let start = text.find("let c").unwrap();
+        assert_eq!(
+            &text[statement_lines(text, start..start + 10)],
+            "let c = 3;"
+        );
+    }
+}
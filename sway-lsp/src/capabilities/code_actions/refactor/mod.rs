@@ -0,0 +1,431 @@
+pub(crate) mod extract_function;
+pub(crate) mod extract_variable;
+pub(crate) mod if_let_match;
+pub(crate) mod inline_variable;
+
+use crate::{
+    core::session::Session,
+    utils::document::{offset_of_position, position_of_offset},
+};
+use lsp_types::{
+    CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+use serde_json::Value;
+use std::{collections::HashMap, ops, sync::Arc};
+use sway_ast::{
+    expr::LoopControlFlow, AttributeDecl, CodeBlockContents, Expr, ExprArrayDescriptor,
+    ExprTupleDescriptor, IfCondition, IfExpr, ItemFn, ItemImplItem, ItemKind, MatchBranchKind,
+    Module, Statement,
+};
+use sway_error::handler::Handler;
+use sway_types::{Span, Spanned};
+
+/// The context of the refactorings of the selected range of a document.
+pub(crate) struct RefactorContext<'a> {
+    session: &'a Session,
+    uri: &'a Url,
+    text: &'a str,
+    /// The byte offsets of the selected range, excluding the whitespace around it.
+    selection: ops::Range<usize>,
+}
+
+impl RefactorContext<'_> {
+    /// Returns the [Range] of the given byte offsets of the document.
+    fn range(&self, offsets: ops::Range<usize>) -> Range {
+        Range::new(
+            position_of_offset(self.text, offsets.start),
+            position_of_offset(self.text, offsets.end),
+        )
+    }
+
+    /// Returns the byte offset of the given [Range]'s start in the document.
+    fn offset(&self, range: &Range) -> Option<usize> {
+        offset_of_position(self.text, range.start)
+    }
+
+    /// Returns a [CodeActionOrCommand] applying the given edits of the document.
+    fn code_action(
+        &self,
+        title: &str,
+        kind: CodeActionKind,
+        text_edits: Vec<TextEdit>,
+    ) -> CodeActionOrCommand {
+        let changes = HashMap::from([(self.uri.clone(), text_edits)]);
+        CodeActionOrCommand::CodeAction(LspCodeAction {
+            title: title.to_string(),
+            kind: Some(kind),
+            edit: Some(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            }),
+            data: Some(Value::String(self.uri.to_string())),
+            ..Default::default()
+        })
+    }
+}
+
+/// A function declared in a module.
+pub(crate) struct FnBody<'a> {
+    item_fn: &'a ItemFn,
+    attributes: &'a [AttributeDecl],
+    /// The span of the item declaring the function at the top level of the module, which is
+    /// either the function itself or the `impl` block containing it.
+    item_span: Span,
+}
+
+/// Returns the refactorings of the selected range of the document.
+///
+/// Unlike the other code actions, these apply to any code within function bodies rather than to
+/// the declaration under the cursor.
+pub(crate) fn code_actions(
+    session: &Session,
+    range: &Range,
+    uri: &Url,
+    text: &str,
+) -> Vec<CodeActionOrCommand> {
+    let (Some(start), Some(end)) = (
+        offset_of_position(text, range.start),
+        offset_of_position(text, range.end),
+    ) else {
+        return vec![];
+    };
+    let Ok(module) = sway_parse::parse_file(&Handler::default(), Arc::from(text), None) else {
+        return vec![];
+    };
+    let selected = &text[start..end];
+    let start = start + (selected.len() - selected.trim_start().len());
+    let end = (end - (selected.len() - selected.trim_end().len())).max(start);
+    let ctx = RefactorContext {
+        session,
+        uri,
+        text,
+        selection: start..end,
+    };
+    let fns = fn_bodies(&module.value);
+    [
+        extract_variable::code_action(&ctx, &fns),
+        extract_function::code_action(&ctx, &fns),
+        inline_variable::code_action(&ctx, &fns),
+        if_let_match::code_action(&ctx, &fns),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Returns the functions of the module, including those of its `impl` blocks.
+fn fn_bodies(module: &Module) -> Vec<FnBody<'_>> {
+    let mut fns = vec![];
+    for item in &module.items {
+        match &item.value {
+            ItemKind::Fn(item_fn) => fns.push(FnBody {
+                item_fn,
+                attributes: &item.attribute_list,
+                item_span: item.span(),
+            }),
+            ItemKind::Impl(item_impl) => {
+                for impl_item in item_impl.contents.get() {
+                    if let ItemImplItem::Fn(item_fn) = &impl_item.value {
+                        fns.push(FnBody {
+                            item_fn,
+                            attributes: &impl_item.attribute_list,
+                            item_span: item.span(),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    fns
+}
+
+/// Returns the function whose body contains the given byte offsets.
+fn enclosing_fn<'a, 'b>(
+    fns: &'b [FnBody<'a>],
+    offsets: &ops::Range<usize>,
+) -> Option<&'b FnBody<'a>> {
+    fns.iter()
+        .find(|body| contains(&body.item_fn.body.span(), offsets))
+}
+
+/// A node nested directly within an expression.
+pub(crate) enum Child<'a> {
+    /// An expression, and whether it is evaluated whenever its parent is, so that it can be
+    /// evaluated before the statement containing its parent instead.
+    Expr(&'a Expr, bool),
+    /// A block of statements.
+    Block(&'a CodeBlockContents),
+}
+
+/// Returns the expressions and blocks nested directly within the expression.
+pub(crate) fn children(expr: &Expr) -> Vec<Child<'_>> {
+    let mut children = vec![];
+    match expr {
+        Expr::AbiCast { args, .. } => children.push(Child::Expr(&args.get().address, true)),
+        Expr::Struct { fields, .. } => children.extend(
+            fields
+                .get()
+                .into_iter()
+                .filter_map(|field| field.expr_opt.as_ref())
+                .map(|(_, expr)| Child::Expr(expr, true)),
+        ),
+        Expr::Tuple(tuple) => {
+            if let ExprTupleDescriptor::Cons { head, tail, .. } = tuple.get() {
+                children.push(Child::Expr(head, true));
+                children.extend(tail.into_iter().map(|expr| Child::Expr(expr, true)));
+            }
+        }
+        Expr::Parens(parens) => children.push(Child::Expr(parens.get(), true)),
+        Expr::Block(block) => children.push(Child::Block(block.get())),
+        Expr::Array(array) => match array.get() {
+            ExprArrayDescriptor::Sequence(elements) => {
+                children.extend(elements.into_iter().map(|expr| Child::Expr(expr, true)))
+            }
+            ExprArrayDescriptor::Repeat { value, length, .. } => {
+                children.push(Child::Expr(value, true));
+                children.push(Child::Expr(length, true));
+            }
+        },
+        Expr::Return { expr_opt, .. } => {
+            children.extend(expr_opt.iter().map(|expr| Child::Expr(expr, true)))
+        }
+        Expr::If(if_expr) => if_children(if_expr, true, &mut children),
+        Expr::Match {
+            value, branches, ..
+        } => {
+            children.push(Child::Expr(value, true));
+            for branch in branches.get() {
+                children.push(match &branch.kind {
+                    MatchBranchKind::Block { block, .. } => Child::Block(block.get()),
+                    MatchBranchKind::Expr { expr, .. } => Child::Expr(expr, false),
+                });
+            }
+        }
+        Expr::While {
+            condition, block, ..
+        } => {
+            children.push(Child::Expr(condition, false));
+            children.push(Child::Block(block.get()));
+        }
+        Expr::For {
+            iterator, block, ..
+        } => {
+            children.push(Child::Expr(iterator, true));
+            children.push(Child::Block(block.get()));
+        }
+        // The function itself is a path that cannot be evaluated on its own.
+        Expr::FuncApp { func, args } => {
+            children.push(Child::Expr(func, false));
+            children.extend(args.get().into_iter().map(|expr| Child::Expr(expr, true)));
+        }
+        Expr::Index { target, arg } => {
+            children.push(Child::Expr(target, true));
+            children.push(Child::Expr(arg.get(), true));
+        }
+        Expr::MethodCall {
+            target,
+            contract_args_opt,
+            args,
+            ..
+        } => {
+            children.push(Child::Expr(target, true));
+            if let Some(contract_args) = contract_args_opt {
+                children.extend(
+                    contract_args
+                        .get()
+                        .into_iter()
+                        .filter_map(|field| field.expr_opt.as_ref())
+                        .map(|(_, expr)| Child::Expr(expr, true)),
+                );
+            }
+            children.extend(args.get().into_iter().map(|expr| Child::Expr(expr, true)));
+        }
+        Expr::FieldProjection { target, .. } | Expr::TupleFieldProjection { target, .. } => {
+            children.push(Child::Expr(target, true))
+        }
+        Expr::Ref { expr, .. } | Expr::Deref { expr, .. } | Expr::Not { expr, .. } => {
+            children.push(Child::Expr(expr, true))
+        }
+        // The right-hand side of a lazy operator is only evaluated depending on the left-hand side.
+        Expr::LogicalAnd { lhs, rhs, .. } | Expr::LogicalOr { lhs, rhs, .. } => {
+            children.push(Child::Expr(lhs, true));
+            children.push(Child::Expr(rhs, false));
+        }
+        Expr::Mul { lhs, rhs, .. }
+        | Expr::Div { lhs, rhs, .. }
+        | Expr::Pow { lhs, rhs, .. }
+        | Expr::Modulo { lhs, rhs, .. }
+        | Expr::Add { lhs, rhs, .. }
+        | Expr::Sub { lhs, rhs, .. }
+        | Expr::Shl { lhs, rhs, .. }
+        | Expr::Shr { lhs, rhs, .. }
+        | Expr::BitAnd { lhs, rhs, .. }
+        | Expr::BitXor { lhs, rhs, .. }
+        | Expr::BitOr { lhs, rhs, .. }
+        | Expr::Equal { lhs, rhs, .. }
+        | Expr::NotEqual { lhs, rhs, .. }
+        | Expr::LessThan { lhs, rhs, .. }
+        | Expr::GreaterThan { lhs, rhs, .. }
+        | Expr::LessThanEq { lhs, rhs, .. }
+        | Expr::GreaterThanEq { lhs, rhs, .. } => {
+            children.push(Child::Expr(lhs, true));
+            children.push(Child::Expr(rhs, true));
+        }
+        Expr::Reassignment { expr, .. } => children.push(Child::Expr(expr, true)),
+        Expr::Error(..)
+        | Expr::Path(_)
+        | Expr::Literal(_)
+        | Expr::Asm(_)
+        | Expr::Break { .. }
+        | Expr::Continue { .. } => {}
+    }
+    children
+}
+
+/// Pushes the conditions and blocks of the `if` expression and its `else if` branches, whose
+/// conditions are only evaluated depending on the previous ones.
+fn if_children<'a>(if_expr: &'a IfExpr, evaluated: bool, children: &mut Vec<Child<'a>>) {
+    match &if_expr.condition {
+        IfCondition::Expr(condition) => children.push(Child::Expr(condition, evaluated)),
+        IfCondition::Let { rhs, .. } => children.push(Child::Expr(rhs, evaluated)),
+    }
+    children.push(Child::Block(if_expr.then_block.get()));
+    match &if_expr.else_opt {
+        Some((_, LoopControlFlow::Continue(else_if_expr))) => {
+            if_children(else_if_expr, false, children)
+        }
+        Some((_, LoopControlFlow::Break(else_block))) => {
+            children.push(Child::Block(else_block.get()))
+        }
+        None => {}
+    }
+}
+
+/// Returns the expressions of the statements and the final expression of the block.
+pub(crate) fn block_exprs(block: &CodeBlockContents) -> impl Iterator<Item = &Expr> {
+    block
+        .statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Let(statement_let) => Some(&statement_let.expr),
+            Statement::Expr { expr, .. } => Some(expr),
+            Statement::Item(_) | Statement::Error(..) => None,
+        })
+        .chain(block.final_expr_opt.as_deref())
+}
+
+/// Calls `f` with each block nested within the block, including itself, outermost first.
+pub(crate) fn visit_blocks<'a>(
+    block: &'a CodeBlockContents,
+    f: &mut impl FnMut(&'a CodeBlockContents),
+) {
+    f(block);
+    for expr in block_exprs(block) {
+        visit_expr_blocks(expr, f);
+    }
+}
+
+fn visit_expr_blocks<'a>(expr: &'a Expr, f: &mut impl FnMut(&'a CodeBlockContents)) {
+    for child in children(expr) {
+        match child {
+            Child::Expr(expr, _) => visit_expr_blocks(expr, f),
+            Child::Block(block) => visit_blocks(block, f),
+        }
+    }
+}
+
+/// Calls `f` with each expression within the block, outermost first.
+pub(crate) fn visit_exprs<'a>(block: &'a CodeBlockContents, f: &mut impl FnMut(&'a Expr)) {
+    for expr in block_exprs(block) {
+        visit_expr(expr, f);
+    }
+}
+
+fn visit_expr<'a>(expr: &'a Expr, f: &mut impl FnMut(&'a Expr)) {
+    f(expr);
+    for child in children(expr) {
+        match child {
+            Child::Expr(expr, _) => visit_expr(expr, f),
+            Child::Block(block) => visit_exprs(block, f),
+        }
+    }
+}
+
+/// Returns true if the span contains the byte offsets.
+fn contains(span: &Span, offsets: &ops::Range<usize>) -> bool {
+    span.start() <= offsets.start && offsets.end <= span.end()
+}
+
+/// Returns true if the span and the byte offsets overlap.
+fn overlaps(span: &Span, offsets: &ops::Range<usize>) -> bool {
+    span.start() < offsets.end && offsets.start < span.end()
+}
+
+/// Returns true if the text contains the given word.
+fn contains_word(text: &str, word: &str) -> bool {
+    words(text).any(|text_word| text_word == word)
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+}
+
+/// Returns the given name, or the name followed by the first number making it unused in the
+/// text.
+fn unused_name(text: &str, name: &str) -> String {
+    let used = words(text).collect::<Vec<_>>();
+    std::iter::once(name.to_string())
+        .chain((1..).map(|i| format!("{name}{i}")))
+        .find(|candidate| !used.contains(&candidate.as_str()))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Returns the number of spaces indenting the line of the given byte offset.
+pub(crate) fn indentation(text: &str, offset: usize) -> usize {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[line_start..];
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Moves the lines of the text following its first line from the indentation `from` to the
+/// indentation `to`.
+fn reindent(text: &str, from: usize, to: usize) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.trim().is_empty() {
+                return line.trim_end().to_string();
+            }
+            let indentation = line.len() - line.trim_start_matches(' ').len();
+            let indentation = (indentation + to).saturating_sub(from);
+            format!(
+                "{}{}",
+                " ".repeat(indentation),
+                line.trim_start_matches(' ')
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_names() {
+        assert_eq!(unused_name("let a = b;", "c"), "c");
+        assert_eq!(unused_name("let c = c1;", "c"), "c2");
+    }
+
+    #[test]
+    fn reindent_lines() {
+        assert_eq!(
+            reindent("foo {\n        bar\n\n    }", 4, 8),
+            "foo {\n            bar\n\n        }"
+        );
+        assert_eq!(reindent("foo {\n    bar\n}", 4, 0), "foo {\nbar\n}");
+    }
+}
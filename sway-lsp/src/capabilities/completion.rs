@@ -1,23 +1,371 @@
-use crate::core::token::TokenIdent;
+use crate::{
+    capabilities::{
+        code_actions::diagnostic::auto_import::{import_call_path, FileImports},
+        workspace_symbol::{match_name, NameMatch},
+    },
+    core::{
+        session::Session,
+        token::{get_range_from_span, TypedAstToken},
+    },
+    utils::document::{offset_of_position, position_of_offset},
+};
 use lsp_types::{
-    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit, Position,
-    Range, TextEdit,
+    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
+    InsertTextFormat, Position, Range, TextEdit, Url,
 };
+use std::{collections::HashSet, sync::Arc};
 use sway_core::{
-    language::ty::{TyAstNodeContent, TyDecl, TyFunctionDecl, TyFunctionParameter},
-    namespace::Items,
+    language::ty::{self, TyAstNodeContent, TyDecl, TyFunctionDecl, TyFunctionParameter},
+    namespace::{Items, Module, Namespace, ResolvedDeclaration},
     Engines, TypeId, TypeInfo,
 };
+use sway_error::handler::Handler;
+use sway_types::{constants, Ident};
 
-pub(crate) fn to_completion_items(
-    namespace: &Items,
-    engines: &Engines,
-    ident_to_complete: &TokenIdent,
-    fn_decl: &TyFunctionDecl,
+/// The argument of `#[test]` marking tests that are expected to revert.
+const TEST_SHOULD_REVERT_NAME: &str = "should_revert";
+
+/// The position at which completion was requested, as determined from the text of the document.
+#[derive(Debug, PartialEq, Eq)]
+struct CompletionContext {
+    kind: ContextKind,
+    /// The partially typed identifier before the cursor, which is replaced by the completion.
+    prefix: String,
+    /// The range of the partially typed identifier.
+    prefix_range: Range,
+}
+
+/// What is being typed at the position at which completion was requested.
+#[derive(Debug, PartialEq, Eq)]
+enum ContextKind {
+    /// A field or method after a `.` following an expression, e.g. `foo.ba`. `receiver` is the
+    /// text of the expression and `receiver_position` the position of its last character.
+    Member {
+        receiver: String,
+        receiver_position: Position,
+    },
+    /// A storage field or namespace after `storage.`, e.g. `storage.ns.`.
+    Storage { namespace: Vec<String> },
+    /// An item after a path, e.g. `std::hash::` or `Color::`.
+    Path { segments: Vec<String> },
+    /// An attribute, e.g. `#[sto`, or one of its arguments, e.g. `#[storage(re`.
+    Attribute { name: Option<String> },
+    /// A field name within a struct expression, e.g. `Point { x: 1, `.
+    StructField {
+        struct_name: String,
+        fields: Vec<String>,
+    },
+    /// Anything else an identifier or a keyword may be written.
+    Ident,
+}
+
+/// Returns the completion items for the given position within the text of the document.
+pub fn completion_items(
+    session: Arc<Session>,
+    uri: &Url,
     position: Position,
+    text: &str,
+) -> Option<Vec<CompletionItem>> {
+    let _p = tracing::trace_span!("completion_items").entered();
+    let context = completion_context(text, position)?;
+    let engines = session.engines.read();
+    let namespace = module_namespace(&session, &engines, uri)?;
+    let range = context.prefix_range;
+    let completion_items = match &context.kind {
+        ContextKind::Member {
+            receiver,
+            receiver_position,
+        } => member_completion_items(
+            &session,
+            &engines,
+            &namespace,
+            uri,
+            receiver,
+            *receiver_position,
+            range,
+        ),
+        ContextKind::Storage {
+            namespace: storage_namespace,
+        } => storage_completion_items(&engines, &namespace, storage_namespace),
+        ContextKind::Path { segments } => {
+            path_completion_items(&engines, &namespace, segments, range)
+        }
+        ContextKind::Attribute { name } => attribute_completion_items(name.as_deref()),
+        ContextKind::StructField {
+            struct_name,
+            fields,
+        } => struct_field_completion_items(&engines, &namespace, struct_name, fields),
+        ContextKind::Ident => {
+            ident_completion_items(&session, &engines, &namespace, uri, position, &context)
+        }
+    };
+    Some(completion_items)
+}
+
+/// Determines what is being typed at the given position.
+///
+/// This works on the text rather than on the AST, as the code being typed is usually incomplete.
+/// Returns [None] if nothing should be completed, e.g. within a comment.
+fn completion_context(text: &str, position: Position) -> Option<CompletionContext> {
+    let offset = offset_of_position(text, position)?;
+    let before_cursor = &text[..offset];
+    let prefix_start = ident_start(before_cursor);
+    let before_prefix = &before_cursor[..prefix_start];
+    let line = &before_prefix[before_prefix.rfind('\n').map_or(0, |i| i + 1)..];
+    if line.contains("//") {
+        return None;
+    }
+
+    let trimmed = before_prefix.trim_end();
+    let kind = match attribute_context(before_prefix) {
+        Some(kind) => kind,
+        None if trimmed.ends_with('.') && !trimmed.ends_with("..") => {
+            member_context(text, trimmed)?
+        }
+        None => path_context(before_prefix)
+            .or_else(|| struct_field_context(before_prefix))
+            .unwrap_or(ContextKind::Ident),
+    };
+    Some(CompletionContext {
+        kind,
+        prefix: before_cursor[prefix_start..].to_string(),
+        prefix_range: Range::new(position_of_offset(text, prefix_start), position),
+    })
+}
+
+fn attribute_context(before_prefix: &str) -> Option<ContextKind> {
+    let attribute = &before_prefix[before_prefix.rfind("#[")? + 2..];
+    if attribute.contains(']') {
+        return None;
+    }
+    let name = match attribute.rfind('(') {
+        Some(open_paren) if !attribute[open_paren..].contains(')') => {
+            let name = attribute[..open_paren].trim_end();
+            Some(name[ident_start(name)..].to_string())
+        }
+        _ => None,
+    };
+    Some(ContextKind::Attribute { name })
+}
+
+/// `before_dot` is the text before the cursor, ending with the `.`.
+fn member_context(text: &str, before_dot: &str) -> Option<ContextKind> {
+    let receiver_text = before_dot.strip_suffix('.')?.trim_end();
+    let receiver_start = expression_start(receiver_text);
+    let receiver = &receiver_text[receiver_start..];
+    // Numeric literals, e.g. `1.`, have no members.
+    if receiver.is_empty() || receiver.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let segments = receiver.split('.').collect::<Vec<_>>();
+    if segments[0] == "storage" && segments.iter().all(|segment| is_ident(segment)) {
+        return Some(ContextKind::Storage {
+            namespace: segments[1..].iter().map(ToString::to_string).collect(),
+        });
+    }
+    let (last_char_offset, _) = receiver_text.char_indices().last()?;
+    Some(ContextKind::Member {
+        receiver: receiver.to_string(),
+        receiver_position: position_of_offset(text, last_char_offset),
+    })
+}
+
+fn path_context(before_prefix: &str) -> Option<ContextKind> {
+    let mut rest = before_prefix.strip_suffix("::")?;
+    let mut segments = vec![];
+    loop {
+        let start = ident_start(rest);
+        if start == rest.len() {
+            break;
+        }
+        segments.push(rest[start..].to_string());
+        match rest[..start].strip_suffix("::") {
+            Some(before_segment) => rest = before_segment,
+            None => break,
+        }
+    }
+    if segments.is_empty() {
+        return None;
+    }
+    segments.reverse();
+    Some(ContextKind::Path { segments })
+}
+
+fn struct_field_context(before_prefix: &str) -> Option<ContextKind> {
+    // Walk backwards to the unclosed opening brace.
+    let mut depth = 0u32;
+    let mut open_brace = None;
+    for (i, c) in before_prefix.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '{' => {
+                open_brace = Some(i);
+                break;
+            }
+            '(' | '[' | ';' => return None,
+            _ => {}
+        }
+    }
+    let open_brace = open_brace?;
+
+    // The cursor must be at a field name rather than within a field value.
+    let fields = split_top_level(&before_prefix[open_brace + 1..]);
+    let (current, previous) = fields.split_last()?;
+    if !current.trim().is_empty() {
+        return None;
+    }
+
+    // The braces must follow the name of a struct at the start of an expression, rather than
+    // belong to a declaration, a block or a type.
+    let before_brace = before_prefix[..open_brace].trim_end();
+    let name_start = ident_start(before_brace);
+    let struct_name = &before_brace[name_start..];
+    if !struct_name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut before_name = before_brace[..name_start].trim_end();
+    while let Some(before_separator) = before_name.strip_suffix("::") {
+        before_name = &before_separator[..ident_start(before_separator)];
+    }
+    let before_name = before_name.trim_end();
+    let preceding_word = &before_name[ident_start(before_name)..];
+    let starts_expression = match preceding_word {
+        "" => {
+            before_name.is_empty()
+                || before_name.ends_with("=>")
+                || before_name.ends_with(['(', '[', '{', ',', ';'])
+                || before_name
+                    .strip_suffix('=')
+                    .is_some_and(|before_eq| !before_eq.ends_with(['=', '!', '<', '>']))
+        }
+        "return" => true,
+        _ => false,
+    };
+    if !starts_expression {
+        return None;
+    }
+
+    let fields = previous
+        .iter()
+        .filter_map(|field| {
+            let name = field.split(':').next()?.trim();
+            is_ident(name).then(|| name.to_string())
+        })
+        .collect();
+    Some(ContextKind::StructField {
+        struct_name: struct_name.to_string(),
+        fields,
+    })
+}
+
+/// Splits the text at the commas that are not nested within brackets.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0u32;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Returns the byte offset at which the identifier ending the text starts.
+fn ident_start(text: &str) -> usize {
+    text.char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()
+        .map_or(text.len(), |(i, _)| i)
+}
+
+/// Returns the byte offset at which the expression ending the text starts, e.g. `a.b(c)[0]`.
+fn expression_start(text: &str) -> usize {
+    let mut depth = 0u32;
+    let mut start = text.len();
+    for (i, c) in text.char_indices().rev() {
+        match c {
+            ')' | ']' => depth += 1,
+            '(' | '[' if depth > 0 => depth -= 1,
+            _ if depth > 0 => {}
+            c if is_ident_char(c) || c == '.' => {}
+            _ => break,
+        }
+        start = i;
+    }
+    start
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_ident(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(is_ident_char)
+}
+
+/// Returns the namespace of the module defined in the given file, or that of the program's root
+/// module if the file does not define a module of the program.
+fn module_namespace(session: &Session, engines: &Engines, uri: &Url) -> Option<Namespace> {
+    let compiled_program = session.compiled_program.read();
+    let program = compiled_program.typed.as_ref()?;
+    let path = uri.to_file_path().ok()?;
+    let module = program
+        .root
+        .submodules_recursive()
+        .map(|(_, submodule)| &*submodule.module)
+        .find(|module| {
+            module
+                .span
+                .source_id()
+                .is_some_and(|source_id| engines.se().get_path(source_id) == path)
+        })
+        .unwrap_or(&program.root);
+    Some(module.namespace.clone())
+}
+
+/// Completes the fields and methods of the receiver's type.
+fn member_completion_items(
+    session: &Session,
+    engines: &Engines,
+    namespace: &Namespace,
+    uri: &Url,
+    receiver: &str,
+    receiver_position: Position,
+    range: Range,
 ) -> Vec<CompletionItem> {
-    type_id_of_raw_ident(engines, namespace, &ident_to_complete.name, fn_decl)
-        .map(|type_id| completion_items_for_type_id(engines, namespace, type_id, position))
+    let items = namespace.module(engines).current_items();
+    let fn_tokens =
+        session
+            .token_map()
+            .tokens_at_position(engines, uri, receiver_position, Some(true));
+    let type_id = fn_tokens
+        .first()
+        .and_then(|fn_token| match &fn_token.value().typed {
+            Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => {
+                type_id_of_raw_ident(engines, items, receiver, fn_decl)
+            }
+            _ => None,
+        })
+        .or_else(|| {
+            session
+                .token_map()
+                .token_at_position(uri, receiver_position)?
+                .value()
+                .type_id()
+        });
+    type_id
+        .map(|type_id| completion_items_for_type_id(engines, items, type_id, range))
         .unwrap_or_default()
 }
 
@@ -26,7 +374,7 @@ fn completion_items_for_type_id(
     engines: &Engines,
     namespace: &Items,
     type_id: TypeId,
-    position: Position,
+    range: Range,
 ) -> Vec<CompletionItem> {
     let mut completion_items = vec![];
     let type_info = engines.te().get(type_id);
@@ -49,53 +397,563 @@ fn completion_items_for_type_id(
     for method in namespace.get_methods_for_type(engines, type_id) {
         let method = method.expect_typed();
         let fn_decl = engines.de().get_function(&method.id().clone());
-        let params = &fn_decl.parameters;
 
         // Only show methods that take `self` as the first parameter.
-        if params.first().is_some_and(TyFunctionParameter::is_self) {
-            let params_short = if params.is_empty() {
-                "()".to_string()
-            } else {
-                "(…)".to_string()
-            };
-            let params_edit_str = params
+        if fn_decl
+            .parameters
+            .first()
+            .is_some_and(TyFunctionParameter::is_self)
+        {
+            completion_items.push(fn_completion_item(
+                engines,
+                &fn_decl,
+                CompletionItemKind::METHOD,
+                Some(type_id),
+                range,
+            ));
+        }
+    }
+
+    completion_items
+}
+
+/// Completes the fields and nested namespaces of the given storage namespace.
+fn storage_completion_items(
+    engines: &Engines,
+    namespace: &Namespace,
+    storage_namespace: &[String],
+) -> Vec<CompletionItem> {
+    let Some(storage) = namespace
+        .root_module()
+        .current_items()
+        .get_declared_storage(engines.de())
+    else {
+        return vec![];
+    };
+    let mut completion_items: Vec<CompletionItem> = vec![];
+    for field in &storage.fields {
+        let in_namespace = field.namespace_names.len() >= storage_namespace.len()
+            && field
+                .namespace_names
                 .iter()
-                .filter_map(|p| {
-                    if p.is_self() {
-                        return None;
-                    }
-                    Some(p.name.as_str())
-                })
-                .collect::<Vec<&str>>()
-                .join(", ");
-            let item = CompletionItem {
-                kind: Some(CompletionItemKind::METHOD),
-                label: format!("{}{}", method.name().clone().as_str(), params_short),
-                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
-                    range: Range {
-                        start: position,
-                        end: position,
-                    },
-                    new_text: format!("{}({})", method.name().clone().as_str(), params_edit_str),
-                })),
+                .zip(storage_namespace)
+                .all(|(name, expected)| name.as_str() == expected.as_str());
+        if !in_namespace {
+            continue;
+        }
+        let item = match field.namespace_names.get(storage_namespace.len()) {
+            Some(nested_namespace) => CompletionItem {
+                kind: Some(CompletionItemKind::MODULE),
+                label: nested_namespace.to_string(),
+                ..Default::default()
+            },
+            None => CompletionItem {
+                kind: Some(CompletionItemKind::FIELD),
+                label: field.name.to_string(),
                 label_details: Some(CompletionItemLabelDetails {
-                    description: Some(fn_signature_string(engines, &fn_decl, &type_id)),
+                    description: Some(field.type_argument.span.as_str().to_string()),
                     detail: None,
                 }),
                 ..Default::default()
-            };
+            },
+        };
+        if !completion_items
+            .iter()
+            .any(|other| other.label == item.label)
+        {
+            completion_items.push(item);
+        }
+    }
+    completion_items
+}
+
+/// Completes the items of a module, or the enum variants and associated functions of a type.
+fn path_completion_items(
+    engines: &Engines,
+    namespace: &Namespace,
+    segments: &[String],
+    range: Range,
+) -> Vec<CompletionItem> {
+    let path = segments
+        .iter()
+        .map(|segment| Ident::new_no_span(segment.clone()))
+        .collect::<Vec<_>>();
+    let current_module = namespace.module(engines);
+    // Modules are looked up relative to the current module, and then relative to the root module,
+    // which contains the dependencies such as `std`.
+    let find_module = |path: &[Ident]| {
+        current_module
+            .submodule(engines, path)
+            .or_else(|| namespace.root_module().submodule(engines, path))
+    };
+    if let Some(module) = find_module(&path) {
+        return module_completion_items(engines, module, range);
+    }
+
+    let Some((type_name, module_path)) = path.split_last() else {
+        return vec![];
+    };
+    let Some(decl) = find_module(module_path)
+        .and_then(|module| lookup_symbol(module.current_items(), type_name))
+    else {
+        return vec![];
+    };
+    type_completion_items(engines, current_module.current_items(), &decl, range)
+}
+
+/// Completes the submodules and public items of the given module.
+fn module_completion_items(
+    engines: &Engines,
+    module: &Module,
+    range: Range,
+) -> Vec<CompletionItem> {
+    let mut submodules = module.submodules().keys().collect::<Vec<_>>();
+    submodules.sort();
+    let mut completion_items = submodules
+        .into_iter()
+        .map(|name| CompletionItem {
+            kind: Some(CompletionItemKind::MODULE),
+            label: name.clone(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    completion_items.extend(
+        module
+            .current_items()
+            .symbols()
+            .iter()
+            .filter_map(|(name, decl)| match decl {
+                ResolvedDeclaration::Typed(decl) if decl.visibility(engines.de()).is_public() => {
+                    decl_completion_item(engines, name, decl, range)
+                }
+                _ => None,
+            }),
+    );
+    completion_items
+}
+
+/// Completes the variants of an enum and the associated functions and methods of a type.
+fn type_completion_items(
+    engines: &Engines,
+    items: &Items,
+    decl: &TyDecl,
+    range: Range,
+) -> Vec<CompletionItem> {
+    let mut completion_items = vec![];
+    if let TyDecl::EnumDecl(ty::EnumDecl { decl_id }) = decl {
+        let enum_decl = engines.de().get_enum(decl_id);
+        completion_items.extend(enum_decl.variants.iter().map(|variant| CompletionItem {
+            kind: Some(CompletionItemKind::ENUM_MEMBER),
+            label: variant.name.to_string(),
+            label_details: Some(CompletionItemLabelDetails {
+                description: Some(engines.help_out(variant.type_argument.type_id).to_string()),
+                detail: None,
+            }),
+            ..Default::default()
+        }));
+    }
+
+    let Ok(type_id) = decl.return_type(&Handler::default(), engines) else {
+        return completion_items;
+    };
+    for method in items.get_methods_for_type(engines, type_id) {
+        let method = method.expect_typed();
+        let fn_decl = engines.de().get_function(&method.id().clone());
+        let kind = if fn_decl
+            .parameters
+            .first()
+            .is_some_and(TyFunctionParameter::is_self)
+        {
+            CompletionItemKind::METHOD
+        } else {
+            CompletionItemKind::FUNCTION
+        };
+        completion_items.push(fn_completion_item(
+            engines,
+            &fn_decl,
+            kind,
+            Some(type_id),
+            range,
+        ));
+    }
+    completion_items
+}
+
+/// Completes the names of attributes, or the arguments of the attribute with the given name.
+fn attribute_completion_items(name: Option<&str>) -> Vec<CompletionItem> {
+    let names: &[&str] = match name {
+        None => constants::VALID_ATTRIBUTE_NAMES,
+        Some(constants::STORAGE_PURITY_ATTRIBUTE_NAME) => &[
+            constants::STORAGE_PURITY_READ_NAME,
+            constants::STORAGE_PURITY_WRITE_NAME,
+        ],
+        Some(constants::INLINE_ATTRIBUTE_NAME) => {
+            &[constants::INLINE_ALWAYS_NAME, constants::INLINE_NEVER_NAME]
+        }
+        Some(constants::ALLOW_ATTRIBUTE_NAME) => &[
+            constants::ALLOW_DEAD_CODE_NAME,
+            constants::ALLOW_DEPRECATED_NAME,
+        ],
+        Some(constants::CFG_ATTRIBUTE_NAME) => &[
+            constants::CFG_TARGET_ARG_NAME,
+            constants::CFG_PROGRAM_TYPE_ARG_NAME,
+            constants::CFG_EXPERIMENTAL_NEW_ENCODING,
+        ],
+        Some(constants::TEST_ATTRIBUTE_NAME) => &[TEST_SHOULD_REVERT_NAME],
+        Some(_) => &[],
+    };
+    names
+        .iter()
+        // Doc comments are written as `///` rather than as attributes.
+        .filter(|name| **name != constants::DOC_COMMENT_ATTRIBUTE_NAME)
+        .map(|name| CompletionItem {
+            kind: Some(CompletionItemKind::KEYWORD),
+            label: name.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completes the fields of the given struct that are not yet initialized.
+fn struct_field_completion_items(
+    engines: &Engines,
+    namespace: &Namespace,
+    struct_name: &str,
+    fields: &[String],
+) -> Vec<CompletionItem> {
+    let items = namespace.module(engines).current_items();
+    let Some(TyDecl::StructDecl(ty::StructDecl { decl_id })) =
+        lookup_symbol(items, &Ident::new_no_span(struct_name.to_string()))
+    else {
+        return vec![];
+    };
+    engines
+        .de()
+        .get_struct(&decl_id)
+        .fields
+        .iter()
+        .filter(|field| {
+            !fields
+                .iter()
+                .any(|name| name.as_str() == field.name.as_str())
+        })
+        .map(|field| CompletionItem {
+            kind: Some(CompletionItemKind::FIELD),
+            label: field.name.to_string(),
+            label_details: Some(CompletionItemLabelDetails {
+                description: Some(field.type_argument.span.as_str().to_string()),
+                detail: None,
+            }),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completes the variables, items and keywords in scope, followed by the declarations that can
+/// be imported.
+fn ident_completion_items(
+    session: &Session,
+    engines: &Engines,
+    namespace: &Namespace,
+    uri: &Url,
+    position: Position,
+    context: &CompletionContext,
+) -> Vec<CompletionItem> {
+    let range = context.prefix_range;
+    let mut completion_items = vec![];
+    let mut names = HashSet::new();
+
+    for (name, type_id) in local_variables(session, engines, uri, position) {
+        if names.insert(name.clone()) {
+            completion_items.push(CompletionItem {
+                kind: Some(CompletionItemKind::VARIABLE),
+                label: name,
+                label_details: Some(CompletionItemLabelDetails {
+                    description: Some(engines.help_out(type_id).to_string()),
+                    detail: None,
+                }),
+                ..Default::default()
+            });
+        }
+    }
+
+    let items = namespace.module(engines).current_items();
+    for (name, decl) in items
+        .symbols()
+        .iter()
+        .chain(items.get_all_imported_symbols())
+    {
+        let ResolvedDeclaration::Typed(decl) = decl else {
+            continue;
+        };
+        if names.contains(name.as_str()) {
+            continue;
+        }
+        if let Some(item) = decl_completion_item(engines, name, decl, range) {
+            names.insert(name.to_string());
             completion_items.push(item);
         }
     }
 
+    let mut keywords = sway_parse::RESERVED_KEYWORDS.iter().collect::<Vec<_>>();
+    keywords.sort();
+    completion_items.extend(keywords.into_iter().map(|keyword| CompletionItem {
+        kind: Some(CompletionItemKind::KEYWORD),
+        label: keyword.to_string(),
+        ..Default::default()
+    }));
+
+    if !context.prefix.is_empty() {
+        completion_items.extend(auto_import_completion_items(
+            session,
+            engines,
+            namespace,
+            uri,
+            &context.prefix,
+            &names,
+            range,
+        ));
+    }
     completion_items
 }
 
+/// Returns the names and types of the parameters and variables of the function enclosing the
+/// given position, declared before it. Later declarations come first, as they shadow earlier ones.
+fn local_variables(
+    session: &Session,
+    engines: &Engines,
+    uri: &Url,
+    position: Position,
+) -> Vec<(String, TypeId)> {
+    let fn_decl = session
+        .token_map()
+        .tokens_at_position(engines, uri, position, Some(true))
+        .first()
+        .and_then(|fn_token| match &fn_token.value().typed {
+            Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => Some(fn_decl.clone()),
+            _ => None,
+        });
+    let Some(fn_decl) = fn_decl else {
+        return vec![];
+    };
+    let body_range = get_range_from_span(&fn_decl.span);
+    let mut variables = session
+        .token_map()
+        .tokens_for_file(uri)
+        .filter_map(|entry| {
+            let (ident, token) = entry.pair();
+            match &token.typed {
+                Some(TypedAstToken::TypedDeclaration(TyDecl::VariableDecl(variable_decl)))
+                    if ident.range.start >= body_range.start && ident.range.end <= position =>
+                {
+                    Some((
+                        ident.range.start,
+                        ident.name.clone(),
+                        variable_decl.return_type,
+                    ))
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    variables.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    variables
+        .into_iter()
+        .map(|(_, name, type_id)| (name, type_id))
+        .chain(
+            fn_decl
+                .parameters
+                .iter()
+                .map(|param| (param.name.to_string(), param.type_argument.type_id)),
+        )
+        .collect()
+}
+
+/// Completes the declarations of the program and its dependencies that are not in scope and whose
+/// names start with the prefix. Accepting one of them also inserts the `use` statement for it.
+fn auto_import_completion_items(
+    session: &Session,
+    engines: &Engines,
+    namespace: &Namespace,
+    uri: &Url,
+    prefix: &str,
+    in_scope: &HashSet<String>,
+    range: Range,
+) -> Vec<CompletionItem> {
+    let imports = FileImports::collect(session.token_map(), uri);
+    let mut seen = HashSet::new();
+    let mut completion_items = session
+        .token_map()
+        .iter()
+        .filter_map(|entry| {
+            let (ident, token) = entry.pair();
+            if in_scope.contains(&ident.name)
+                || !matches!(
+                    match_name(prefix, &ident.name),
+                    Some(NameMatch::Exact | NameMatch::Prefix)
+                )
+            {
+                return None;
+            }
+            let typed = token.typed.as_ref()?;
+            let kind = import_kind(engines, typed)?;
+            let call_path = import_call_path(engines, namespace, typed)?;
+            if call_path.prefixes.is_empty() || !seen.insert(call_path.to_string()) {
+                return None;
+            }
+            Some(CompletionItem {
+                kind: Some(kind),
+                label: ident.name.clone(),
+                label_details: Some(CompletionItemLabelDetails {
+                    description: Some(format!("use {call_path}")),
+                    detail: None,
+                }),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range,
+                    new_text: ident.name.clone(),
+                })),
+                additional_text_edits: Some(vec![imports.text_edit(&call_path)]),
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+    completion_items.sort_by(|a, b| {
+        a.label.cmp(&b.label).then_with(|| {
+            let description = |item: &CompletionItem| {
+                item.label_details
+                    .as_ref()
+                    .and_then(|details| details.description.clone())
+            };
+            description(a).cmp(&description(b))
+        })
+    });
+    completion_items
+}
+
+/// Returns the kind of the completion item for a public declaration that can be imported.
+fn import_kind(engines: &Engines, typed: &TypedAstToken) -> Option<CompletionItemKind> {
+    match typed {
+        // Methods and associated functions are called through their type rather than imported.
+        TypedAstToken::TypedDeclaration(TyDecl::FunctionDecl(ty::FunctionDecl { decl_id })) => {
+            let fn_decl = engines.de().get_function(decl_id);
+            (fn_decl.visibility.is_public() && fn_decl.implementing_type.is_none())
+                .then_some(CompletionItemKind::FUNCTION)
+        }
+        TypedAstToken::TypedDeclaration(decl) if decl.visibility(engines.de()).is_public() => {
+            decl_kind(decl)
+        }
+        TypedAstToken::TypedFunctionDeclaration(fn_decl)
+            if fn_decl.visibility.is_public() && fn_decl.implementing_type.is_none() =>
+        {
+            Some(CompletionItemKind::FUNCTION)
+        }
+        TypedAstToken::TypedConstantDeclaration(const_decl)
+            if const_decl.visibility.is_public() =>
+        {
+            Some(CompletionItemKind::CONSTANT)
+        }
+        TypedAstToken::TypedTypeAliasDeclaration(type_alias)
+            if type_alias.visibility.is_public() =>
+        {
+            Some(CompletionItemKind::STRUCT)
+        }
+        _ => None,
+    }
+}
+
+fn decl_kind(decl: &TyDecl) -> Option<CompletionItemKind> {
+    match decl {
+        TyDecl::FunctionDecl(_) => Some(CompletionItemKind::FUNCTION),
+        TyDecl::StructDecl(_) | TyDecl::TypeAliasDecl(_) => Some(CompletionItemKind::STRUCT),
+        TyDecl::EnumDecl(_) => Some(CompletionItemKind::ENUM),
+        TyDecl::EnumVariantDecl(_) => Some(CompletionItemKind::ENUM_MEMBER),
+        TyDecl::TraitDecl(_) | TyDecl::AbiDecl(_) => Some(CompletionItemKind::INTERFACE),
+        TyDecl::ConstantDecl(_) | TyDecl::ConfigurableDecl(_) => Some(CompletionItemKind::CONSTANT),
+        _ => None,
+    }
+}
+
+/// Builds the completion item for a declaration with the given name.
+fn decl_completion_item(
+    engines: &Engines,
+    name: &Ident,
+    decl: &TyDecl,
+    range: Range,
+) -> Option<CompletionItem> {
+    if let TyDecl::FunctionDecl(ty::FunctionDecl { decl_id }) = decl {
+        let fn_decl = engines.de().get_function(decl_id);
+        return Some(fn_completion_item(
+            engines,
+            &fn_decl,
+            CompletionItemKind::FUNCTION,
+            None,
+            range,
+        ));
+    }
+    Some(CompletionItem {
+        kind: Some(decl_kind(decl)?),
+        label: name.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Builds the completion item for calling a function, with a snippet placeholder for each of its
+/// arguments.
+fn fn_completion_item(
+    engines: &Engines,
+    fn_decl: &TyFunctionDecl,
+    kind: CompletionItemKind,
+    parent_type_id: Option<TypeId>,
+    range: Range,
+) -> CompletionItem {
+    let args = fn_decl
+        .parameters
+        .iter()
+        .filter(|param| !param.is_self())
+        .collect::<Vec<_>>();
+    let args_short = if args.is_empty() { "()" } else { "(…)" };
+    let args_snippet = args
+        .iter()
+        .enumerate()
+        .map(|(i, param)| format!("${{{}:{}}}", i + 1, param.name.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    CompletionItem {
+        kind: Some(kind),
+        label: format!("{}{}", fn_decl.name.as_str(), args_short),
+        text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+            range,
+            new_text: format!("{}({})", fn_decl.name.as_str(), args_snippet),
+        })),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        label_details: Some(CompletionItemLabelDetails {
+            description: Some(fn_signature_string(engines, fn_decl, parent_type_id)),
+            detail: None,
+        }),
+        ..Default::default()
+    }
+}
+
+/// Looks up the declaration with the given name among the items declared in or imported into a
+/// scope.
+fn lookup_symbol(items: &Items, name: &Ident) -> Option<TyDecl> {
+    let decl = items.symbols().get(name).or_else(|| {
+        items
+            .get_all_imported_symbols()
+            .find(|(imported_name, _)| *imported_name == name)
+            .map(|(_, decl)| decl)
+    })?;
+    match decl {
+        ResolvedDeclaration::Typed(decl) => Some(decl.clone()),
+        ResolvedDeclaration::Parsed(_) => None,
+    }
+}
+
 /// Returns the [String] of the shortened function signature to display in the completion item's label details.
 fn fn_signature_string(
     engines: &Engines,
     fn_decl: &TyFunctionDecl,
-    parent_type_id: &TypeId,
+    parent_type_id: Option<TypeId>,
 ) -> String {
     let params_str = fn_decl
         .parameters
@@ -120,12 +978,12 @@ fn fn_signature_string(
 fn replace_self_with_type_str(
     engines: &Engines,
     type_str: String,
-    parent_type_id: &TypeId,
+    parent_type_id: Option<TypeId>,
 ) -> String {
-    if type_str == "Self" {
-        return engines.help_out(parent_type_id).to_string();
+    match parent_type_id {
+        Some(parent_type_id) if type_str == "Self" => engines.help_out(parent_type_id).to_string(),
+        _ => type_str,
     }
-    type_str
 }
 
 /// Returns the [TypeId] of an ident that may include field accesses and may be incomplete.
@@ -207,3 +1065,100 @@ fn type_id_of_local_ident(ident_name: &str, fn_decl: &TyFunctionDecl) -> Option<
             })
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_at(text: &str, line: u32, character: u32) -> Option<ContextKind> {
+        completion_context(text, Position::new(line, character)).map(|context| context.kind)
+    }
+
+    #[test]
+    fn completion_context_member() {
+        let text = "fn main() {\n    let x = foo.get(1).ba\n}";
+        let context = completion_context(text, Position::new(1, 25)).unwrap();
+        assert_eq!(
+            context.kind,
+            ContextKind::Member {
+                receiver: "foo.get(1)".to_string(),
+                receiver_position: Position::new(1, 21),
+            }
+        );
+        assert_eq!(context.prefix, "ba");
+        assert_eq!(
+            context.prefix_range,
+            Range::new(Position::new(1, 23), Position::new(1, 25))
+        );
+        assert_eq!(kind_at("let x = 1.", 0, 10), None);
+        assert_eq!(kind_at("// foo.", 0, 7), None);
+    }
+
+    #[test]
+    fn completion_context_storage() {
+        assert_eq!(
+            kind_at("    storage.", 0, 12),
+            Some(ContextKind::Storage { namespace: vec![] })
+        );
+        assert_eq!(
+            kind_at("    storage.ns.co", 0, 17),
+            Some(ContextKind::Storage {
+                namespace: vec!["ns".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn completion_context_path() {
+        assert_eq!(
+            kind_at("use std::hash::", 0, 15),
+            Some(ContextKind::Path {
+                segments: vec!["std".to_string(), "hash".to_string()]
+            })
+        );
+        assert_eq!(
+            kind_at("    let c = Color::Bl", 0, 21),
+            Some(ContextKind::Path {
+                segments: vec!["Color".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn completion_context_attribute() {
+        assert_eq!(
+            kind_at("#[sto", 0, 5),
+            Some(ContextKind::Attribute { name: None })
+        );
+        assert_eq!(
+            kind_at("#[storage(read, ", 0, 16),
+            Some(ContextKind::Attribute {
+                name: Some("storage".to_string())
+            })
+        );
+        assert_eq!(kind_at("#[test]\nfn f", 1, 4), Some(ContextKind::Ident));
+    }
+
+    #[test]
+    fn completion_context_struct_field() {
+        assert_eq!(
+            kind_at("    let p = Point { x: foo(1, 2), ", 0, 34),
+            Some(ContextKind::StructField {
+                struct_name: "Point".to_string(),
+                fields: vec!["x".to_string()],
+            })
+        );
+        assert_eq!(
+            kind_at("    let p = Point { x: ", 0, 23),
+            Some(ContextKind::Ident)
+        );
+        assert_eq!(
+            kind_at("impl Point {\n    ", 1, 4),
+            Some(ContextKind::Ident)
+        );
+        assert_eq!(
+            kind_at("    if a == B {\n    ", 1, 4),
+            Some(ContextKind::Ident)
+        );
+    }
+}
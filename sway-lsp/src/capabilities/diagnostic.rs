@@ -5,7 +5,10 @@ use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
 use serde::{Deserialize, Serialize};
 use sway_error::warning::CompileWarning;
 use sway_error::{error::CompileError, warning::Warning};
-use sway_types::{LineCol, LineColRange, SourceEngine, Spanned};
+use sway_types::{
+    style::{to_screaming_snake_case, to_snake_case, to_upper_camel_case},
+    LineCol, LineColRange, SourceEngine, Spanned,
+};
 
 pub(crate) type DiagnosticMap = HashMap<PathBuf, Diagnostics>;
 
@@ -28,11 +31,14 @@ fn get_error_diagnostic(error: &CompileError) -> Diagnostic {
 }
 
 fn get_warning_diagnostic(warning: &CompileWarning) -> Diagnostic {
+    let data = serde_json::to_value(DiagnosticData::try_from(warning.clone()).ok()).ok();
+
     Diagnostic {
         range: get_range(warning.span().line_col()),
         severity: Some(DiagnosticSeverity::WARNING),
         message: warning.to_friendly_warning_string(),
         tags: get_warning_diagnostic_tags(&warning.warning_content),
+        data,
         ..Default::default()
     }
 }
@@ -94,13 +100,53 @@ fn get_warning_diagnostic_tags(warning: &Warning) -> Option<Vec<DiagnosticTag>>
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DiagnosticData {
     pub unknown_symbol_name: Option<String>,
+    /// The storage attributes, such as `read, write`, the diagnosed function must be declared with.
+    pub storage_attributes: Option<String>,
+    /// The patterns missing from the diagnosed `match` expression, as reported by the compiler.
+    pub missing_patterns: Option<String>,
+    /// The names of the functions missing from the diagnosed trait implementation.
+    pub missing_functions: Option<Vec<String>>,
+    /// The name following the naming conventions to rename the diagnosed declaration to.
+    pub suggested_name: Option<String>,
+    /// Whether the diagnosed declaration is never used.
+    #[serde(default)]
+    pub dead_code: bool,
 }
 
 impl TryFrom<CompileWarning> for DiagnosticData {
     type Error = anyhow::Error;
 
-    fn try_from(_value: CompileWarning) -> Result<Self, Self::Error> {
-        anyhow::bail!("Not implemented");
+    fn try_from(value: CompileWarning) -> Result<Self, Self::Error> {
+        let suggested_name = |name: &str, to_case: fn(&str) -> String| DiagnosticData {
+            suggested_name: Some(to_case(name)),
+            ..Default::default()
+        };
+        match value.warning_content {
+            Warning::NonClassCaseStructName { struct_name: name }
+            | Warning::NonClassCaseTypeParameter { name }
+            | Warning::NonClassCaseTraitName { name }
+            | Warning::NonClassCaseEnumName { enum_name: name }
+            | Warning::NonClassCaseEnumVariantName { variant_name: name } => {
+                Ok(suggested_name(name.as_str(), to_upper_camel_case))
+            }
+            Warning::NonSnakeCaseStructFieldName { field_name: name }
+            | Warning::NonSnakeCaseFunctionName { name } => {
+                Ok(suggested_name(name.as_str(), to_snake_case))
+            }
+            Warning::NonScreamingSnakeCaseConstName { name } => {
+                Ok(suggested_name(name.as_str(), to_screaming_snake_case))
+            }
+            Warning::DeadDeclaration
+            | Warning::DeadEnumDeclaration
+            | Warning::DeadFunctionDeclaration
+            | Warning::DeadMethod
+            | Warning::DeadStructDeclaration
+            | Warning::DeadTrait => Ok(DiagnosticData {
+                dead_code: true,
+                ..Default::default()
+            }),
+            _ => anyhow::bail!("Not implemented"),
+        }
     }
 }
 
@@ -111,12 +157,39 @@ impl TryFrom<CompileError> for DiagnosticData {
         match value {
             CompileError::SymbolNotFound { name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(name.to_string()),
+                ..Default::default()
             }),
             CompileError::TraitNotFound { name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(name),
+                ..Default::default()
             }),
             CompileError::UnknownVariable { var_name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(var_name.to_string()),
+                ..Default::default()
+            }),
+            CompileError::StorageAccessMismatched {
+                suggested_attributes,
+                ..
+            } => Ok(DiagnosticData {
+                storage_attributes: Some(suggested_attributes),
+                ..Default::default()
+            }),
+            CompileError::MatchExpressionNonExhaustive {
+                missing_patterns, ..
+            } => Ok(DiagnosticData {
+                missing_patterns: Some(missing_patterns),
+                ..Default::default()
+            }),
+            CompileError::MissingInterfaceSurfaceMethods {
+                missing_functions, ..
+            } => Ok(DiagnosticData {
+                missing_functions: Some(
+                    missing_functions
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect(),
+                ),
+                ..Default::default()
             }),
             _ => anyhow::bail!("Not implemented"),
         }
@@ -0,0 +1,178 @@
+use crate::utils::document::position_of_offset;
+use forc_pkg::manifest::{GenericManifestFile, PackageManifestFile};
+use lsp_types::{DocumentLink, Range, Url};
+use std::{path::Path, sync::Arc};
+use sway_ast::ItemKind;
+use sway_error::handler::Handler;
+use sway_types::Spanned;
+use sway_utils::constants::MANIFEST_FILE_NAME;
+use toml_edit::{ImDocument, Item};
+
+/// The tables of a manifest that declare dependencies.
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "contract-dependencies"];
+
+/// Returns the links from the `mod` declarations of the Sway file at the given path to the files
+/// of the declared modules.
+pub fn module_links(path: &Path, text: &str) -> Vec<DocumentLink> {
+    let _p = tracing::trace_span!("module_links").entered();
+    let Some(dir) = path.parent() else {
+        return vec![];
+    };
+    let Ok(module) = sway_parse::parse_file(&Handler::default(), Arc::from(text), None) else {
+        return vec![];
+    };
+    // The modules declared in the entry file of a package are relative to its directory, while
+    // those declared in other modules are relative to a directory named after the module.
+    let is_entry =
+        PackageManifestFile::from_dir(dir).is_ok_and(|manifest| manifest.entry_path() == path);
+    let module_name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|_| !is_entry);
+    module
+        .value
+        .items
+        .iter()
+        .filter_map(|item| match &item.value {
+            ItemKind::Submodule(submodule) => {
+                let target = sway_core::module_path(dir, module_name, submodule);
+                Some(DocumentLink {
+                    range: range_of_offsets(
+                        text,
+                        submodule.name.span().start(),
+                        submodule.name.span().end(),
+                    ),
+                    target: Some(Url::from_file_path(target).ok()?),
+                    tooltip: None,
+                    data: None,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the links from the `path` and `git` dependencies declared in the text of the manifest
+/// in the given directory to the manifests or repositories of the dependencies.
+pub fn manifest_links(manifest_dir: &Path, text: &str) -> Vec<DocumentLink> {
+    let _p = tracing::trace_span!("manifest_links").entered();
+    let Ok(document) = ImDocument::parse(text) else {
+        return vec![];
+    };
+    let mut dependencies = vec![];
+    for table in DEPENDENCY_TABLES {
+        if let Some(table) = document.get(table).and_then(Item::as_table_like) {
+            dependencies.extend(table.iter().map(|(_, dependency)| dependency));
+        }
+    }
+    // Patches are declared per source, e.g. `[patch.'https://github.com/fuellabs/sway']`.
+    if let Some(patch) = document.get("patch").and_then(Item::as_table_like) {
+        for (_, source) in patch.iter() {
+            if let Some(source) = source.as_table_like() {
+                dependencies.extend(source.iter().map(|(_, dependency)| dependency));
+            }
+        }
+    }
+    dependencies
+        .into_iter()
+        .filter_map(|dependency| dependency_link(manifest_dir, text, dependency))
+        .collect()
+}
+
+fn dependency_link(manifest_dir: &Path, text: &str, dependency: &Item) -> Option<DocumentLink> {
+    let (value, target) = if let Some(path) = dependency.get("path") {
+        let dependency_dir = manifest_dir.join(path.as_str()?);
+        let dependency_manifest = dependency_dir.join(MANIFEST_FILE_NAME);
+        let target = if dependency_manifest.is_file() {
+            dependency_manifest
+        } else {
+            dependency_dir
+        };
+        (path, Url::from_file_path(target).ok()?)
+    } else if let Some(git) = dependency.get("git") {
+        (git, Url::parse(git.as_str()?).ok()?)
+    } else {
+        return None;
+    };
+    // Exclude the quotes of the string from the link.
+    let span = value.span()?;
+    Some(DocumentLink {
+        range: range_of_offsets(text, span.start + 1, span.end.saturating_sub(1)),
+        target: Some(target),
+        tooltip: None,
+        data: None,
+    })
+}
+
+fn range_of_offsets(text: &str, start: usize, end: usize) -> Range {
+    Range::new(
+        position_of_offset(text, start),
+        position_of_offset(text, end),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Position;
+
+    #[test]
+    fn manifest_links_for_path_and_git_dependencies() {
+        let manifest_dir = std::env::temp_dir().join("project");
+        let text = r#"[project]
+name = "project"
+
+[dependencies]
+std = { git = "https://github.com/fuellabs/sway", tag = "v0.60.0" }
+other = { path = "../other" }
+version_only = "0.1.0"
+
+[contract-dependencies.token]
+path = "../token"
+"#;
+        let links = manifest_links(&manifest_dir, text);
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| (link.range, link.target.clone().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Range::new(Position::new(4, 15), Position::new(4, 47)),
+                    Url::parse("https://github.com/fuellabs/sway").unwrap(),
+                ),
+                (
+                    Range::new(Position::new(5, 18), Position::new(5, 26)),
+                    Url::from_file_path(manifest_dir.join("../other")).unwrap(),
+                ),
+                (
+                    Range::new(Position::new(9, 8), Position::new(9, 16)),
+                    Url::from_file_path(manifest_dir.join("../token")).unwrap(),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn module_links_for_submodules() {
+        let dir = std::env::temp_dir();
+        let text = "library;\n\nmod foo;\npub mod bar;\n";
+        let links = module_links(&dir.join("baz.sw"), text);
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| (link.range, link.target.clone().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Range::new(Position::new(2, 4), Position::new(2, 7)),
+                    Url::from_file_path(dir.join("baz").join("foo.sw")).unwrap(),
+                ),
+                (
+                    Range::new(Position::new(3, 8), Position::new(3, 11)),
+                    Url::from_file_path(dir.join("baz").join("bar.sw")).unwrap(),
+                ),
+            ]
+        );
+    }
+}
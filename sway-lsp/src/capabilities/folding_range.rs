@@ -0,0 +1,201 @@
+use crate::core::token::get_range_from_span;
+use lsp_types::{FoldingRange, FoldingRangeKind};
+use std::sync::Arc;
+use sway_ast::{
+    attribute::Annotated,
+    token::{CommentedTokenStream, CommentedTokenTree, CommentedTree},
+    ItemImplItem, ItemKind, ItemTraitItem,
+};
+use sway_error::handler::Handler;
+use sway_types::{Span, Spanned};
+
+/// Returns the folding ranges of the given Sway source text.
+///
+/// Groups of `use` statements fold as imports and runs of doc comments fold as comments. Items,
+/// including the functions within `impl`, `trait` and `abi` blocks, and any other multi-line
+/// braces, parentheses or brackets fold as regions.
+pub fn folding_ranges(text: &str) -> Vec<FoldingRange> {
+    let _p = tracing::trace_span!("folding_ranges").entered();
+    let src: Arc<str> = Arc::from(text);
+    let handler = Handler::default();
+    let mut folding_ranges = vec![];
+
+    if let Ok(module) = sway_parse::parse_file(&handler, src.clone(), None) {
+        let items = &module.value.items;
+        for use_group in items
+            .split(|item| !matches!(item.value, ItemKind::Use(_)))
+            .filter(|use_group| !use_group.is_empty())
+        {
+            let span = Span::join(use_group[0].span(), &use_group[use_group.len() - 1].span());
+            folding_ranges.extend(folding_range(&span, Some(FoldingRangeKind::Imports)));
+        }
+        let mut item_spans = vec![];
+        for item in items {
+            collect_item_spans(&item.value, &mut item_spans);
+        }
+        folding_ranges.extend(
+            item_spans
+                .iter()
+                .filter_map(|span| folding_range(span, None)),
+        );
+    }
+
+    if let Ok(token_stream) = sway_parse::lex_commented(&handler, &src, 0, src.len(), &None) {
+        collect_token_folding_ranges(&token_stream, &mut folding_ranges);
+    }
+
+    // Only keep the first folding range starting on each line, in order of the priority above.
+    let mut start_lines = vec![];
+    folding_ranges.retain(|folding_range| {
+        if start_lines.contains(&folding_range.start_line) {
+            return false;
+        }
+        start_lines.push(folding_range.start_line);
+        true
+    });
+    folding_ranges.sort_by_key(|folding_range| folding_range.start_line);
+    folding_ranges
+}
+
+/// Collects the spans of the given item and of the items nested within it.
+fn collect_item_spans(item: &ItemKind, spans: &mut Vec<Span>) {
+    spans.push(item.span());
+    match item {
+        ItemKind::Impl(item_impl) => {
+            for impl_item in item_impl.contents.get() {
+                match &impl_item.value {
+                    ItemImplItem::Fn(item_fn) => spans.push(item_fn.span()),
+                    ItemImplItem::Const(item_const) => spans.push(item_const.span()),
+                    ItemImplItem::Type(_) => {}
+                }
+            }
+        }
+        ItemKind::Trait(item_trait) => {
+            collect_trait_item_spans(item_trait.trait_items.get(), spans);
+            if let Some(trait_defs) = &item_trait.trait_defs_opt {
+                spans.extend(trait_defs.get().iter().map(|item_fn| item_fn.value.span()));
+            }
+        }
+        ItemKind::Abi(item_abi) => {
+            collect_trait_item_spans(item_abi.abi_items.get(), spans);
+            if let Some(abi_defs) = &item_abi.abi_defs_opt {
+                spans.extend(abi_defs.get().iter().map(|item_fn| item_fn.value.span()));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_trait_item_spans(trait_items: &[Annotated<ItemTraitItem>], spans: &mut Vec<Span>) {
+    spans.extend(
+        trait_items
+            .iter()
+            .filter(|trait_item| !matches!(trait_item.value, ItemTraitItem::Error(..)))
+            .map(|trait_item| trait_item.value.span()),
+    );
+}
+
+/// Collects the folding ranges of runs of doc comments and of multi-line delimited groups.
+fn collect_token_folding_ranges(
+    token_stream: &CommentedTokenStream,
+    folding_ranges: &mut Vec<FoldingRange>,
+) {
+    let mut doc_comments: Option<(Span, Span)> = None;
+    for token_tree in token_stream.token_trees() {
+        if let CommentedTokenTree::Tree(CommentedTree::DocComment(doc_comment)) = token_tree {
+            doc_comments = match doc_comments {
+                Some((first, last)) if is_next_line(&last, &doc_comment.span) => {
+                    Some((first, doc_comment.span.clone()))
+                }
+                previous => {
+                    folding_ranges.extend(doc_comment_folding_range(previous));
+                    Some((doc_comment.span.clone(), doc_comment.span.clone()))
+                }
+            };
+            continue;
+        }
+        if let CommentedTokenTree::Tree(CommentedTree::Group(group)) = token_tree {
+            folding_ranges.extend(folding_range(&group.span, None));
+            collect_token_folding_ranges(&group.token_stream, folding_ranges);
+        }
+    }
+    folding_ranges.extend(doc_comment_folding_range(doc_comments));
+}
+
+fn doc_comment_folding_range(doc_comments: Option<(Span, Span)>) -> Option<FoldingRange> {
+    let (first, last) = doc_comments?;
+    folding_range(&Span::join(first, &last), Some(FoldingRangeKind::Comment))
+}
+
+/// Returns true if the second span starts on the line following the end of the first span.
+fn is_next_line(first: &Span, second: &Span) -> bool {
+    get_range_from_span(first).end.line + 1 == get_range_from_span(second).start.line
+}
+
+/// Returns the folding range for the given span if it spans multiple lines.
+///
+/// A closing brace, parenthesis or bracket is left unfolded on its own line.
+fn folding_range(span: &Span, kind: Option<FoldingRangeKind>) -> Option<FoldingRange> {
+    let range = get_range_from_span(span);
+    let mut end_line = range.end.line;
+    if span.as_str().ends_with(['}', ')', ']']) {
+        end_line = end_line.saturating_sub(1);
+    }
+    if end_line <= range.start.line {
+        return None;
+    }
+    Some(FoldingRange {
+        start_line: range.start.line,
+        start_character: None,
+        end_line,
+        end_character: None,
+        kind,
+        collapsed_text: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folds(text: &str) -> Vec<(u32, u32, Option<FoldingRangeKind>)> {
+        folding_ranges(text)
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind))
+            .collect()
+    }
+
+    #[test]
+    fn folding_ranges_for_items_and_blocks() {
+        let text = r#"library;
+
+use std::hash::Hash;
+use std::hash::sha256;
+
+/// Adds one.
+/// Returns the result.
+fn add_one(x: u64) -> u64 {
+    if x > 0 {
+        x + 1
+    } else {
+        1
+    }
+}
+"#;
+        assert_eq!(
+            folds(text),
+            vec![
+                (2, 3, Some(FoldingRangeKind::Imports)),
+                (5, 6, Some(FoldingRangeKind::Comment)),
+                (7, 12, None),
+                (8, 9, None),
+                (10, 11, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn folding_ranges_ignore_single_lines() {
+        assert!(folds("library;\n\nfn f() {}\nuse std::hash::Hash;\n").is_empty());
+    }
+}
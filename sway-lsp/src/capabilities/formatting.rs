@@ -1,6 +1,7 @@
 use crate::{
     core::document::Documents,
     error::{DocumentError, LanguageServerError},
+    utils::document::{offset_of_position, position_of_offset},
 };
 use lsp_types::{Position, Range, TextEdit, Url};
 use std::sync::Arc;
@@ -18,6 +19,73 @@ pub fn format_text(documents: &Documents, url: &Url) -> Result<Vec<TextEdit>, La
         .map(|page_text_edit| vec![page_text_edit])
 }
 
+/// Formats the items or statements of the document overlapping the given range.
+pub fn format_range(
+    documents: &Documents,
+    url: &Url,
+    range: Range,
+) -> Result<Vec<TextEdit>, LanguageServerError> {
+    let _p = tracing::trace_span!("format_range").entered();
+    let document = documents.try_get(url.path()).try_unwrap().ok_or_else(|| {
+        DocumentError::DocumentNotFound {
+            path: url.path().to_string(),
+        }
+    })?;
+    let text = document.get_text();
+    let (Some(start), Some(end)) = (
+        offset_of_position(text, range.start),
+        offset_of_position(text, range.end),
+    ) else {
+        return Ok(vec![]);
+    };
+    get_range_text_edit(Arc::from(text), start..end, &mut <_>::default())
+        .map(|text_edit| text_edit.into_iter().collect())
+}
+
+/// Formats the item or statement ended by the character typed before the given position.
+pub fn format_on_type(
+    documents: &Documents,
+    url: &Url,
+    position: Position,
+) -> Result<Vec<TextEdit>, LanguageServerError> {
+    let _p = tracing::trace_span!("format_on_type").entered();
+    let document = documents.try_get(url.path()).try_unwrap().ok_or_else(|| {
+        DocumentError::DocumentNotFound {
+            path: url.path().to_string(),
+        }
+    })?;
+    let text = document.get_text();
+    let Some(end) = offset_of_position(text, position) else {
+        return Ok(vec![]);
+    };
+    let Some((start, _)) = text[..end].char_indices().next_back() else {
+        return Ok(vec![]);
+    };
+    get_range_text_edit(Arc::from(text), start..end, &mut <_>::default())
+        .map(|text_edit| text_edit.into_iter().collect())
+}
+
+/// Returns the edit formatting the items or statements of the text overlapping the given byte
+/// range, if there are any.
+pub fn get_range_text_edit(
+    text: Arc<str>,
+    range: std::ops::Range<usize>,
+    formatter: &mut Formatter,
+) -> Result<Option<TextEdit>, LanguageServerError> {
+    // we only format if code is correct
+    let formatted_range = formatter
+        .format_range(text.clone(), range, None)
+        .map_err(LanguageServerError::FormatError)?;
+
+    Ok(formatted_range.map(|(range, formatted_code)| TextEdit {
+        range: Range::new(
+            position_of_offset(&text, range.start),
+            position_of_offset(&text, range.end),
+        ),
+        new_text: formatted_code,
+    }))
+}
+
 pub fn get_page_text_edit(
     text: Arc<str>,
     formatter: &mut Formatter,
@@ -73,11 +73,7 @@ impl<'a> HoverLinkContents<'a> {
 
     /// Adds a single type to the list of related types.
     fn add_related_type(&mut self, name: String, span: &Span, callpath: CallPath) {
-        if let Ok(mut uri) = get_url_from_span(self.engines.se(), span) {
-            let converted_url = self.session.sync.temp_to_workspace_url(&uri);
-            if let Ok(url) = converted_url {
-                uri = url;
-            }
+        if let Ok(uri) = get_url_from_span(self.engines.se(), span) {
             let range = get_range_from_span(span);
             self.related_types.push(RelatedType {
                 name,
@@ -124,19 +120,10 @@ impl<'a> HoverLinkContents<'a> {
     }
 
     /// Adds implementations to the list of implementation spans, with the declaration span first.
-    /// Ensure that all paths are converted to workspace paths before adding them.
     fn add_implementations(&mut self, decl_span: &Span, mut impl_spans: Vec<Span>) {
         let mut all_spans = vec![decl_span.clone()];
         all_spans.append(&mut impl_spans);
         all_spans.dedup();
-        for span in &all_spans {
-            let span_result = self
-                .session
-                .sync
-                .temp_to_workspace_span(self.engines.se(), span);
-            if let Ok(span) = span_result {
-                self.implementations.push(span);
-            }
-        }
+        self.implementations.append(&mut all_spans);
     }
 }
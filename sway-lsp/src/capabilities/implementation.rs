@@ -0,0 +1,204 @@
+use crate::{
+    capabilities::{call_hierarchy::span_location, type_hierarchy::interface_decls},
+    core::{session::Session, token::TypedAstToken},
+};
+use lsp_types::{request::GotoImplementationResponse, Location, Position, Url};
+use std::sync::Arc;
+use sway_core::{
+    decl_engine::InterfaceDeclId,
+    engine_threading::SpannedWithEngines,
+    language::ty::{self, TyDecl},
+    Engines,
+};
+use sway_types::{Ident, Span, Spanned};
+
+/// Returns the implementations of the item declared or referenced at the given position.
+///
+/// For traits and ABIs these are the impl blocks implementing them, for structs and enums the
+/// impl blocks implementing a trait for them, and for functions declared in a trait or ABI the
+/// functions implementing them.
+pub fn goto_implementation(
+    session: Arc<Session>,
+    uri: &Url,
+    position: Position,
+) -> Option<GotoImplementationResponse> {
+    let _p = tracing::trace_span!("goto_implementation").entered();
+    let engines = session.engines.read();
+    let decl_ident = session
+        .token_map()
+        .token_at_position(uri, position)?
+        .value()
+        .declared_token_ident(&engines)?;
+    let decl = session
+        .token_map()
+        .try_get(&decl_ident)
+        .try_unwrap()?
+        .value()
+        .typed
+        .clone()?;
+    let spans = match decl {
+        TypedAstToken::TypedDeclaration(decl @ (TyDecl::TraitDecl(_) | TyDecl::AbiDecl(_))) => {
+            impl_spans_for_interface(&session, &engines, &decl.span(&engines))
+        }
+        TypedAstToken::TypedDeclaration(decl @ (TyDecl::StructDecl(_) | TyDecl::EnumDecl(_))) => {
+            let namespace = session.namespace()?;
+            namespace
+                .module(&engines)
+                .current_items()
+                .get_impl_spans_for_decl(&engines, &decl)
+        }
+        TypedAstToken::TypedTraitFn(trait_fn) => {
+            let interface = enclosing_interface(&session, &engines, &trait_fn)?;
+            impl_fn_spans(
+                &session,
+                &engines,
+                &interface.span(&engines),
+                &trait_fn.name,
+            )
+        }
+        TypedAstToken::TypedFunctionDeclaration(fn_decl) => {
+            trait_fn_impl_spans(&session, &engines, &fn_decl)?
+        }
+        TypedAstToken::TypedDeclaration(TyDecl::FunctionDecl(ty::FunctionDecl { decl_id })) => {
+            trait_fn_impl_spans(&session, &engines, &engines.de().get_function(&decl_id))?
+        }
+        _ => return None,
+    };
+
+    let mut locations: Vec<Location> = vec![];
+    for (url, range) in spans
+        .iter()
+        .filter_map(|span| span_location(&engines, span))
+    {
+        let location = Location::new(url, range);
+        if !locations.contains(&location) {
+            locations.push(location);
+        }
+    }
+    if locations.is_empty() {
+        return None;
+    }
+    Some(GotoImplementationResponse::Array(locations))
+}
+
+/// Returns the spans of the impl blocks implementing the trait or ABI declared at the given span.
+fn impl_spans_for_interface(session: &Session, engines: &Engines, decl_span: &Span) -> Vec<Span> {
+    let Some(namespace) = session.namespace() else {
+        return vec![];
+    };
+    namespace
+        .module(engines)
+        .current_items()
+        .get_impls_for_trait_decl_span(decl_span)
+        .into_iter()
+        .map(|(_, impl_span)| impl_span)
+        .collect()
+}
+
+/// Returns the name spans of the functions named `fn_name` within the impl blocks implementing the
+/// trait or ABI declared at the given span.
+fn impl_fn_spans(
+    session: &Session,
+    engines: &Engines,
+    decl_span: &Span,
+    fn_name: &Ident,
+) -> Vec<Span> {
+    // The trait map does not hold the items of ABI implementations, so the functions implemented
+    // within the program are searched as well.
+    let mut spans: Vec<Span> = session
+        .token_map()
+        .iter()
+        .filter_map(|entry| match &entry.value().typed {
+            Some(TypedAstToken::TypedFunctionDeclaration(fn_decl))
+                if fn_decl.name == *fn_name
+                    && implemented_interface_span(engines, fn_decl).as_ref() == Some(decl_span) =>
+            {
+                Some(fn_decl.name.span())
+            }
+            _ => None,
+        })
+        .collect();
+    if let Some(namespace) = session.namespace() {
+        spans.extend(
+            namespace
+                .module(engines)
+                .current_items()
+                .get_impl_items_for_trait_decl_span(decl_span, fn_name.as_str())
+                .into_iter()
+                .filter_map(|item| match item {
+                    ty::TyTraitItem::Fn(decl_ref) => {
+                        Some(engines.de().get_function(&decl_ref).name.span())
+                    }
+                    _ => None,
+                }),
+        );
+    }
+    spans
+}
+
+/// Returns the span of the trait or ABI declaration implemented by the impl block declaring the
+/// given function, if any.
+fn implemented_interface_span(engines: &Engines, fn_decl: &ty::TyFunctionDecl) -> Option<Span> {
+    match fn_decl.implementing_type.as_ref()? {
+        TyDecl::ImplSelfOrTrait(ty::ImplSelfOrTrait { decl_id }) => engines
+            .de()
+            .get_impl_self_or_trait(decl_id)
+            .trait_decl_ref
+            .as_ref()
+            .map(|decl_ref| decl_ref.decl_span().clone()),
+        _ => None,
+    }
+}
+
+/// If the given function is declared in or implements a trait or ABI, returns the name spans of
+/// all of the functions implementing it.
+fn trait_fn_impl_spans(
+    session: &Session,
+    engines: &Engines,
+    fn_decl: &ty::TyFunctionDecl,
+) -> Option<Vec<Span>> {
+    let interface_decl_id = match fn_decl.implementing_type.as_ref()? {
+        TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => InterfaceDeclId::Trait(*decl_id),
+        TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => InterfaceDeclId::Abi(*decl_id),
+        TyDecl::ImplSelfOrTrait(ty::ImplSelfOrTrait { decl_id }) => engines
+            .de()
+            .get_impl_self_or_trait(decl_id)
+            .trait_decl_ref
+            .as_ref()?
+            .id()
+            .clone(),
+        _ => return None,
+    };
+    let decl_span = match interface_decl_id {
+        InterfaceDeclId::Trait(decl_id) => engines.de().get_trait(&decl_id).span.clone(),
+        InterfaceDeclId::Abi(decl_id) => engines.de().get_abi(&decl_id).span.clone(),
+    };
+    Some(impl_fn_spans(session, engines, &decl_span, &fn_decl.name))
+}
+
+/// Returns the trait or ABI declaring the given function in its interface.
+fn enclosing_interface(
+    session: &Session,
+    engines: &Engines,
+    trait_fn: &ty::TyTraitFn,
+) -> Option<TyDecl> {
+    let declares_fn = |interface_surface: &[ty::TyTraitInterfaceItem]| {
+        interface_surface.iter().any(|item| match item {
+            ty::TyTraitInterfaceItem::TraitFn(decl_ref) => {
+                engines.de().get_trait_fn(decl_ref).name.span() == trait_fn.name.span()
+            }
+            _ => false,
+        })
+    };
+    interface_decls(session)
+        .into_iter()
+        .find(|decl| match decl {
+            TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => {
+                declares_fn(&engines.de().get_trait(decl_id).interface_surface)
+            }
+            TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => {
+                declares_fn(&engines.de().get_abi(decl_id).interface_surface)
+            }
+            _ => false,
+        })
+}
@@ -8,8 +8,14 @@ use crate::{
 use lsp_types::{self, Range, Url};
 use std::sync::Arc;
 use sway_core::{
-    language::ty::{TyDecl, TyExpression, TyExpressionVariant},
+    fuel_prelude::{fuel_crypto::Hasher, fuel_types::Bytes32},
+    ir_generation::storage::get_storage_key_string,
+    language::{
+        ty::{TyDecl, TyExpression, TyExpressionVariant, TyFunctionDecl, TyStorageAccess},
+        Literal,
+    },
     type_system::TypeInfo,
+    Engines,
 };
 use sway_types::{Ident, Spanned};
 
@@ -17,6 +23,8 @@ use sway_types::{Ident, Spanned};
 pub enum InlayKind {
     TypeHint,
     Parameter,
+    ClosingBrace,
+    StorageKey,
 }
 
 #[derive(Debug)]
@@ -35,61 +43,74 @@ pub fn inlay_hints(
 ) -> Option<Vec<lsp_types::InlayHint>> {
     let _span = tracing::trace_span!("inlay_hints").entered();
 
-    if !config.type_hints {
+    if !config.type_hints
+        && !config.parameter_hints
+        && !config.closing_brace_hints
+        && !config.storage_key_hints
+    {
         return None;
     }
 
+    let is_in_range =
+        |hint_range: &Range| hint_range.start >= range.start && hint_range.end <= range.end;
+    let engines = session.engines.read();
+
     // 1. Iterate through all tokens in the file
-    // 2. Filter for TypedVariableDeclaration tokens within the provided range
-    // 3. For each variable declaration:
-    //    a. If it's a function application, generate parameter hints
-    //    b. If it doesn't have a type ascription and its type is known:
-    //       - Look up the type information
-    //       - Generate a type hint
-    // 4. Collect all generated hints into a single vector
-    let hints: Vec<lsp_types::InlayHint> = session
+    // 2. Generate the hints enabled in the config for the tokens within the provided range:
+    //    a. Parameter hints for the arguments of function applications
+    //    b. Type hints for variable declarations without a type ascription whose type is known
+    //    c. Return type hints after the closing brace of long function declarations
+    //    d. Storage key hints for storage accesses
+    // 3. Collect all generated hints into a single vector, ordered by position
+    let mut hints: Vec<lsp_types::InlayHint> = session
         .token_map()
         .tokens_for_file(uri)
-        .filter_map(|item| {
-            let token = item.value();
-            token.typed.as_ref().and_then(|t| match t {
-                TypedAstToken::TypedDeclaration(TyDecl::VariableDecl(var_decl)) => {
-                    let var_range = get_range_from_span(&var_decl.name.span());
-                    if var_range.start >= range.start && var_range.end <= range.end {
-                        Some(var_decl.clone())
-                    } else {
-                        None
+        .flat_map(|item| {
+            let (ident, token) = item.pair();
+            let mut hints = Vec::new();
+            match &token.typed {
+                Some(TypedAstToken::TypedExpression(TyExpression {
+                    expression: TyExpressionVariant::FunctionApplication { arguments, .. },
+                    ..
+                })) if config.parameter_hints && is_in_range(&ident.range) => {
+                    hints.extend(handle_function_parameters(arguments, config));
+                }
+                Some(TypedAstToken::TypedDeclaration(TyDecl::VariableDecl(var)))
+                    if config.type_hints
+                        && var.type_ascription.call_path_tree.is_none()
+                        && is_in_range(&get_range_from_span(&var.name.span())) =>
+                {
+                    let type_info = engines.te().get(var.type_ascription.type_id);
+                    if !matches!(
+                        *type_info,
+                        TypeInfo::Unknown | TypeInfo::UnknownGeneric { .. }
+                    ) {
+                        let range = get_range_from_span(&var.name.span());
+                        let kind = InlayKind::TypeHint;
+                        let label = format!("{}", engines.help_out(&var.type_ascription));
+                        let inlay_hint = InlayHint { range, kind, label };
+                        hints.push(self::inlay_hint(config, inlay_hint));
                     }
                 }
-                _ => None,
-            })
-        })
-        .flat_map(|var| {
-            let mut hints = Vec::new();
-
-            // Function parameter hints
-            if let TyExpressionVariant::FunctionApplication { arguments, .. } = &var.body.expression
-            {
-                hints.extend(handle_function_parameters(arguments, config));
-            }
-
-            // Variable declaration hints
-            if var.type_ascription.call_path_tree.is_none() {
-                let type_info = session.engines.read().te().get(var.type_ascription.type_id);
-                if !matches!(
-                    *type_info,
-                    TypeInfo::Unknown | TypeInfo::UnknownGeneric { .. }
-                ) {
-                    let range = get_range_from_span(&var.name.span());
-                    let kind = InlayKind::TypeHint;
-                    let label = format!("{}", session.engines.read().help_out(var.type_ascription));
-                    let inlay_hint = InlayHint { range, kind, label };
-                    hints.push(self::inlay_hint(config, inlay_hint));
+                // The type parameters and where clauses of a function are tokenized with its
+                // declaration as well, only its name gets the hint.
+                Some(TypedAstToken::TypedFunctionDeclaration(fn_decl))
+                    if config.closing_brace_hints
+                        && ident.range == get_range_from_span(&fn_decl.name.span()) =>
+                {
+                    hints.extend(handle_closing_brace(&engines, fn_decl, range, config));
                 }
+                Some(TypedAstToken::TypedStorageAccess(storage_access))
+                    if config.storage_key_hints =>
+                {
+                    hints.extend(handle_storage_access(storage_access, range, config));
+                }
+                _ => {}
             }
             hints
         })
         .collect();
+    hints.sort_by_key(|hint| hint.position);
 
     Some(hints)
 }
@@ -100,42 +121,99 @@ fn handle_function_parameters(
 ) -> Vec<lsp_types::InlayHint> {
     arguments
         .iter()
-        .flat_map(|(name, exp)| {
-            let mut hints = Vec::new();
-            let (should_create_hint, span) = match &exp.expression {
-                TyExpressionVariant::Literal(_)
-                | TyExpressionVariant::ConstantExpression { .. }
-                | TyExpressionVariant::Tuple { .. }
-                | TyExpressionVariant::Array { .. }
-                | TyExpressionVariant::ArrayIndex { .. }
-                | TyExpressionVariant::FunctionApplication { .. }
-                | TyExpressionVariant::StructFieldAccess { .. }
-                | TyExpressionVariant::TupleElemAccess { .. } => (true, &exp.span),
+        .filter(|(name, exp)| {
+            // Skip the receiver of method calls and arguments already named after their parameter.
+            name.as_str() != "self"
+                && argument_name(exp) != Some(name.as_str().trim_start_matches('_'))
+        })
+        .map(|(name, exp)| {
+            let span = match &exp.expression {
                 TyExpressionVariant::EnumInstantiation {
                     call_path_binding, ..
-                } => (true, &call_path_binding.span),
-                _ => (false, &exp.span),
+                } => &call_path_binding.span,
+                _ => &exp.span,
             };
-            if should_create_hint {
-                let range = get_range_from_span(span);
-                let kind = InlayKind::Parameter;
-                let label = name.as_str().to_string();
-                let inlay_hint = InlayHint { range, kind, label };
-                hints.push(self::inlay_hint(config, inlay_hint));
-            }
-            // Handle nested function applications
-            if let TyExpressionVariant::FunctionApplication {
-                arguments: nested_args,
-                ..
-            } = &exp.expression
-            {
-                hints.extend(handle_function_parameters(nested_args, config));
-            }
-            hints
+            let range = get_range_from_span(span);
+            let kind = InlayKind::Parameter;
+            let label = name.as_str().to_string();
+            let inlay_hint = InlayHint { range, kind, label };
+            self::inlay_hint(config, inlay_hint)
         })
         .collect::<Vec<_>>()
 }
 
+/// Returns the name of the variable or struct field passed as an argument, if any.
+fn argument_name(exp: &TyExpression) -> Option<&str> {
+    match &exp.expression {
+        TyExpressionVariant::VariableExpression { name, .. } => Some(name.as_str()),
+        TyExpressionVariant::StructFieldAccess {
+            field_to_access, ..
+        } => Some(field_to_access.name.as_str()),
+        _ => None,
+    }
+}
+
+fn handle_closing_brace(
+    engines: &Engines,
+    fn_decl: &TyFunctionDecl,
+    range: &Range,
+    config: &InlayHintsConfig,
+) -> Option<lsp_types::InlayHint> {
+    let fn_range = get_range_from_span(&fn_decl.span);
+    let line_count = (fn_range.end.line - fn_range.start.line + 1) as usize;
+    if line_count < config.closing_brace_hints_min_lines
+        || fn_range.end < range.start
+        || fn_range.end > range.end
+    {
+        return None;
+    }
+    let return_type = engines.te().get(fn_decl.return_type.type_id);
+    let label = if return_type.is_unit() {
+        format!("fn {}", fn_decl.name)
+    } else {
+        format!(
+            "fn {} -> {}",
+            fn_decl.name,
+            engines.help_out(&fn_decl.return_type)
+        )
+    };
+    let inlay_hint = InlayHint {
+        range: fn_range,
+        kind: InlayKind::ClosingBrace,
+        label,
+    };
+    Some(self::inlay_hint(config, inlay_hint))
+}
+
+fn handle_storage_access(
+    storage_access: &TyStorageAccess,
+    range: &Range,
+    config: &InlayHintsConfig,
+) -> Option<lsp_types::InlayHint> {
+    let field = storage_access.fields.first()?;
+    let field_range = get_range_from_span(&field.name.span());
+    if field_range.start < range.start || field_range.end > range.end {
+        return None;
+    }
+    let key = match storage_access.key_expression.as_deref() {
+        None => Hasher::hash(get_storage_key_string(
+            storage_access.storage_field_names.clone(),
+        )),
+        Some(TyExpression {
+            expression: TyExpressionVariant::Literal(Literal::B256(bytes)),
+            ..
+        }) => Bytes32::new(*bytes),
+        // Keys given by any other expression are only known once the expression is evaluated.
+        Some(_) => return None,
+    };
+    let inlay_hint = InlayHint {
+        range: field_range,
+        kind: InlayKind::StorageKey,
+        label: format!("{key:#x}"),
+    };
+    Some(self::inlay_hint(config, inlay_hint))
+}
+
 fn inlay_hint(config: &InlayHintsConfig, inlay_hint: InlayHint) -> lsp_types::InlayHint {
     let truncate_label = |label: String| -> String {
         if let Some(max_length) = config.max_length {
@@ -158,22 +236,27 @@ fn inlay_hint(config: &InlayHintsConfig, inlay_hint: InlayHint) -> lsp_types::In
     lsp_types::InlayHint {
         position: match inlay_hint.kind {
             // after annotated thing
-            InlayKind::TypeHint => inlay_hint.range.end,
+            InlayKind::TypeHint | InlayKind::ClosingBrace | InlayKind::StorageKey => {
+                inlay_hint.range.end
+            }
             InlayKind::Parameter => inlay_hint.range.start,
         },
         label: lsp_types::InlayHintLabel::String(truncate_label(label)),
         kind: match inlay_hint.kind {
             InlayKind::TypeHint => Some(lsp_types::InlayHintKind::TYPE),
             InlayKind::Parameter => Some(lsp_types::InlayHintKind::PARAMETER),
+            InlayKind::ClosingBrace | InlayKind::StorageKey => None,
         },
         tooltip: None,
         padding_left: Some(match inlay_hint.kind {
             InlayKind::TypeHint => !config.render_colons,
             InlayKind::Parameter => false,
+            InlayKind::ClosingBrace | InlayKind::StorageKey => true,
         }),
         padding_right: Some(match inlay_hint.kind {
             InlayKind::TypeHint => false,
             InlayKind::Parameter => !config.render_colons,
+            InlayKind::ClosingBrace | InlayKind::StorageKey => false,
         }),
         text_edits: None,
         data: None,
@@ -0,0 +1,580 @@
+//! Validation, completion and hover documentation for `Forc.toml` manifests.
+
+use crate::utils::document::{offset_of_position, position_of_offset};
+use forc_pkg::manifest::{Dependency, PackageManifest, WorkspaceManifest};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Diagnostic, DiagnosticSeverity,
+    Documentation, Hover, HoverContents, MarkupContent, MarkupKind, Position, Range, TextEdit, Url,
+};
+use std::path::Path;
+use sway_utils::constants::MANIFEST_FILE_NAME;
+use toml_edit::{ImDocument, Item};
+
+/// The source of the diagnostics reported for manifests.
+const DIAGNOSTIC_SOURCE: &str = "forc";
+
+/// A key of a manifest table.
+struct Field {
+    key: &'static str,
+    ty: &'static str,
+    documentation: &'static str,
+}
+
+const fn field(key: &'static str, ty: &'static str, documentation: &'static str) -> Field {
+    Field {
+        key,
+        ty,
+        documentation,
+    }
+}
+
+/// The tables that may be declared at the top level of a manifest.
+const TABLES: &[Field] = &[
+    field("project", "table", "The metadata of the package."),
+    field(
+        "network",
+        "table",
+        "The network the package is deployed to.",
+    ),
+    field(
+        "dependencies",
+        "table",
+        "The libraries the package depends on, by name.",
+    ),
+    field(
+        "contract-dependencies",
+        "table",
+        "The contracts the package depends on, by name. Their contract IDs are available to the package at compile time.",
+    ),
+    field(
+        "patch",
+        "table",
+        "Overrides of the dependencies fetched from a source, declared per source, e.g. `[patch.'https://github.com/fuellabs/sway']`.",
+    ),
+    field(
+        "build-profile",
+        "table",
+        "Named sets of build options, selected with `--build-profile`. The `debug` and `release` profiles are always available.",
+    ),
+    field(
+        "proxy",
+        "table",
+        "The proxy contract updated when the contract is deployed.",
+    ),
+    field(
+        "workspace",
+        "table",
+        "The member packages of a workspace. A workspace manifest declares no `[project]`.",
+    ),
+];
+
+const PROJECT_FIELDS: &[Field] = &[
+    field("authors", "array of strings", "The authors of the package."),
+    field("name", "string", "The name of the package."),
+    field(
+        "organization",
+        "string",
+        "The organization the package belongs to.",
+    ),
+    field("license", "string", "The license of the package."),
+    field(
+        "entry",
+        "string",
+        "The entry file of the package, relative to its `src` directory. Defaults to `main.sw`.",
+    ),
+    field(
+        "implicit-std",
+        "boolean",
+        "Whether `std` is added to the dependencies when neither `core` nor `std` is declared. Defaults to `true`.",
+    ),
+    field(
+        "forc-version",
+        "string",
+        "The minimum version of `forc` required to build the package.",
+    ),
+];
+
+const NETWORK_FIELDS: &[Field] = &[field(
+    "url",
+    "string",
+    "The URL of the node. Defaults to `http://127.0.0.1:4000`.",
+)];
+
+const PROXY_FIELDS: &[Field] = &[
+    field(
+        "enabled",
+        "boolean",
+        "Whether a proxy contract is deployed or updated along with the contract.",
+    ),
+    field(
+        "address",
+        "string",
+        "The ID of an existing proxy contract, updated to point to the newly deployed contract.",
+    ),
+];
+
+const WORKSPACE_FIELDS: &[Field] = &[field(
+    "members",
+    "array of strings",
+    "The directories of the member packages, relative to the workspace manifest.",
+)];
+
+const DEPENDENCY_FIELDS: &[Field] = &[
+    field("version", "string", "The version of the dependency."),
+    field(
+        "path",
+        "string",
+        "The directory of the dependency, relative to this manifest.",
+    ),
+    field(
+        "git",
+        "string",
+        "The URL of the git repository the dependency is fetched from.",
+    ),
+    field(
+        "branch",
+        "string",
+        "The branch of the git repository to fetch. Requires `git`.",
+    ),
+    field(
+        "tag",
+        "string",
+        "The tag of the git repository to fetch. Requires `git`.",
+    ),
+    field(
+        "rev",
+        "string",
+        "The commit of the git repository to fetch. Requires `git`.",
+    ),
+    field(
+        "package",
+        "string",
+        "The name of the package within the source, if it differs from the name of the dependency.",
+    ),
+    field(
+        "ipfs",
+        "string",
+        "The IPFS CID the dependency is fetched from.",
+    ),
+];
+
+const CONTRACT_DEPENDENCY_FIELDS: &[Field] = &[field(
+    "salt",
+    "string",
+    "The `0x`-prefixed salt used to compute the contract ID of the dependency. Defaults to zero.",
+)];
+
+const BUILD_PROFILE_FIELDS: &[Field] = &[
+    field("print-ast", "boolean", "Print the typed AST of the package."),
+    field(
+        "print-dca-graph",
+        "string",
+        "Print the dead code analysis graph to the given path, or to stdout if empty.",
+    ),
+    field(
+        "print-dca-graph-url-format",
+        "string",
+        "The URL format of the source locations in the printed dead code analysis graph, e.g. `vscode://file/{path}:{line}:{col}`.",
+    ),
+    field(
+        "print-ir",
+        "table",
+        "The IR states of the package to print, e.g. `{ initial = true, final = true, modified = false, passes = [] }`.",
+    ),
+    field(
+        "print-asm",
+        "table",
+        "The assembly of the package to print, e.g. `{ virtual = false, allocated = false, final = true }`.",
+    ),
+    field(
+        "print-bytecode",
+        "boolean",
+        "Print the bytecode of the package.",
+    ),
+    field(
+        "print-bytecode-spans",
+        "boolean",
+        "Print the source spans of the printed bytecode.",
+    ),
+    field("terse", "boolean", "Print fewer build messages."),
+    field(
+        "time-phases",
+        "boolean",
+        "Print the time taken by each phase of compilation.",
+    ),
+    field(
+        "metrics-outfile",
+        "string",
+        "Write the time taken by each phase of compilation to the given file.",
+    ),
+    field(
+        "include-tests",
+        "boolean",
+        "Compile the unit tests of the package.",
+    ),
+    field(
+        "error-on-warnings",
+        "boolean",
+        "Treat warnings as errors.",
+    ),
+    field(
+        "reverse-results",
+        "boolean",
+        "Output the results of tests in reverse order.",
+    ),
+    field(
+        "optimization-level",
+        "integer",
+        "The optimization level of the IR, either `0` or `1`.",
+    ),
+    field(
+        "experimental",
+        "table",
+        "Experimental features of the compiler to enable, e.g. `{ new_encoding = true }`.",
+    ),
+];
+
+/// Returns true if the document at the given [Url] is a `Forc.toml` manifest.
+pub fn is_manifest(uri: &Url) -> bool {
+    uri.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .is_some_and(|file_name| file_name == MANIFEST_FILE_NAME)
+}
+
+/// Returns the diagnostics for the text of the manifest in the given directory.
+///
+/// These are the errors of deserializing the text as a package or workspace manifest, and the
+/// errors of validating it.
+pub fn diagnostics(manifest_dir: &Path, text: &str) -> Vec<Diagnostic> {
+    let _p = tracing::trace_span!("manifest_diagnostics").entered();
+    let table = match toml::from_str::<toml::Table>(text) {
+        Ok(table) => table,
+        Err(err) => return vec![deserialization_diagnostic(text, &err)],
+    };
+    let Ok(document) = ImDocument::parse(text) else {
+        return vec![];
+    };
+    let value_span = |path: &[&str]| manifest_value(&document, path).and_then(Item::span);
+
+    if table.contains_key("workspace") {
+        let manifest = match toml::from_str::<WorkspaceManifest>(text) {
+            Ok(manifest) => manifest,
+            Err(err) => return vec![deserialization_diagnostic(text, &err)],
+        };
+        return match manifest.validate(manifest_dir) {
+            Ok(()) => vec![],
+            Err(err) => {
+                let span = value_span(&["workspace", "members"]).unwrap_or(0..0);
+                vec![diagnostic(text, span, err.to_string())]
+            }
+        };
+    }
+
+    let manifest = match toml::from_str::<PackageManifest>(text) {
+        Ok(manifest) => manifest,
+        Err(err) => return vec![deserialization_diagnostic(text, &err)],
+    };
+    // Validate each part of the manifest validated by `PackageManifest::validate` separately, so
+    // that the errors are reported at the values they concern.
+    let mut diagnostics = vec![];
+    if let Err(err) = forc_util::validate_project_name(&manifest.project.name) {
+        let span = value_span(&["project", "name"]).unwrap_or(0..0);
+        diagnostics.push(diagnostic(text, span, err.to_string()));
+    }
+    if let Some(organization) = &manifest.project.organization {
+        if let Err(err) = forc_util::validate_name(organization, "organization name") {
+            let span = value_span(&["project", "organization"]).unwrap_or(0..0);
+            diagnostics.push(diagnostic(text, span, err.to_string()));
+        }
+    }
+    for (name, dependency) in manifest.deps() {
+        if let Dependency::Detailed(details) = dependency {
+            if let Err(err) = details.validate() {
+                let span = value_span(&["dependencies", name]).unwrap_or(0..0);
+                diagnostics.push(diagnostic(text, span, format!("{name}: {err}")));
+            }
+        }
+    }
+    if diagnostics.is_empty() {
+        if let Err(err) = manifest.validate() {
+            diagnostics.push(diagnostic(text, 0..0, err.to_string()));
+        }
+    }
+    diagnostics
+}
+
+fn deserialization_diagnostic(text: &str, err: &toml::de::Error) -> Diagnostic {
+    diagnostic(
+        text,
+        err.span().unwrap_or(0..0),
+        format!("failed to parse manifest: {}", err.message()),
+    )
+}
+
+fn diagnostic(text: &str, span: std::ops::Range<usize>, message: String) -> Diagnostic {
+    Diagnostic {
+        range: Range::new(
+            position_of_offset(text, span.start),
+            position_of_offset(text, span.end),
+        ),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Where the cursor is within a manifest.
+#[derive(Debug, PartialEq, Eq)]
+enum ManifestContext {
+    /// The name of a table in a table header, e.g. `[proj`.
+    TableHeader,
+    /// A key of the table with the given path, e.g. `["build-profile", "release"]`.
+    Key { table: Vec<String> },
+}
+
+/// Returns the completion items for the table names and keys of a manifest.
+pub fn completion_items(text: &str, position: Position) -> Option<Vec<CompletionItem>> {
+    let offset = offset_of_position(text, position)?;
+    let before_cursor = &text[..offset];
+    let prefix_start = key_start(before_cursor);
+    let (context, _) = manifest_context(text, prefix_start)?;
+    let range = Range::new(position_of_offset(text, prefix_start), position);
+    let (fields, kind) = match &context {
+        ManifestContext::TableHeader => (TABLES.iter().collect(), CompletionItemKind::MODULE),
+        ManifestContext::Key { table } => (table_fields(table), CompletionItemKind::PROPERTY),
+    };
+    Some(
+        fields
+            .into_iter()
+            .map(|field| CompletionItem {
+                label: field.key.to_string(),
+                kind: Some(kind),
+                detail: Some(field.ty.to_string()),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: field.documentation.to_string(),
+                })),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range,
+                    new_text: field.key.to_string(),
+                })),
+                ..Default::default()
+            })
+            .collect(),
+    )
+}
+
+/// Returns the documentation of the table name or key at the given position of a manifest.
+pub fn hover(text: &str, position: Position) -> Option<Hover> {
+    let offset = offset_of_position(text, position)?;
+    let start = key_start(&text[..offset]);
+    let end = offset
+        + text[offset..]
+            .find(|c: char| !is_key_char(c))
+            .unwrap_or(text.len() - offset);
+    let key = &text[start..end];
+    if key.is_empty() {
+        return None;
+    }
+    let (context, line) = manifest_context(text, start)?;
+    let field = match &context {
+        // Only the first segment of a table header names a documented table.
+        ManifestContext::TableHeader => {
+            if !line.trim_start().trim_start_matches('[').trim().is_empty() {
+                return None;
+            }
+            TABLES.iter().find(|field| field.key == key)?
+        }
+        ManifestContext::Key { table } => table_fields(table)
+            .into_iter()
+            .find(|field| field.key == key)?,
+    };
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!(
+                "```toml\n{}\n```\n---\n{}\n\n_Type:_ {}",
+                field.key, field.documentation, field.ty
+            ),
+        }),
+        range: Some(Range::new(
+            position_of_offset(text, start),
+            position_of_offset(text, end),
+        )),
+    })
+}
+
+/// Determines whether the key starting at the given offset is a table name or a key, and returns
+/// it along with the text of its line before the key.
+fn manifest_context(text: &str, key_offset: usize) -> Option<(ManifestContext, &str)> {
+    let line_start = text[..key_offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[line_start..key_offset];
+    if line.contains('#') {
+        return None;
+    }
+    if line.trim_start().starts_with('[') {
+        return (!line.contains(']')).then_some((ManifestContext::TableHeader, line));
+    }
+
+    let mut table = current_table(&text[..line_start]);
+    // Within an inline table, e.g. `std = { pa`, the keys are those of the table named by the
+    // key the inline table is assigned to.
+    let inline_start = line.rfind('{');
+    if let Some(inline_start) = inline_start.filter(|i| !line[*i..].contains('}')) {
+        let key = line[..inline_start].trim_end().strip_suffix('=')?.trim();
+        table.push(unquote(key).to_string());
+        let inline_key = &line[line.rfind([',', '{']).map_or(0, |i| i + 1)..];
+        return (!inline_key.contains('=')).then_some((ManifestContext::Key { table }, line));
+    }
+    (!line.contains('=')).then_some((ManifestContext::Key { table }, line))
+}
+
+/// Returns the path of the table declared by the last table header in the given text.
+fn current_table(text: &str) -> Vec<String> {
+    text.lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with('['))
+        .map(|header| {
+            let header = header.trim_start_matches('[');
+            let header = &header[..header.find(']').unwrap_or(header.len())];
+            header
+                .split('.')
+                .map(|segment| unquote(segment.trim()).to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the fields of the table with the given path.
+fn table_fields(table: &[String]) -> Vec<&'static Field> {
+    let table = table.iter().map(String::as_str).collect::<Vec<_>>();
+    let fields: &[&[Field]] = match table.as_slice() {
+        [] => &[],
+        ["project"] => &[PROJECT_FIELDS],
+        ["network"] => &[NETWORK_FIELDS],
+        ["proxy"] => &[PROXY_FIELDS],
+        ["workspace"] => &[WORKSPACE_FIELDS],
+        ["build-profile", _] => &[BUILD_PROFILE_FIELDS],
+        ["dependencies", _] | ["patch", _, _] => &[DEPENDENCY_FIELDS],
+        ["contract-dependencies", _] => &[DEPENDENCY_FIELDS, CONTRACT_DEPENDENCY_FIELDS],
+        _ => &[],
+    };
+    fields.iter().flat_map(|fields| fields.iter()).collect()
+}
+
+/// Returns the byte offset at which the key ending the text starts.
+fn key_start(text: &str) -> usize {
+    text.char_indices()
+        .rev()
+        .take_while(|(_, c)| is_key_char(*c))
+        .last()
+        .map_or(text.len(), |(i, _)| i)
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+fn unquote(key: &str) -> &str {
+    key.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Returns the value of the key at the given path of the manifest, if it is declared.
+fn manifest_value<'a>(document: &'a ImDocument<&str>, path: &[&str]) -> Option<&'a Item> {
+    let mut item = document.as_item();
+    for key in path {
+        item = item.get(key)?;
+    }
+    Some(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[project]
+authors = ["Fuel Labs <contact@fuel.sh>"]
+name = "my-project"
+
+[dependencies]
+std = { pa
+
+[build-profile.release]
+te
+"#;
+
+    fn labels(text: &str, line: u32, character: u32) -> Vec<String> {
+        completion_items(text, Position::new(line, character))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    #[test]
+    fn manifest_context_for_tables_and_keys() {
+        assert_eq!(
+            manifest_context("[proj", 1).map(|(context, _)| context),
+            Some(ManifestContext::TableHeader)
+        );
+        assert_eq!(
+            manifest_context(MANIFEST, MANIFEST.find("pa\n").unwrap()).map(|(context, _)| context),
+            Some(ManifestContext::Key {
+                table: vec!["dependencies".to_string(), "std".to_string()]
+            })
+        );
+        assert_eq!(
+            manifest_context(MANIFEST, MANIFEST.find("te\n").unwrap()).map(|(context, _)| context),
+            Some(ManifestContext::Key {
+                table: vec!["build-profile".to_string(), "release".to_string()]
+            })
+        );
+        // Values are not completed.
+        assert_eq!(
+            manifest_context(MANIFEST, MANIFEST.find("my-project").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn completion_items_for_keys() {
+        assert!(labels(MANIFEST, 5, 10).contains(&"path".to_string()));
+        assert!(labels(MANIFEST, 8, 2).contains(&"terse".to_string()));
+        assert!(labels("[", 0, 1).contains(&"build-profile".to_string()));
+        assert!(labels(MANIFEST, 2, 12).is_empty());
+    }
+
+    #[test]
+    fn hover_for_keys() {
+        let key_hover = hover(MANIFEST, Position::new(1, 2)).unwrap();
+        let HoverContents::Markup(contents) = key_hover.contents else {
+            panic!("expected markup contents");
+        };
+        assert!(contents.value.contains("The authors of the package."));
+        assert!(hover(MANIFEST, Position::new(0, 3)).is_some());
+        assert!(hover(MANIFEST, Position::new(2, 12)).is_none());
+    }
+
+    #[test]
+    fn diagnostics_for_invalid_manifests() {
+        let dir = std::env::temp_dir();
+        let conflicting_source = diagnostics(
+            &dir,
+            "[project]\nname = \"my_project\"\nlicense = \"Apache-2.0\"\n\n[dependencies]\nfoo = { path = \"../foo\", branch = \"main\" }\n",
+        );
+        assert_eq!(conflicting_source.len(), 1);
+        assert_eq!(conflicting_source[0].range.start, Position::new(5, 6));
+
+        let missing_license = diagnostics(&dir, "[project]\nname = \"my_project\"\n");
+        assert_eq!(missing_license.len(), 1);
+        assert!(missing_license[0].message.contains("license"));
+
+        assert!(diagnostics(
+            &dir,
+            "[project]\nname = \"my_project\"\nlicense = \"Apache-2.0\"\n"
+        )
+        .is_empty());
+    }
+}
@@ -1,16 +1,26 @@
+pub mod call_hierarchy;
 pub mod code_actions;
 pub mod code_lens;
 pub mod completion;
 pub mod diagnostic;
+pub mod document_link;
 pub mod document_symbol;
+pub mod folding_range;
 pub mod formatting;
 pub mod highlight;
 pub mod hover;
+pub mod implementation;
 pub mod inlay_hints;
+pub mod manifest;
 pub mod on_enter;
 pub mod rename;
 pub mod runnable;
+pub mod selection_range;
 pub mod semantic_tokens;
+pub mod signature_help;
+pub mod type_definition;
+pub mod type_hierarchy;
+pub mod workspace_symbol;
 
 pub(crate) use code_actions::code_actions;
 pub(crate) use on_enter::on_enter;
@@ -17,7 +17,7 @@ const DOC_COMMENT_START: &str = "///";
 pub fn on_enter(
     config: &OnEnterConfig,
     documents: &Documents,
-    temp_uri: &Url,
+    uri: &Url,
     params: &OnEnterParams,
 ) -> Option<WorkspaceEdit> {
     if !(params.content_changes[0].text.contains(NEWLINE)) {
@@ -26,7 +26,7 @@ pub fn on_enter(
 
     let mut workspace_edit = None;
     let text_document = documents
-        .get_text_document(temp_uri)
+        .get_text_document(uri)
         .expect("could not get text document");
 
     if config.continue_doc_comments.unwrap_or(false) {
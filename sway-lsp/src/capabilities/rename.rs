@@ -2,20 +2,27 @@ use crate::{
     core::{
         session::Session,
         token::{SymbolKind, Token, TokenIdent, TypedAstToken},
-        token_map::TokenMapExt,
+        token_map::{TokenMap, TokenMapExt},
     },
     error::{LanguageServerError, RenameError},
     utils::document::get_url_from_path,
 };
 use lsp_types::{Position, PrepareRenameResponse, TextEdit, Url, WorkspaceEdit};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    sync::Arc,
+};
 use sway_core::{language::ty, Engines};
 use sway_types::SourceEngine;
 
 const RAW_IDENTIFIER: &str = "r#";
 
+/// Renames the token at the given position along with all of its references, including the ones in
+/// the given sessions of the other members of the workspace.
 pub fn rename(
     session: Arc<Session>,
+    workspace_sessions: &[Arc<Session>],
     new_name: String,
     url: &Url,
     position: Position,
@@ -50,51 +57,63 @@ pub fn rename(
         ));
     }
 
+    let engines = session.engines.read();
+    let token_maps = iter::once(session.token_map())
+        .chain(workspace_sessions.iter().map(|session| session.token_map()))
+        .collect::<Vec<_>>();
+
     // If the token is a function, find the parent declaration
     // and collect idents for all methods of ABI Decl, Trait Decl, and Impl Trait
-    let map_of_changes: HashMap<Url, Vec<TextEdit>> = (if token.kind == SymbolKind::Function {
-        find_all_methods_for_decl(&session, &session.engines.read(), url, position)?
+    let idents = if token.kind == SymbolKind::Function {
+        find_all_methods_for_decl(&session, &token_maps, &engines, url, position)?
     } else {
-        // otherwise, just find all references of the token in the token map
-        session
-            .token_map()
+        // otherwise, just find all references of the token in the token maps
+        token_maps
             .iter()
-            .all_references_of_token(token, &session.engines.read())
-            .map(|item| item.key().clone())
-            .collect::<Vec<TokenIdent>>()
-    })
-    .into_iter()
-    .filter_map(|ident| {
-        if ident.name == "self" {
-            return None;
-        }
-        let mut range = ident.range;
-        if ident.is_raw_ident() {
-            // Make sure the start char starts at the beginning,
-            // taking the r# tokens into account.
-            range.start.character -= RAW_IDENTIFIER.len() as u32;
-        }
-        if let Some(path) = &ident.path {
-            let url = get_url_from_path(path).ok()?;
-            if let Some(url) = session.sync.to_workspace_url(url) {
+            .flat_map(|token_map| {
+                token_map
+                    .iter()
+                    .all_references_of_token(token, &engines)
+                    .map(|item| item.key().clone())
+                    .collect::<Vec<TokenIdent>>()
+            })
+            .collect()
+    };
+
+    // The declarations of dependencies are collected by every member depending on them.
+    let mut seen = HashSet::new();
+    let map_of_changes: HashMap<Url, Vec<TextEdit>> = idents
+        .into_iter()
+        .filter(|ident| seen.insert(ident.clone()))
+        .filter_map(|ident| {
+            if ident.name == "self" {
+                return None;
+            }
+            let mut range = ident.range;
+            if ident.is_raw_ident() {
+                // Make sure the start char starts at the beginning,
+                // taking the r# tokens into account.
+                range.start.character -= RAW_IDENTIFIER.len() as u32;
+            }
+            if let Some(path) = &ident.path {
+                let url = get_url_from_path(path).ok()?;
                 let edit = TextEdit::new(range, new_name.clone());
                 return Some((url, vec![edit]));
-            };
-        }
+            }
 
-        None
-    })
-    .fold(HashMap::new(), |mut map, (k, mut v)| {
-        map.entry(k)
-            .and_modify(|existing| {
-                existing.append(&mut v);
-                // Sort the TextEdits by their range in reverse order so the client applies edits
-                // from the end of the document to the beginning, preventing issues with offset changes.
-                existing.sort_unstable_by(|a, b| b.range.start.cmp(&a.range.start));
-            })
-            .or_insert(v);
-        map
-    });
+            None
+        })
+        .fold(HashMap::new(), |mut map, (k, mut v)| {
+            map.entry(k)
+                .and_modify(|existing| {
+                    existing.append(&mut v);
+                    // Sort the TextEdits by their range in reverse order so the client applies edits
+                    // from the end of the document to the beginning, preventing issues with offset changes.
+                    existing.sort_unstable_by(|a, b| b.range.start.cmp(&a.range.start));
+                })
+                .or_insert(v);
+            map
+        });
     Ok(WorkspaceEdit::new(map_of_changes))
 }
 
@@ -156,9 +175,8 @@ fn is_token_in_workspace(
         .ok_or(RenameError::TokenNotFound)?;
 
     // Check the span of the tokens definitions to determine if it's in the users workspace.
-    let temp_path = &session.sync.temp_dir()?;
     if let Some(path) = &decl_ident.path {
-        if !path.starts_with(temp_path) {
+        if !session.sync.is_in_workspace(path) {
             return Err(LanguageServerError::RenameError(
                 RenameError::TokenNotPartOfWorkspace,
             ));
@@ -185,6 +203,7 @@ fn trait_interface_idents<'a>(
 /// Returns the `Ident`s of all methods found for an `AbiDecl`, `TraitDecl`, or `ImplTrait`.
 fn find_all_methods_for_decl<'a>(
     session: &'a Session,
+    token_maps: &[&'a TokenMap],
     engines: &'a Engines,
     url: &'a Url,
     position: Position,
@@ -196,10 +215,13 @@ fn find_all_methods_for_decl<'a>(
         .ok_or(RenameError::TokenNotFound)?;
     let decl_token = t.value();
 
-    let idents = session
-        .token_map()
+    let idents = token_maps
         .iter()
-        .all_references_of_token(decl_token, engines)
+        .flat_map(|token_map| {
+            token_map
+                .iter()
+                .all_references_of_token(decl_token, engines)
+        })
         .filter_map(|item| {
             let token = item.value();
             token.typed.as_ref().and_then(|typed| match typed {
@@ -0,0 +1,485 @@
+use crate::{core::token::get_range_from_span, utils::document::offset_of_position};
+use lsp_types::{Position, Range, SelectionRange};
+use std::sync::Arc;
+use sway_ast::{
+    attribute::Annotated, expr::LoopControlFlow, CodeBlockContents, Expr, ExprArrayDescriptor,
+    ExprStructField, ExprTupleDescriptor, FnArgs, IfCondition, IfExpr, Item, ItemFn, ItemImplItem,
+    ItemKind, ItemTraitItem, MatchBranchKind, Statement, StorageEntry,
+};
+use sway_error::handler::Handler;
+use sway_types::{Span, Spanned};
+
+/// Returns the selection ranges for each of the given positions within the Sway source text.
+///
+/// Each selection range starts at the innermost node of the parsed tree containing the position
+/// and expands through its ancestors up to the enclosing item.
+pub fn selection_ranges(text: &str, positions: Vec<Position>) -> Vec<SelectionRange> {
+    let _p = tracing::trace_span!("selection_ranges").entered();
+    let src: Arc<str> = Arc::from(text);
+    let module = sway_parse::parse_file(&Handler::default(), src, None).ok();
+    positions
+        .into_iter()
+        .map(|position| {
+            let empty_range = SelectionRange {
+                range: Range::new(position, position),
+                parent: None,
+            };
+            let (Some(module), Some(offset)) = (&module, offset_of_position(text, position)) else {
+                return empty_range;
+            };
+            let mut collector = SpanCollector {
+                offset,
+                spans: vec![],
+            };
+            for item in &module.value.items {
+                collector.item(item);
+            }
+            collector
+                .spans
+                .into_iter()
+                .fold(None, |parent: Option<SelectionRange>, span| {
+                    let range = get_range_from_span(&span);
+                    match parent {
+                        Some(parent) if parent.range == range => Some(parent),
+                        parent => Some(SelectionRange {
+                            range,
+                            parent: parent.map(Box::new),
+                        }),
+                    }
+                })
+                .unwrap_or(empty_range)
+        })
+        .collect()
+}
+
+/// Collects the spans of the nodes of the parsed tree containing the offset, from the outermost
+/// to the innermost node.
+struct SpanCollector {
+    offset: usize,
+    spans: Vec<Span>,
+}
+
+impl SpanCollector {
+    /// Pushes the span if it contains the offset, and returns whether it does.
+    fn push(&mut self, span: Span) -> bool {
+        if span.start() > self.offset || span.end() < self.offset {
+            return false;
+        }
+        self.spans.push(span);
+        true
+    }
+
+    fn annotated<T: Spanned>(&mut self, annotated: &Annotated<T>) -> bool {
+        let span = match annotated.attribute_list.first() {
+            Some(attribute) => Span::join(attribute.span(), &annotated.value.span()),
+            None => annotated.value.span(),
+        };
+        self.push(span) && self.push(annotated.value.span())
+    }
+
+    fn item(&mut self, item: &Item) {
+        if !self.annotated(item) {
+            return;
+        }
+        match &item.value {
+            ItemKind::Fn(item_fn) => self.item_fn(item_fn),
+            ItemKind::Impl(item_impl) => {
+                if self.push(item_impl.contents.span()) {
+                    for impl_item in item_impl.contents.get() {
+                        if !self.annotated(impl_item) {
+                            continue;
+                        }
+                        match &impl_item.value {
+                            ItemImplItem::Fn(item_fn) => self.item_fn(item_fn),
+                            ItemImplItem::Const(item_const) => self.expr_opt(&item_const.expr_opt),
+                            ItemImplItem::Type(_) => {}
+                        }
+                    }
+                }
+            }
+            ItemKind::Trait(item_trait) => {
+                self.trait_items(item_trait.trait_items.get());
+                if let Some(trait_defs) = &item_trait.trait_defs_opt {
+                    self.fns(trait_defs.get());
+                }
+            }
+            ItemKind::Abi(item_abi) => {
+                self.trait_items(item_abi.abi_items.get());
+                if let Some(abi_defs) = &item_abi.abi_defs_opt {
+                    self.fns(abi_defs.get());
+                }
+            }
+            ItemKind::Struct(item_struct) => {
+                if self.push(item_struct.fields.span()) {
+                    for field in item_struct.fields.get() {
+                        self.annotated(field);
+                    }
+                }
+            }
+            ItemKind::Enum(item_enum) => {
+                if self.push(item_enum.fields.span()) {
+                    for field in item_enum.fields.get() {
+                        self.annotated(field);
+                    }
+                }
+            }
+            ItemKind::Storage(item_storage) => {
+                if self.push(item_storage.entries.span()) {
+                    for entry in item_storage.entries.get() {
+                        if self.annotated(entry) {
+                            self.storage_entry(&entry.value);
+                        }
+                    }
+                }
+            }
+            ItemKind::Configurable(item_configurable) => {
+                if self.push(item_configurable.fields.span()) {
+                    for field in item_configurable.fields.get() {
+                        if self.annotated(field) {
+                            self.expr(&field.value.initializer);
+                        }
+                    }
+                }
+            }
+            ItemKind::Const(item_const) => self.expr_opt(&item_const.expr_opt),
+            ItemKind::Submodule(_)
+            | ItemKind::Use(_)
+            | ItemKind::TypeAlias(_)
+            | ItemKind::Error(..) => {}
+        }
+    }
+
+    fn storage_entry(&mut self, entry: &StorageEntry) {
+        if let Some(namespace) = &entry.namespace {
+            if self.push(namespace.span()) {
+                for nested_entry in namespace.get() {
+                    if self.annotated(nested_entry) {
+                        self.storage_entry(&nested_entry.value);
+                    }
+                }
+            }
+        }
+        if let Some(field) = &entry.field {
+            self.expr_opt(&field.key_expr);
+            self.expr(&field.initializer);
+        }
+    }
+
+    fn trait_items(&mut self, trait_items: &[Annotated<ItemTraitItem>]) {
+        for trait_item in trait_items {
+            if self.annotated(trait_item) {
+                if let ItemTraitItem::Const(item_const, _) = &trait_item.value {
+                    self.expr_opt(&item_const.expr_opt);
+                }
+            }
+        }
+    }
+
+    fn fns(&mut self, item_fns: &[Annotated<ItemFn>]) {
+        for item_fn in item_fns {
+            if self.annotated(item_fn) {
+                self.item_fn(&item_fn.value);
+            }
+        }
+    }
+
+    fn item_fn(&mut self, item_fn: &ItemFn) {
+        let signature = &item_fn.fn_signature;
+        if self.push(signature.span()) {
+            if self.push(signature.arguments.span()) {
+                let args = match signature.arguments.get() {
+                    FnArgs::Static(args) => Some(args),
+                    FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+                };
+                for arg in args.into_iter().flatten() {
+                    self.push(arg.span());
+                }
+            }
+            return;
+        }
+        if self.push(item_fn.body.span()) {
+            self.code_block(item_fn.body.get());
+        }
+    }
+
+    fn code_block(&mut self, code_block: &CodeBlockContents) {
+        for statement in &code_block.statements {
+            if !self.push(statement.span()) {
+                continue;
+            }
+            match statement {
+                Statement::Let(statement_let) => {
+                    self.push(statement_let.pattern.span());
+                    self.expr(&statement_let.expr);
+                }
+                Statement::Item(item) => self.item(item),
+                Statement::Expr { expr, .. } => self.expr(expr),
+                Statement::Error(..) => {}
+            }
+        }
+        if let Some(final_expr) = &code_block.final_expr_opt {
+            self.expr(final_expr);
+        }
+    }
+
+    fn expr_opt(&mut self, expr_opt: &Option<Expr>) {
+        if let Some(expr) = expr_opt {
+            self.expr(expr);
+        }
+    }
+
+    fn exprs<'a>(&mut self, exprs: impl IntoIterator<Item = &'a Expr>) {
+        for expr in exprs {
+            self.expr(expr);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        if !self.push(expr.span()) {
+            return;
+        }
+        match expr {
+            Expr::AbiCast { args, .. } => {
+                if self.push(args.span()) {
+                    self.expr(&args.get().address);
+                }
+            }
+            Expr::Struct { fields, .. } => {
+                if self.push(fields.span()) {
+                    self.struct_fields(fields.get());
+                }
+            }
+            Expr::Tuple(tuple) => {
+                if let ExprTupleDescriptor::Cons { head, tail, .. } = tuple.get() {
+                    self.expr(head);
+                    self.exprs(tail);
+                }
+            }
+            Expr::Parens(parens) => self.expr(parens.get()),
+            Expr::Block(block) => self.code_block(block.get()),
+            Expr::Array(array) => match array.get() {
+                ExprArrayDescriptor::Sequence(elements) => self.exprs(elements),
+                ExprArrayDescriptor::Repeat { value, length, .. } => {
+                    self.expr(value);
+                    self.expr(length);
+                }
+            },
+            Expr::Return { expr_opt, .. } => {
+                if let Some(expr) = expr_opt {
+                    self.expr(expr);
+                }
+            }
+            Expr::If(if_expr) => self.if_expr(if_expr),
+            Expr::Match {
+                value, branches, ..
+            } => {
+                self.expr(value);
+                if self.push(branches.span()) {
+                    for branch in branches.get() {
+                        if !self.push(branch.span()) {
+                            continue;
+                        }
+                        self.push(branch.pattern.span());
+                        match &branch.kind {
+                            MatchBranchKind::Block { block, .. } => {
+                                if self.push(block.span()) {
+                                    self.code_block(block.get());
+                                }
+                            }
+                            MatchBranchKind::Expr { expr, .. } => self.expr(expr),
+                        }
+                    }
+                }
+            }
+            Expr::While {
+                condition, block, ..
+            } => {
+                self.expr(condition);
+                if self.push(block.span()) {
+                    self.code_block(block.get());
+                }
+            }
+            Expr::For {
+                value_pattern,
+                iterator,
+                block,
+                ..
+            } => {
+                self.push(value_pattern.span());
+                self.expr(iterator);
+                if self.push(block.span()) {
+                    self.code_block(block.get());
+                }
+            }
+            Expr::FuncApp { func, args } => {
+                self.expr(func);
+                if self.push(args.span()) {
+                    self.exprs(args.get());
+                }
+            }
+            Expr::Index { target, arg } => {
+                self.expr(target);
+                if self.push(arg.span()) {
+                    self.expr(arg.get());
+                }
+            }
+            Expr::MethodCall {
+                target,
+                path_seg,
+                contract_args_opt,
+                args,
+                ..
+            } => {
+                self.expr(target);
+                self.push(path_seg.span());
+                if let Some(contract_args) = contract_args_opt {
+                    if self.push(contract_args.span()) {
+                        self.struct_fields(contract_args.get());
+                    }
+                }
+                if self.push(args.span()) {
+                    self.exprs(args.get());
+                }
+            }
+            Expr::FieldProjection { target, name, .. } => {
+                self.expr(target);
+                self.push(name.span());
+            }
+            Expr::TupleFieldProjection {
+                target, field_span, ..
+            } => {
+                self.expr(target);
+                self.push(field_span.clone());
+            }
+            Expr::Ref { expr, .. } | Expr::Deref { expr, .. } | Expr::Not { expr, .. } => {
+                self.expr(expr)
+            }
+            Expr::Mul { lhs, rhs, .. }
+            | Expr::Div { lhs, rhs, .. }
+            | Expr::Pow { lhs, rhs, .. }
+            | Expr::Modulo { lhs, rhs, .. }
+            | Expr::Add { lhs, rhs, .. }
+            | Expr::Sub { lhs, rhs, .. }
+            | Expr::Shl { lhs, rhs, .. }
+            | Expr::Shr { lhs, rhs, .. }
+            | Expr::BitAnd { lhs, rhs, .. }
+            | Expr::BitXor { lhs, rhs, .. }
+            | Expr::BitOr { lhs, rhs, .. }
+            | Expr::Equal { lhs, rhs, .. }
+            | Expr::NotEqual { lhs, rhs, .. }
+            | Expr::LessThan { lhs, rhs, .. }
+            | Expr::GreaterThan { lhs, rhs, .. }
+            | Expr::LessThanEq { lhs, rhs, .. }
+            | Expr::GreaterThanEq { lhs, rhs, .. }
+            | Expr::LogicalAnd { lhs, rhs, .. }
+            | Expr::LogicalOr { lhs, rhs, .. } => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            Expr::Reassignment {
+                assignable, expr, ..
+            } => {
+                self.push(assignable.span());
+                self.expr(expr);
+            }
+            Expr::Error(..)
+            | Expr::Path(_)
+            | Expr::Literal(_)
+            | Expr::Asm(_)
+            | Expr::Break { .. }
+            | Expr::Continue { .. } => {}
+        }
+    }
+
+    fn struct_fields<'a>(&mut self, fields: impl IntoIterator<Item = &'a ExprStructField>) {
+        for field in fields {
+            if self.push(field.span()) {
+                self.push(field.field_name.span());
+                if let Some((_, expr)) = &field.expr_opt {
+                    self.expr(expr);
+                }
+            }
+        }
+    }
+
+    fn if_expr(&mut self, if_expr: &IfExpr) {
+        match &if_expr.condition {
+            IfCondition::Expr(condition) => self.expr(condition),
+            IfCondition::Let { lhs, rhs, .. } => {
+                self.push(lhs.span());
+                self.expr(rhs);
+            }
+        }
+        if self.push(if_expr.then_block.span()) {
+            self.code_block(if_expr.then_block.get());
+        }
+        let Some((_, else_branch)) = &if_expr.else_opt else {
+            return;
+        };
+        let else_span = match else_branch {
+            LoopControlFlow::Continue(else_if_expr) => else_if_expr.span(),
+            LoopControlFlow::Break(else_block) => else_block.span(),
+        };
+        if self.push(else_span) {
+            match else_branch {
+                LoopControlFlow::Continue(else_if_expr) => self.if_expr(else_if_expr),
+                LoopControlFlow::Break(else_block) => self.code_block(else_block.get()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(selection_range: &SelectionRange) -> Vec<Range> {
+        let mut ranges = vec![selection_range.range];
+        let mut parent = &selection_range.parent;
+        while let Some(selection_range) = parent {
+            ranges.push(selection_range.range);
+            parent = &selection_range.parent;
+        }
+        ranges
+    }
+
+    #[test]
+    fn selection_ranges_expand_through_ancestors() {
+        let text = "library;\n\nfn f(a: u64) -> u64 {\n    let b = a + 1;\n    b\n}\n";
+        let selection_ranges = selection_ranges(text, vec![Position::new(3, 12)]);
+        let range = |start_line, start_character, end_line, end_character| {
+            Range::new(
+                Position::new(start_line, start_character),
+                Position::new(end_line, end_character),
+            )
+        };
+        assert_eq!(
+            ranges(&selection_ranges[0]),
+            vec![
+                // `a`
+                range(3, 12, 3, 13),
+                // `a + 1`
+                range(3, 12, 3, 17),
+                // `let b = a + 1;`
+                range(3, 4, 3, 18),
+                // The function body.
+                range(2, 20, 5, 1),
+                // The function.
+                range(2, 0, 5, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn selection_ranges_outside_items_are_empty() {
+        let text = "library;\n\nfn f() {}\n";
+        let position = Position::new(1, 0);
+        assert_eq!(
+            selection_ranges(text, vec![position]),
+            vec![SelectionRange {
+                range: Range::new(position, position),
+                parent: None,
+            }]
+        );
+    }
+}
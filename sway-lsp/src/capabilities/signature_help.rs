@@ -0,0 +1,473 @@
+use crate::{
+    core::{
+        session::Session,
+        token::{Token, TypedAstToken},
+    },
+    utils::document::{offset_of_position, position_of_offset},
+};
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, Position,
+    SignatureHelp, SignatureInformation, Url,
+};
+use std::sync::Arc;
+use sway_ast::Intrinsic;
+use sway_core::{
+    language::ty::{self, TyFunctionDecl},
+    transform::AttributeKind,
+    Engines, TypeId, TypeInfo,
+};
+use sway_types::Ident;
+
+/// A call expression surrounding the cursor, found by scanning the document text.
+#[derive(Debug, PartialEq, Eq)]
+struct CallContext {
+    /// The name of the called function, method or intrinsic.
+    callee: String,
+    /// The position of the first character of the callee name.
+    callee_position: Position,
+    /// The position of the last character of the receiver, if this is a method call.
+    receiver_position: Option<Position>,
+    /// The index of the argument the cursor is in.
+    active_argument: u32,
+}
+
+/// Returns the signature of the function, method or intrinsic called by the call expression
+/// surrounding the cursor, highlighting the parameter that corresponds to the argument the cursor
+/// is in.
+pub fn signature_help(
+    session: Arc<Session>,
+    uri: &Url,
+    position: Position,
+    text: &str,
+) -> Option<SignatureHelp> {
+    let _p = tracing::trace_span!("signature_help").entered();
+    let call = call_context(text, position)?;
+
+    if let Some(intrinsic) = Intrinsic::try_from_str(&call.callee) {
+        return Some(signature_help_for_intrinsic(
+            &intrinsic,
+            call.active_argument,
+        ));
+    }
+
+    let engines = session.engines.read();
+    let fn_decl = session
+        .token_map()
+        .token_at_position(uri, call.callee_position)
+        .and_then(|t| fn_decl_of_token(&engines, t.value()))
+        .or_else(|| match call.receiver_position {
+            Some(receiver_position) => {
+                let t = session
+                    .token_map()
+                    .token_at_position(uri, receiver_position)?;
+                let type_id = t.value().type_id()?;
+                method_of_type(&session, &engines, type_id, &call.callee)
+            }
+            None => function_in_namespace(&session, &engines, &call.callee),
+        })?;
+
+    // The receiver of a method call is passed as the `self` parameter.
+    let is_method_call = call.receiver_position.is_some()
+        && fn_decl
+            .parameters
+            .first()
+            .is_some_and(ty::TyFunctionParameter::is_self);
+    let active_parameter = call.active_argument + u32::from(is_method_call);
+    Some(signature_help_for_fn_decl(
+        &engines,
+        &fn_decl,
+        active_parameter,
+    ))
+}
+
+/// Returns the function declaration called by the given token, if the token is the name within a
+/// function or method application, including calls into a contract ABI.
+fn fn_decl_of_token(engines: &Engines, token: &Token) -> Option<TyFunctionDecl> {
+    match &token.typed {
+        Some(TypedAstToken::TypedExpression(ty::TyExpression {
+            expression: ty::TyExpressionVariant::FunctionApplication { fn_ref, .. },
+            ..
+        })) => Some((*engines.de().get_function(fn_ref)).clone()),
+        Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => Some(fn_decl.clone()),
+        _ => None,
+    }
+}
+
+/// Looks up the method with the given name among the methods implemented for `type_id`.
+fn method_of_type(
+    session: &Session,
+    engines: &Engines,
+    type_id: TypeId,
+    method_name: &str,
+) -> Option<TyFunctionDecl> {
+    let namespace = session.namespace()?;
+    namespace
+        .module(engines)
+        .current_items()
+        .get_methods_for_type(engines, type_id)
+        .into_iter()
+        .map(|method| method.expect_typed())
+        .find(|method| method.name().as_str() == method_name)
+        .map(|method| (*engines.de().get_function(method.id())).clone())
+}
+
+/// Looks up the free function with the given name in the root module of the program.
+fn function_in_namespace(
+    session: &Session,
+    engines: &Engines,
+    fn_name: &str,
+) -> Option<TyFunctionDecl> {
+    let namespace = session.namespace()?;
+    let items = namespace.module(engines).current_items();
+    match items
+        .symbols()
+        .get(&Ident::new_no_span(fn_name.to_string()))?
+    {
+        sway_core::namespace::ResolvedDeclaration::Typed(ty::TyDecl::FunctionDecl(
+            ty::FunctionDecl { decl_id, .. },
+        )) => Some((*engines.de().get_function(decl_id)).clone()),
+        _ => None,
+    }
+}
+
+fn signature_help_for_fn_decl(
+    engines: &Engines,
+    fn_decl: &TyFunctionDecl,
+    active_parameter: u32,
+) -> SignatureHelp {
+    let parameters = fn_decl
+        .parameters
+        .iter()
+        .map(|param| {
+            if param.is_self() {
+                return "self".to_string();
+            }
+            format!(
+                "{}: {}",
+                param.name.as_str(),
+                engines.help_out(&param.type_argument.type_id)
+            )
+        })
+        .collect::<Vec<_>>();
+    let return_type = match &*engines.te().get(fn_decl.return_type.type_id) {
+        TypeInfo::Tuple(fields) if fields.is_empty() => String::new(),
+        _ => format!(" -> {}", engines.help_out(&fn_decl.return_type.type_id)),
+    };
+    let label = format!(
+        "fn {}({}){return_type}",
+        fn_decl.name.as_str(),
+        parameters.join(", ")
+    );
+    let documentation = fn_decl
+        .attributes
+        .get(&AttributeKind::DocComment)
+        .map(|attributes| {
+            attributes
+                .iter()
+                .filter_map(|attribute| attribute.args.first())
+                .map(|arg| arg.name.as_str().trim())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|doc| !doc.is_empty())
+        .map(|doc| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: doc,
+            })
+        });
+    signature_help_from_parts(label, parameters, documentation, active_parameter)
+}
+
+fn signature_help_for_intrinsic(intrinsic: &Intrinsic, active_parameter: u32) -> SignatureHelp {
+    let parameters = intrinsic_parameters(intrinsic)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let label = format!("__{intrinsic}({})", parameters.join(", "));
+    signature_help_from_parts(label, parameters, None, active_parameter)
+}
+
+fn signature_help_from_parts(
+    label: String,
+    parameters: Vec<String>,
+    documentation: Option<Documentation>,
+    active_parameter: u32,
+) -> SignatureHelp {
+    let parameters = parameters
+        .into_iter()
+        .map(|param| ParameterInformation {
+            label: ParameterLabel::Simple(param),
+            documentation: None,
+        })
+        .collect();
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation,
+            parameters: Some(parameters),
+            active_parameter: Some(active_parameter),
+        }],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    }
+}
+
+/// Returns the names of the arguments expected by the given intrinsic.
+fn intrinsic_parameters(intrinsic: &Intrinsic) -> &'static [&'static str] {
+    match intrinsic {
+        Intrinsic::IsReferenceType
+        | Intrinsic::SizeOfType
+        | Intrinsic::SizeOfStr
+        | Intrinsic::IsStrArray
+        | Intrinsic::AssertIsStrArray
+        | Intrinsic::JmpMem
+        | Intrinsic::EncodeBufferEmpty => &[],
+        Intrinsic::SizeOfVal | Intrinsic::AddrOf | Intrinsic::Log | Intrinsic::Not => &["val"],
+        Intrinsic::ToStrArray => &["s"],
+        Intrinsic::Eq
+        | Intrinsic::Gt
+        | Intrinsic::Lt
+        | Intrinsic::Add
+        | Intrinsic::Sub
+        | Intrinsic::Mul
+        | Intrinsic::Div
+        | Intrinsic::And
+        | Intrinsic::Or
+        | Intrinsic::Xor
+        | Intrinsic::Lsh
+        | Intrinsic::Rsh
+        | Intrinsic::Mod => &["lhs", "rhs"],
+        Intrinsic::Gtf => &["index", "tx_field_id"],
+        Intrinsic::StateClear => &["key", "slots"],
+        Intrinsic::StateLoadWord => &["key"],
+        Intrinsic::StateStoreWord => &["key", "val"],
+        Intrinsic::StateLoadQuad | Intrinsic::StateStoreQuad => &["key", "ptr", "slots"],
+        Intrinsic::Revert => &["code"],
+        Intrinsic::PtrAdd | Intrinsic::PtrSub => &["ptr", "count"],
+        Intrinsic::Smo => &["recipient", "data", "coins"],
+        Intrinsic::ContractCall => &["params", "coins", "asset_id", "gas"],
+        Intrinsic::ContractRet => &["ptr", "len"],
+        Intrinsic::EncodeBufferAppend => &["buffer", "val"],
+        Intrinsic::EncodeBufferAsRawSlice => &["buffer"],
+        Intrinsic::Slice => &["item", "start", "end"],
+        Intrinsic::ElemAt => &["item", "index"],
+    }
+}
+
+/// Finds the innermost call expression whose argument list contains the given position.
+///
+/// This works on the text rather than on the AST, as the call being typed is usually incomplete
+/// and can not be parsed yet.
+fn call_context(text: &str, position: Position) -> Option<CallContext> {
+    let offset = offset_of_position(text, position)?;
+    let before_cursor = &without_strings_and_comments(&text[..offset]);
+
+    // Walk backwards to the unclosed opening parenthesis, counting the arguments on the way.
+    let mut depth = 0u32;
+    let mut active_argument = 0;
+    let mut open_paren = None;
+    for (i, c) in before_cursor.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                open_paren = Some(i);
+                break;
+            }
+            // The cursor is within an array, a block or a struct expression.
+            '[' | '{' | ';' => return None,
+            ',' if depth == 0 => active_argument += 1,
+            _ => {}
+        }
+    }
+
+    // Skip any whitespace and turbofish between the callee name and the parenthesis.
+    let mut callee_end = before_cursor[..open_paren?].trim_end();
+    if let Some(without_turbofish) = strip_turbofish(callee_end) {
+        callee_end = without_turbofish.trim_end();
+    }
+    let callee_start = callee_end
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_'))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let callee = &callee_end[callee_start..];
+    if callee.is_empty() || callee.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let before_callee = callee_end[..callee_start].trim_end();
+    let receiver_position = before_callee
+        .strip_suffix('.')
+        .map(str::trim_end)
+        .and_then(|receiver| receiver.char_indices().last())
+        .map(|(i, _)| position_of_offset(text, i));
+
+    Some(CallContext {
+        callee: callee.to_string(),
+        callee_position: position_of_offset(text, callee_start),
+        receiver_position,
+        active_argument,
+    })
+}
+
+/// Returns the text with the contents of its string literals and comments replaced by spaces, so
+/// that the brackets and commas within them are ignored. Each replaced character is replaced by as
+/// many spaces as it has bytes, so that the offsets within the text are kept.
+fn without_strings_and_comments(text: &str) -> String {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum State {
+        Code,
+        Str,
+        LineComment,
+        BlockComment(u32),
+    }
+
+    let blank = |c: char| " ".repeat(c.len_utf8());
+    let mut code = String::with_capacity(text.len());
+    let mut state = State::Code;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match state {
+            State::Code => {
+                match (c, chars.peek()) {
+                    ('"', _) => state = State::Str,
+                    ('/', Some('/')) => state = State::LineComment,
+                    ('/', Some('*')) => state = State::BlockComment(0),
+                    _ => {}
+                }
+                code.push(c);
+            }
+            State::Str => match c {
+                '"' => {
+                    state = State::Code;
+                    code.push(c);
+                }
+                '\\' => {
+                    code.push_str(&blank(c));
+                    if let Some(escaped) = chars.next() {
+                        code.push_str(&blank(escaped));
+                    }
+                }
+                _ => code.push_str(&blank(c)),
+            },
+            State::LineComment => {
+                if c == '\n' {
+                    state = State::Code;
+                    code.push(c);
+                } else {
+                    code.push_str(&blank(c));
+                }
+            }
+            State::BlockComment(depth) => {
+                match (c, chars.peek()) {
+                    ('/', Some('*')) => state = State::BlockComment(depth + 1),
+                    ('*', Some('/')) => {
+                        chars.next();
+                        code.push_str("  ");
+                        state = match depth {
+                            0 => State::Code,
+                            _ => State::BlockComment(depth - 1),
+                        };
+                        continue;
+                    }
+                    _ => {}
+                }
+                code.push_str(&blank(c));
+            }
+        }
+    }
+    code
+}
+
+/// Strips a trailing turbofish, e.g. `::<u64>`, from the given text.
+fn strip_turbofish(text: &str) -> Option<&str> {
+    if !text.ends_with('>') {
+        return None;
+    }
+    let mut depth = 0u32;
+    for (i, c) in text.char_indices().rev() {
+        match c {
+            '>' => depth += 1,
+            '<' => {
+                depth -= 1;
+                if depth == 0 {
+                    return text[..i].trim_end().strip_suffix("::");
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_context_free_function() {
+        let text = "fn main() {\n    let x = add(1, foo(2), 3);\n}";
+        let call = call_context(text, Position::new(1, 26)).unwrap();
+        assert_eq!(call.callee, "add");
+        assert_eq!(call.callee_position, Position::new(1, 12));
+        assert_eq!(call.receiver_position, None);
+        assert_eq!(call.active_argument, 2);
+
+        let call = call_context(text, Position::new(1, 23)).unwrap();
+        assert_eq!(call.callee, "foo");
+        assert_eq!(call.active_argument, 0);
+    }
+
+    #[test]
+    fn call_context_method_with_turbofish() {
+        let text = "    vec.get::<u64>(1, ";
+        let call = call_context(text, Position::new(0, 22)).unwrap();
+        assert_eq!(call.callee, "get");
+        assert_eq!(call.callee_position, Position::new(0, 8));
+        assert_eq!(call.receiver_position, Some(Position::new(0, 6)));
+        assert_eq!(call.active_argument, 1);
+    }
+
+    #[test]
+    fn call_context_abi_cast() {
+        let text = "    let x = abi(Wallet, id).send(1, ";
+        let call = call_context(text, Position::new(0, 36)).unwrap();
+        assert_eq!(call.callee, "send");
+        assert_eq!(call.callee_position, Position::new(0, 28));
+        assert_eq!(call.receiver_position, Some(Position::new(0, 26)));
+        assert_eq!(call.active_argument, 1);
+    }
+
+    #[test]
+    fn call_context_non_ascii() {
+        let text = "café.foo(";
+        let call = call_context(text, Position::new(0, 9)).unwrap();
+        assert_eq!(call.callee, "foo");
+        assert_eq!(call.callee_position, Position::new(0, 5));
+        assert_eq!(call.receiver_position, Some(Position::new(0, 3)));
+
+        let text = "//→foo(";
+        assert_eq!(call_context(text, Position::new(0, 7)), None);
+        let text = "x→foo(";
+        let call = call_context(text, Position::new(0, 6)).unwrap();
+        assert_eq!(call.callee, "foo");
+        assert_eq!(call.callee_position, Position::new(0, 2));
+    }
+
+    #[test]
+    fn call_context_skips_strings_and_comments() {
+        let text = "foo(\"a, (b\", /* ) */ 2, // (\n    3, ";
+        let call = call_context(text, Position::new(1, 7)).unwrap();
+        assert_eq!(call.callee, "foo");
+        assert_eq!(call.active_argument, 3);
+    }
+
+    #[test]
+    fn call_context_outside_of_call() {
+        assert_eq!(call_context("let x = (1, 2);", Position::new(0, 12)), None);
+        assert_eq!(call_context("foo(Bar { a: 1, ", Position::new(0, 16)), None);
+        assert_eq!(call_context("let x = 1;", Position::new(0, 9)), None);
+    }
+}
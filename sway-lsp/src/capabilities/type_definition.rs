@@ -0,0 +1,40 @@
+use crate::core::{session::Session, token::ident_of_type_id};
+use lsp_types::{request::GotoTypeDefinitionResponse, Location, Position, Url};
+use std::sync::Arc;
+use sway_core::{Engines, TypeId, TypeInfo};
+
+/// Returns the declaration of the type of the value or item at the given position.
+///
+/// References, arrays and slices resolve to the declaration of their element type.
+pub fn goto_type_definition(
+    session: Arc<Session>,
+    uri: &Url,
+    position: Position,
+) -> Option<GotoTypeDefinitionResponse> {
+    let _p = tracing::trace_span!("goto_type_definition").entered();
+    let engines = session.engines.read();
+    let type_id = session
+        .token_map()
+        .token_at_position(uri, position)?
+        .value()
+        .type_id()?;
+    let decl_ident = ident_of_type_id(&engines, &element_type_id(&engines, type_id))?;
+    let url = Url::from_file_path(decl_ident.path?).ok()?;
+    Some(GotoTypeDefinitionResponse::Scalar(Location::new(
+        url,
+        decl_ident.range,
+    )))
+}
+
+/// Strips any references, arrays and slices from the given type.
+fn element_type_id(engines: &Engines, type_id: TypeId) -> TypeId {
+    match &*engines.te().get(type_id) {
+        TypeInfo::Ref {
+            referenced_type: type_argument,
+            ..
+        }
+        | TypeInfo::Array(type_argument, _)
+        | TypeInfo::Slice(type_argument) => element_type_id(engines, type_argument.type_id),
+        _ => type_id,
+    }
+}
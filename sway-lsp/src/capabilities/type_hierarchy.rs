@@ -0,0 +1,285 @@
+use crate::{
+    capabilities::{call_hierarchy::span_location, workspace_symbol::module_path},
+    core::{
+        session::Session,
+        token::{get_range_from_span, TypedAstToken},
+    },
+};
+use lsp_types::{Position, Range, SymbolKind, TypeHierarchyItem, Url};
+use std::sync::Arc;
+use sway_core::{
+    engine_threading::SpannedWithEngines,
+    language::{
+        parsed::Supertrait,
+        ty::{self, TyDecl},
+    },
+    Engines, TypeId, TypeInfo,
+};
+use sway_types::{Ident, Span, Spanned};
+
+/// Returns the struct, enum, trait or ABI declared or referenced at the given position.
+pub fn prepare_type_hierarchy(
+    session: Arc<Session>,
+    uri: &Url,
+    position: Position,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let _p = tracing::trace_span!("prepare_type_hierarchy").entered();
+    let engines = session.engines.read();
+    let decl = decl_at_position(&session, &engines, uri, position)?;
+    Some(vec![decl_item(&engines, &decl)?])
+}
+
+/// Returns the supertypes of the type described by the given item.
+///
+/// These are the supertraits of a trait or ABI, and the traits implemented by a struct or enum.
+pub fn supertypes(
+    session: Arc<Session>,
+    uri: &Url,
+    item: &TypeHierarchyItem,
+) -> Vec<TypeHierarchyItem> {
+    let _p = tracing::trace_span!("supertypes").entered();
+    let engines = session.engines.read();
+    let Some(decl) = decl_at_position(&session, &engines, uri, item.selection_range.start) else {
+        return vec![];
+    };
+    let interfaces = interface_decls(&session);
+    let supertraits = |supertraits: &[Supertrait]| {
+        supertraits
+            .iter()
+            .filter_map(|supertrait| match &supertrait.decl_ref {
+                Some(decl_ref) => find_interface(&interfaces, &engines, decl_ref.decl_span()),
+                None => interfaces
+                    .iter()
+                    .find(|decl| decl_name(&engines, decl) == supertrait.name.suffix)
+                    .cloned(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let supertypes = match &decl {
+        TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => {
+            supertraits(&engines.de().get_trait(decl_id).supertraits)
+        }
+        TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => {
+            supertraits(&engines.de().get_abi(decl_id).supertraits)
+        }
+        TyDecl::StructDecl(_) | TyDecl::EnumDecl(_) => {
+            let Some(namespace) = session.namespace() else {
+                return vec![];
+            };
+            namespace
+                .module(&engines)
+                .current_items()
+                .get_trait_decl_spans_for_decl(&engines, &decl)
+                .into_iter()
+                .filter_map(|(trait_decl_span, _)| {
+                    find_interface(&interfaces, &engines, &trait_decl_span)
+                })
+                .collect()
+        }
+        _ => vec![],
+    };
+    dedup_items(
+        supertypes
+            .iter()
+            .filter_map(|decl| decl_item(&engines, decl))
+            .collect(),
+    )
+}
+
+/// Returns the subtypes of the type described by the given item.
+///
+/// These are the traits and ABIs declaring a trait or ABI as their supertrait, and the types
+/// implementing it.
+pub fn subtypes(
+    session: Arc<Session>,
+    uri: &Url,
+    item: &TypeHierarchyItem,
+) -> Vec<TypeHierarchyItem> {
+    let _p = tracing::trace_span!("subtypes").entered();
+    let engines = session.engines.read();
+    let Some(decl) = decl_at_position(&session, &engines, uri, item.selection_range.start) else {
+        return vec![];
+    };
+    let (decl_name, decl_span) = match &decl {
+        TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => {
+            let trait_decl = engines.de().get_trait(decl_id);
+            (trait_decl.name.clone(), trait_decl.span.clone())
+        }
+        TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => {
+            let abi_decl = engines.de().get_abi(decl_id);
+            (abi_decl.name.clone(), abi_decl.span.clone())
+        }
+        _ => return vec![],
+    };
+
+    // Traits and ABIs extending this one.
+    let mut subtypes = interface_decls(&session)
+        .iter()
+        .filter(|interface| {
+            let supertraits = match interface {
+                TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => {
+                    engines.de().get_trait(decl_id).supertraits.clone()
+                }
+                TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => {
+                    engines.de().get_abi(decl_id).supertraits.clone()
+                }
+                _ => vec![],
+            };
+            supertraits
+                .iter()
+                .any(|supertrait| match &supertrait.decl_ref {
+                    Some(decl_ref) => decl_ref.decl_span() == &decl_span,
+                    None => supertrait.name.suffix == decl_name,
+                })
+        })
+        .filter_map(|interface| decl_item(&engines, interface))
+        .collect::<Vec<_>>();
+
+    // Types implementing this trait or ABI.
+    if let Some(namespace) = session.namespace() {
+        let impls = namespace
+            .module(&engines)
+            .current_items()
+            .get_impls_for_trait_decl_span(&decl_span);
+        subtypes.extend(impls.into_iter().filter_map(|(type_id, impl_span)| {
+            implementing_type_item(&engines, type_id, &impl_span)
+        }));
+    }
+    dedup_items(subtypes)
+}
+
+/// Resolves the token at the given position to the type declaration it declares or references.
+fn decl_at_position(
+    session: &Session,
+    engines: &Engines,
+    uri: &Url,
+    position: Position,
+) -> Option<TyDecl> {
+    let token = session.token_map().token_at_position(uri, position)?;
+    let decl_ident = token.value().declared_token_ident(engines)?;
+    let decl_token = session.token_map().try_get(&decl_ident).try_unwrap()?;
+    match &decl_token.value().typed {
+        Some(TypedAstToken::TypedDeclaration(
+            decl @ (TyDecl::StructDecl(_)
+            | TyDecl::EnumDecl(_)
+            | TyDecl::TraitDecl(_)
+            | TyDecl::AbiDecl(_)),
+        )) => Some(decl.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the trait and ABI declarations of the program and its dependencies.
+pub(crate) fn interface_decls(session: &Session) -> Vec<TyDecl> {
+    session
+        .token_map()
+        .iter()
+        .filter_map(|entry| match &entry.value().typed {
+            Some(TypedAstToken::TypedDeclaration(
+                decl @ (TyDecl::TraitDecl(_) | TyDecl::AbiDecl(_)),
+            )) => Some(decl.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the trait or ABI with the given declaration span.
+fn find_interface(interfaces: &[TyDecl], engines: &Engines, span: &Span) -> Option<TyDecl> {
+    interfaces
+        .iter()
+        .find(|decl| &decl.span(engines) == span)
+        .cloned()
+}
+
+fn decl_name(engines: &Engines, decl: &TyDecl) -> Ident {
+    match decl {
+        TyDecl::StructDecl(ty::StructDecl { decl_id }) => {
+            engines.de().get_struct(decl_id).call_path.suffix.clone()
+        }
+        TyDecl::EnumDecl(ty::EnumDecl { decl_id }) => {
+            engines.de().get_enum(decl_id).call_path.suffix.clone()
+        }
+        TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => {
+            engines.de().get_trait(decl_id).name.clone()
+        }
+        TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => engines.de().get_abi(decl_id).name.clone(),
+        _ => Ident::new(decl.span(engines)),
+    }
+}
+
+fn decl_item(engines: &Engines, decl: &TyDecl) -> Option<TypeHierarchyItem> {
+    let (kind, detail) = match decl {
+        TyDecl::StructDecl(ty::StructDecl { decl_id }) => (
+            SymbolKind::STRUCT,
+            module_path(&engines.de().get_struct(decl_id).call_path),
+        ),
+        TyDecl::EnumDecl(ty::EnumDecl { decl_id }) => (
+            SymbolKind::ENUM,
+            module_path(&engines.de().get_enum(decl_id).call_path),
+        ),
+        TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => (
+            SymbolKind::INTERFACE,
+            module_path(&engines.de().get_trait(decl_id).call_path),
+        ),
+        TyDecl::AbiDecl(_) => (SymbolKind::INTERFACE, Some("abi".to_string())),
+        _ => return None,
+    };
+    let name = decl_name(engines, decl);
+    let (uri, selection_range) = span_location(engines, &name.span())?;
+    Some(TypeHierarchyItem {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        detail,
+        uri,
+        range: get_range_from_span(&decl.span(engines)),
+        selection_range,
+        data: None,
+    })
+}
+
+/// Returns the item for a type implementing a trait.
+///
+/// Types without a declaration, such as `Contract` or `u64`, are represented by the impl block.
+fn implementing_type_item(
+    engines: &Engines,
+    type_id: TypeId,
+    impl_span: &Span,
+) -> Option<TypeHierarchyItem> {
+    match &*engines.te().get(type_id) {
+        TypeInfo::Struct(decl_id) => decl_item(
+            engines,
+            &TyDecl::StructDecl(ty::StructDecl { decl_id: *decl_id }),
+        ),
+        TypeInfo::Enum(decl_id) => decl_item(
+            engines,
+            &TyDecl::EnumDecl(ty::EnumDecl { decl_id: *decl_id }),
+        ),
+        _ => {
+            let (uri, range) = span_location(engines, impl_span)?;
+            Some(TypeHierarchyItem {
+                name: engines.help_out(type_id).to_string(),
+                kind: SymbolKind::CLASS,
+                tags: None,
+                detail: None,
+                uri,
+                range,
+                selection_range: Range::new(range.start, range.start),
+                data: None,
+            })
+        }
+    }
+}
+
+fn dedup_items(mut items: Vec<TypeHierarchyItem>) -> Vec<TypeHierarchyItem> {
+    let mut seen = vec![];
+    items.retain(|item| {
+        let key = (item.uri.clone(), item.selection_range);
+        if seen.contains(&key) {
+            return false;
+        }
+        seen.push(key);
+        true
+    });
+    items
+}
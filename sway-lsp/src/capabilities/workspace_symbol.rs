@@ -0,0 +1,286 @@
+use crate::{
+    capabilities::document_symbol::symbol_kind,
+    core::{
+        token::{get_range_from_span, AstToken, Token, TokenIdent, TypedAstToken},
+        token_map::TokenMap,
+    },
+};
+use lsp_types::{Location, Range, SymbolInformation, Url};
+use std::{collections::HashSet, path::PathBuf};
+use sway_core::{
+    language::{
+        parsed::Declaration,
+        ty::{self, GetDeclIdent},
+        CallPath,
+    },
+    Engines,
+};
+use sway_types::Ident;
+
+/// The maximum number of symbols returned for a single query.
+const MAX_WORKSPACE_SYMBOLS: usize = 256;
+
+/// How closely a symbol name matches a workspace symbol query, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NameMatch {
+    Exact,
+    Prefix,
+    Substring,
+    Fuzzy,
+}
+
+/// A symbol matching a workspace symbol query.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub name_match: NameMatch,
+    pub symbol: SymbolInformation,
+}
+
+/// Returns the declarations in the given [TokenMap] whose names match the query.
+///
+/// This includes the declarations of the workspace member itself as well as those of its
+/// dependencies, such as `std` and `core`.
+pub fn workspace_symbols(token_map: &TokenMap, engines: &Engines, query: &str) -> Vec<SymbolMatch> {
+    let interfaces = interface_ranges(token_map, engines);
+    token_map
+        .iter()
+        .filter_map(|entry| {
+            let (ident, token) = entry.pair();
+            if !is_workspace_symbol(token) {
+                return None;
+            }
+            let name_match = match_name(query, &ident.name)?;
+            let path = ident.path.as_ref()?;
+            let url = Url::from_file_path(path).ok()?;
+            let container_name = container_name(engines, token)
+                .or_else(|| enclosing_interface(&interfaces, ident).map(ToString::to_string));
+            Some(SymbolMatch {
+                name_match,
+                symbol: symbol_information(ident, token, url, container_name),
+            })
+        })
+        .collect()
+}
+
+/// Removes the duplicates found when searching multiple workspace members, and orders the
+/// symbols by how closely they match the query.
+pub fn rank_symbols(mut matches: Vec<SymbolMatch>) -> Vec<SymbolInformation> {
+    let mut seen = HashSet::new();
+    matches.retain(|m| {
+        seen.insert((
+            m.symbol.name.clone(),
+            m.symbol.location.uri.clone(),
+            m.symbol.location.range.start.line,
+            m.symbol.location.range.start.character,
+        ))
+    });
+    matches.sort_by(|a, b| {
+        a.name_match
+            .cmp(&b.name_match)
+            .then_with(|| a.symbol.name.len().cmp(&b.symbol.name.len()))
+            .then_with(|| a.symbol.name.cmp(&b.symbol.name))
+            .then_with(|| {
+                a.symbol
+                    .location
+                    .uri
+                    .as_str()
+                    .cmp(b.symbol.location.uri.as_str())
+            })
+    });
+    matches
+        .into_iter()
+        .take(MAX_WORKSPACE_SYMBOLS)
+        .map(|m| m.symbol)
+        .collect()
+}
+
+/// Matches the query against the symbol name, ignoring case.
+///
+/// The name matches if it contains all of the characters of the query in the same order.
+/// An empty query matches every name.
+pub(crate) fn match_name(query: &str, name: &str) -> Option<NameMatch> {
+    let query = query.to_lowercase();
+    let name = name.to_lowercase();
+    if query == name {
+        return Some(NameMatch::Exact);
+    }
+    if name.starts_with(&query) {
+        return Some(NameMatch::Prefix);
+    }
+    if name.contains(&query) {
+        return Some(NameMatch::Substring);
+    }
+    let mut name_chars = name.chars();
+    query
+        .chars()
+        .all(|q| name_chars.any(|c| c == q))
+        .then_some(NameMatch::Fuzzy)
+}
+
+/// Returns true if the token is the declaration of an item that can be searched for.
+fn is_workspace_symbol(token: &Token) -> bool {
+    match &token.parsed {
+        AstToken::Declaration(declaration) => matches!(
+            declaration,
+            Declaration::StructDeclaration(_)
+                | Declaration::EnumDeclaration(_)
+                | Declaration::TraitDeclaration(_)
+                | Declaration::AbiDeclaration(_)
+                | Declaration::FunctionDeclaration(_)
+                | Declaration::ConstantDeclaration(_)
+                | Declaration::ConfigurableDeclaration(_)
+        ),
+        AstToken::TraitFn(_) | AstToken::StorageField(_) => true,
+        _ => false,
+    }
+}
+
+/// Returns the name of the item containing the declaration, if it can be determined from
+/// the typed token.
+///
+/// This is the implementing type for methods, the storage namespace for storage fields and the
+/// module path for all other items.
+fn container_name(engines: &Engines, token: &Token) -> Option<String> {
+    match &token.typed {
+        Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => {
+            fn_container_name(engines, fn_decl)
+        }
+        Some(TypedAstToken::TypedDeclaration(decl)) => match decl {
+            ty::TyDecl::FunctionDecl(ty::FunctionDecl { decl_id }) => {
+                fn_container_name(engines, &engines.de().get_function(decl_id))
+            }
+            ty::TyDecl::StructDecl(ty::StructDecl { decl_id }) => {
+                module_path(&engines.de().get_struct(decl_id).call_path)
+            }
+            ty::TyDecl::EnumDecl(ty::EnumDecl { decl_id }) => {
+                module_path(&engines.de().get_enum(decl_id).call_path)
+            }
+            ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id }) => {
+                module_path(&engines.de().get_trait(decl_id).call_path)
+            }
+            ty::TyDecl::ConstantDecl(ty::ConstantDecl { decl_id }) => {
+                module_path(&engines.de().get_constant(decl_id).call_path)
+            }
+            ty::TyDecl::ConfigurableDecl(ty::ConfigurableDecl { decl_id }) => {
+                module_path(&engines.de().get_configurable(decl_id).call_path)
+            }
+            _ => None,
+        },
+        Some(TypedAstToken::TypedConstantDeclaration(const_decl)) => {
+            module_path(&const_decl.call_path)
+        }
+        Some(TypedAstToken::TypedConfigurableDeclaration(decl)) => module_path(&decl.call_path),
+        Some(TypedAstToken::TypedStorageField(field)) => Some(
+            std::iter::once("storage")
+                .chain(field.namespace_names.iter().map(Ident::as_str))
+                .collect::<Vec<_>>()
+                .join("::"),
+        ),
+        _ => match &token.parsed {
+            AstToken::StorageField(_) => Some("storage".to_string()),
+            _ => None,
+        },
+    }
+}
+
+pub(crate) fn fn_container_name(engines: &Engines, fn_decl: &ty::TyFunctionDecl) -> Option<String> {
+    match (&fn_decl.implementing_type, fn_decl.implementing_for_typeid) {
+        (Some(decl @ (ty::TyDecl::TraitDecl(_) | ty::TyDecl::AbiDecl(_))), _) => decl
+            .get_decl_ident(engines)
+            .map(|ident| ident.as_str().to_string()),
+        (_, Some(type_id)) => Some(engines.help_out(type_id).to_string()),
+        (Some(decl), None) => decl
+            .get_decl_ident(engines)
+            .map(|ident| ident.as_str().to_string()),
+        (None, None) => module_path(&fn_decl.call_path),
+    }
+}
+
+/// Returns the path of the module declaring the item, e.g. `std::vec`.
+pub(crate) fn module_path(call_path: &CallPath) -> Option<String> {
+    if call_path.prefixes.is_empty() {
+        return None;
+    }
+    Some(
+        call_path
+            .prefixes
+            .iter()
+            .map(Ident::as_str)
+            .collect::<Vec<_>>()
+            .join("::"),
+    )
+}
+
+/// The file, range and name of every trait and ABI declaration, used to find the interface
+/// declaring a trait function.
+fn interface_ranges(token_map: &TokenMap, engines: &Engines) -> Vec<(PathBuf, Range, String)> {
+    token_map
+        .iter()
+        .filter_map(|entry| {
+            let (name, span) = match &entry.value().typed {
+                Some(TypedAstToken::TypedDeclaration(ty::TyDecl::TraitDecl(ty::TraitDecl {
+                    decl_id,
+                }))) => {
+                    let decl = engines.de().get_trait(decl_id);
+                    (decl.name.to_string(), decl.span.clone())
+                }
+                Some(TypedAstToken::TypedDeclaration(ty::TyDecl::AbiDecl(ty::AbiDecl {
+                    decl_id,
+                }))) => {
+                    let decl = engines.de().get_abi(decl_id);
+                    (decl.name.to_string(), decl.span.clone())
+                }
+                _ => return None,
+            };
+            let path = engines.se().get_path(span.source_id()?);
+            Some((path, get_range_from_span(&span), name))
+        })
+        .collect()
+}
+
+/// Returns the name of the trait or ABI whose declaration contains the given ident.
+fn enclosing_interface<'a>(
+    interfaces: &'a [(PathBuf, Range, String)],
+    ident: &TokenIdent,
+) -> Option<&'a str> {
+    let ident_path = ident.path.as_ref()?;
+    interfaces
+        .iter()
+        .find(|(path, range, _)| {
+            path == ident_path && range.start <= ident.range.start && ident.range.end <= range.end
+        })
+        .map(|(_, _, name)| name.as_str())
+}
+
+#[allow(warnings)]
+// TODO: the "deprecated: None" field is deprecated according to this library
+fn symbol_information(
+    ident: &TokenIdent,
+    token: &Token,
+    url: Url,
+    container_name: Option<String>,
+) -> SymbolInformation {
+    SymbolInformation {
+        name: ident.name.to_string(),
+        kind: symbol_kind(&token.kind),
+        location: Location::new(url, ident.range),
+        tags: None,
+        container_name,
+        deprecated: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_name() {
+        assert_eq!(match_name("vec", "Vec"), Some(NameMatch::Exact));
+        assert_eq!(match_name("Stor", "StorageMap"), Some(NameMatch::Prefix));
+        assert_eq!(match_name("map", "StorageMap"), Some(NameMatch::Substring));
+        assert_eq!(match_name("stmp", "StorageMap"), Some(NameMatch::Fuzzy));
+        assert_eq!(match_name("", "Vec"), Some(NameMatch::Prefix));
+        assert_eq!(match_name("pmts", "StorageMap"), None);
+    }
+}
@@ -119,12 +119,20 @@ pub enum Warnings {
 
 // Options for configuring inlay hints.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct InlayHintsConfig {
     /// Whether to render leading colons for type hints, and trailing colons for parameter hints.
     pub render_colons: bool,
     /// Whether to show inlay type hints for variables.
     pub type_hints: bool,
+    /// Whether to show the names of the parameters at function call sites.
+    pub parameter_hints: bool,
+    /// Whether to show the return type of long functions after their closing brace.
+    pub closing_brace_hints: bool,
+    /// Minimum number of lines a function must span to show a hint after its closing brace.
+    pub closing_brace_hints_min_lines: usize,
+    /// Whether to show the storage keys of `storage` accesses.
+    pub storage_key_hints: bool,
     /// Maximum length for inlay hints. Set to null to have an unlimited length.
     pub max_length: Option<usize>,
 }
@@ -134,6 +142,10 @@ impl Default for InlayHintsConfig {
         Self {
             render_colons: true,
             type_hints: true,
+            parameter_hints: true,
+            closing_brace_hints: true,
+            closing_brace_hints_min_lines: 25,
+            storage_key_hints: false,
             max_length: Some(25),
         }
     }
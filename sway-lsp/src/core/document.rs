@@ -7,7 +7,6 @@ use crate::{
 use dashmap::DashMap;
 use forc_util::fs_locking::PidFileLocking;
 use lsp_types::{Position, Range, TextDocumentContentChangeEvent, Url};
-use tokio::{fs::File, io::AsyncWriteExt};
 
 #[derive(Debug, Clone)]
 pub struct TextDocument {
@@ -134,32 +133,6 @@ impl Documents {
         }
     }
 
-    /// Asynchronously writes the changes to the file and updates the document.
-    pub async fn write_changes_to_file(
-        &self,
-        uri: &Url,
-        changes: &[TextDocumentContentChangeEvent],
-    ) -> Result<(), LanguageServerError> {
-        let src = self.update_text_document(uri, changes)?;
-
-        let mut file =
-            File::create(uri.path())
-                .await
-                .map_err(|err| DocumentError::UnableToCreateFile {
-                    path: uri.path().to_string(),
-                    err: err.to_string(),
-                })?;
-
-        file.write_all(src.as_bytes())
-            .await
-            .map_err(|err| DocumentError::UnableToWriteFile {
-                path: uri.path().to_string(),
-                err: err.to_string(),
-            })?;
-
-        Ok(())
-    }
-
     /// Update the document at the given [Url] with the Vec of changes returned by the client.
     pub fn update_text_document(
         &self,
@@ -7,7 +7,7 @@ use crate::{
     core::{
         document::{Documents, TextDocument},
         sync::SyncWorkspace,
-        token::{self, TypedAstToken},
+        token,
         token_map::{TokenMap, TokenMapExt},
     },
     error::{DirectoryError, DocumentError, LanguageServerError},
@@ -18,16 +18,16 @@ use crate::{
 };
 use dashmap::DashMap;
 use forc_pkg as pkg;
-use lsp_types::{
-    CompletionItem, GotoDefinitionResponse, Location, Position, Range, SymbolInformation, Url,
-};
+use lsp_types::{GotoDefinitionResponse, Location, Position, Range, SymbolInformation, Url};
 use parking_lot::RwLock;
 use pkg::{
     manifest::{GenericManifestFile, ManifestFile},
-    BuildPlan,
+    BuildPlan, PackageManifestFile,
 };
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::{
+    collections::HashSet,
+    iter,
     path::PathBuf,
     sync::{atomic::AtomicBool, Arc},
     time::SystemTime,
@@ -66,7 +66,8 @@ pub struct Session {
     pub runnables: RunnableMap,
     pub build_plan_cache: BuildPlanCache,
     pub compiled_program: RwLock<CompiledProgram>,
-    pub engines: RwLock<Engines>,
+    /// The engines of the session, which are shared by the members of a workspace.
+    pub engines: Arc<RwLock<Engines>>,
     pub sync: SyncWorkspace,
     // Cached diagnostic results that require a lock to access. Readers will wait for writers to complete.
     pub diagnostics: Arc<RwLock<DiagnosticMap>>,
@@ -81,13 +82,19 @@ impl Default for Session {
 
 impl Session {
     pub fn new() -> Self {
+        Self::with_engines(<_>::default())
+    }
+
+    /// Create a session for a member of a workspace that shares its [Engines] with the other
+    /// members, so that their tokens can be resolved together.
+    pub fn with_engines(engines: Arc<RwLock<Engines>>) -> Self {
         Session {
             token_map: TokenMap::new(),
             runnables: DashMap::new(),
             build_plan_cache: BuildPlanCache::default(),
             metrics: DashMap::new(),
             compiled_program: RwLock::new(CompiledProgram::default()),
-            engines: <_>::default(),
+            engines,
             sync: SyncWorkspace::new(),
             diagnostics: Arc::new(RwLock::new(DiagnosticMap::new())),
         }
@@ -99,27 +106,13 @@ impl Session {
         documents: &Documents,
     ) -> Result<ProjectDirectory, LanguageServerError> {
         let manifest_dir = PathBuf::from(uri.path());
-        // Create a new temp dir that clones the current workspace
-        // and store manifest and temp paths
-        self.sync.create_temp_dir_from_workspace(&manifest_dir)?;
-        self.sync.clone_manifest_dir_to_temp()?;
+        // Store the manifest path of the workspace
+        self.sync.init_manifest_dir(&manifest_dir)?;
         // iterate over the project dir, parse all sway files
         let _ = self.store_sway_files(documents).await;
-        self.sync.watch_and_sync_manifest();
         self.sync.manifest_dir().map_err(Into::into)
     }
 
-    pub fn shutdown(&self) {
-        // shutdown the thread watching the manifest file
-        let handle = self.sync.notify_join_handle.read();
-        if let Some(join_handle) = &*handle {
-            join_handle.abort();
-        }
-
-        // Delete the temporary directory.
-        self.sync.remove_temp_dir();
-    }
-
     /// Return a reference to the [TokenMap] of the current session.
     pub fn token_map(&self) -> &TokenMap {
         &self.token_map
@@ -131,7 +124,7 @@ impl Session {
         engines: &mut Engines,
     ) -> Result<(), LanguageServerError> {
         let _p = tracing::trace_span!("garbage_collect").entered();
-        let path = self.sync.temp_dir()?;
+        let path = self.sync.manifest_dir()?;
         let program_id = { engines.se().get_program_id(&path) };
         if let Some(program_id) = program_id {
             engines.clear_program(&program_id);
@@ -151,21 +144,32 @@ impl Session {
         Ok(())
     }
 
-    pub fn token_references(&self, url: &Url, position: Position) -> Option<Vec<Location>> {
+    /// Returns the locations of all references of the token at the given position, including the
+    /// ones in the given sessions of the other members of the workspace.
+    pub fn token_references(
+        &self,
+        url: &Url,
+        position: Position,
+        workspace_sessions: &[Arc<Session>],
+    ) -> Option<Vec<Location>> {
         let _p = tracing::trace_span!("token_references").entered();
-        let token_references: Vec<_> = self
-            .token_map
-            .iter()
-            .all_references_of_token(
-                self.token_map.token_at_position(url, position)?.value(),
-                &self.engines.read(),
-            )
-            .filter_map(|item| {
-                let path = item.key().path.as_ref()?;
-                let uri = Url::from_file_path(path).ok()?;
-                self.sync
-                    .to_workspace_url(uri)
-                    .map(|workspace_url| Location::new(workspace_url, item.key().range))
+        let engines = self.engines.read();
+        let token = self.token_map.token_at_position(url, position)?;
+        // The declarations of dependencies are collected by every member depending on them.
+        let mut seen = HashSet::new();
+        let token_references = iter::once(&self.token_map)
+            .chain(workspace_sessions.iter().map(|session| session.token_map()))
+            .flat_map(|token_map| {
+                token_map
+                    .iter()
+                    .all_references_of_token(token.value(), &engines)
+                    .map(|item| item.key().clone())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|ident| seen.insert(ident.clone()))
+            .filter_map(|ident| {
+                let uri = Url::from_file_path(ident.path.as_ref()?).ok()?;
+                Some(Location::new(uri, ident.range))
             })
             .collect();
         Some(token_references)
@@ -199,48 +203,13 @@ impl Session {
             .and_then(|decl_ident| {
                 decl_ident.path.and_then(|path| {
                     // We use ok() here because we don't care about propagating the error from from_file_path
-                    Url::from_file_path(path).ok().and_then(|url| {
-                        self.sync.to_workspace_url(url).map(|url| {
-                            GotoDefinitionResponse::Scalar(Location::new(url, decl_ident.range))
-                        })
+                    Url::from_file_path(path).ok().map(|url| {
+                        GotoDefinitionResponse::Scalar(Location::new(url, decl_ident.range))
                     })
                 })
             })
     }
 
-    pub fn completion_items(
-        &self,
-        uri: &Url,
-        position: Position,
-        trigger_char: &str,
-    ) -> Option<Vec<CompletionItem>> {
-        let _p = tracing::trace_span!("completion_items").entered();
-        let shifted_position = Position {
-            line: position.line,
-            character: position.character - trigger_char.len() as u32 - 1,
-        };
-        let t = self.token_map.token_at_position(uri, shifted_position)?;
-        let ident_to_complete = t.key();
-        let engines = self.engines.read();
-        let fn_tokens =
-            self.token_map
-                .tokens_at_position(&engines, uri, shifted_position, Some(true));
-        let fn_token = fn_tokens.first()?.value();
-        let compiled_program = &*self.compiled_program.read();
-        if let Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) = fn_token.typed.clone() {
-            let program = compiled_program.typed.clone()?;
-            let engines = self.engines.read();
-            return Some(capabilities::completion::to_completion_items(
-                program.root.namespace.module(&engines).current_items(),
-                &engines,
-                ident_to_complete,
-                &fn_decl,
-                position,
-            ));
-        }
-        None
-    }
-
     /// Returns the [Namespace] from the compiled program if it exists.
     pub fn namespace(&self) -> Option<Namespace> {
         let compiled_program = &*self.compiled_program.read();
@@ -251,16 +220,29 @@ impl Session {
     pub fn symbol_information(&self, url: &Url) -> Option<Vec<SymbolInformation>> {
         let _p = tracing::trace_span!("symbol_information").entered();
         let tokens = self.token_map.tokens_for_file(url);
-        self.sync
-            .to_workspace_url(url.clone())
-            .map(|url| capabilities::document_symbol::to_symbol_information(tokens, &url))
+        Some(capabilities::document_symbol::to_symbol_information(
+            tokens, url,
+        ))
+    }
+
+    /// Returns the declarations of this workspace member and its dependencies matching the query.
+    pub fn workspace_symbols(
+        &self,
+        query: &str,
+    ) -> Vec<capabilities::workspace_symbol::SymbolMatch> {
+        let _p = tracing::trace_span!("workspace_symbols").entered();
+        let engines = self.engines.read();
+        capabilities::workspace_symbol::workspace_symbols(&self.token_map, &engines, query)
     }
 
     /// Populate [Documents] with sway files found in the workspace.
     async fn store_sway_files(&self, documents: &Documents) -> Result<(), LanguageServerError> {
-        let temp_dir = self.sync.temp_dir()?;
+        let manifest_dir = self.sync.manifest_dir()?;
         // Store the documents.
-        for path in get_sway_files(temp_dir).iter().filter_map(|fp| fp.to_str()) {
+        for path in get_sway_files(manifest_dir)
+            .iter()
+            .filter_map(|fp| fp.to_str())
+        {
             documents.store_document(TextDocument::build_from_path(path).await?)?;
         }
         Ok(())
@@ -293,6 +275,18 @@ pub fn build_plan(uri: &Url) -> Result<BuildPlan, LanguageServerError> {
         .map_err(LanguageServerError::BuildPlanFailed)
 }
 
+/// Returns the index of the package of the given session within the compilation order of the
+/// build plan.
+fn package_index(build_plan: &BuildPlan, session: &Session) -> Option<usize> {
+    let manifest_dir = session.sync.manifest_dir().ok()?;
+    let manifest = PackageManifestFile::from_dir(manifest_dir).ok()?;
+    let node = build_plan.find_member_index(manifest.project_name())?;
+    build_plan
+        .compilation_order()
+        .iter()
+        .position(|&compiled| compiled == node)
+}
+
 pub fn compile(
     build_plan: &BuildPlan,
     engines: &Engines,
@@ -449,13 +443,19 @@ pub fn parse_project(
         .build_plan_cache
         .get_or_update(&session.sync.manifest_path(), || build_plan(uri))?;
 
-    let results = compile(
+    let mut results = compile(
         &build_plan,
         engines,
         retrigger_compilation,
         lsp_mode.as_ref(),
         experimental,
     )?;
+    // The build plan of a workspace member includes the other members, and the ones depending on
+    // the member are compiled after it. Their results are dropped, so that the member is the main
+    // program.
+    if let Some(index) = package_index(&build_plan, &session) {
+        results.truncate(index + 1);
+    }
 
     // Check if the last result is None or if results is empty, indicating an error occurred in the compiler.
     // If we don't return an error here, then we will likely crash when trying to access the Engines
@@ -1,82 +1,41 @@
 use crate::{
     error::{DirectoryError, DocumentError, LanguageServerError},
-    utils::document::{get_path_from_url, get_url_from_path, get_url_from_span},
+    utils::document::get_path_from_url,
 };
-use dashmap::DashMap;
 use forc_pkg::manifest::GenericManifestFile;
-use forc_pkg::{manifest::Dependency, PackageManifestFile};
-use indexmap::IndexMap;
+use forc_pkg::{PackageManifestFile, WorkspaceManifestFile};
 use lsp_types::Url;
-use notify::RecursiveMode;
-use notify_debouncer_mini::new_debouncer;
 use parking_lot::RwLock;
 use std::{
-    fs::{self, File},
-    io::{Read, Write},
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
 };
-use sway_types::{SourceEngine, Span};
-use sway_utils::{
-    constants::{LOCK_FILE_NAME, MANIFEST_FILE_NAME},
-    SWAY_EXTENSION,
-};
-use tempfile::Builder;
-use tokio::task::JoinHandle;
-
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub enum Directory {
-    Manifest,
-    Temp,
-}
-
-#[derive(Debug)]
+use sway_core::Engines;
+use sway_types::SourceEngine;
+
+/// Keeps the compiler's view of a workspace in sync with the client.
+///
+/// The workspace is compiled in place. The contents of the documents edited in the client are
+/// stored as overlays in the [SourceEngine], so unsaved changes are compiled without writing
+/// them to disk.
+#[derive(Debug, Default)]
 pub struct SyncWorkspace {
-    pub directories: DashMap<Directory, PathBuf>,
-    pub notify_join_handle: RwLock<Option<JoinHandle<()>>>,
+    manifest_dir: RwLock<Option<PathBuf>>,
+    entry_path: RwLock<Option<PathBuf>>,
+    /// The directory of the workspace manifest, if the project is a member of a workspace.
+    workspace_dir: RwLock<Option<PathBuf>>,
 }
 
 impl SyncWorkspace {
-    pub const LSP_TEMP_PREFIX: &'static str = "SWAY_LSP_TEMP_DIR";
-
     pub(crate) fn new() -> Self {
-        Self {
-            directories: DashMap::new(),
-            notify_join_handle: RwLock::new(None),
-        }
+        Self::default()
     }
 
-    /// Overwrite the contents of the tmp/folder with everything in
-    /// the current workspace.
-    pub fn resync(&self) -> Result<(), LanguageServerError> {
-        self.clone_manifest_dir_to_temp()?;
-        if let Some(manifest) = self
-            .manifest_path()
-            .and_then(|manifest_path| PackageManifestFile::from_dir(manifest_path).ok())
-        {
-            if let Some(temp_manifest_path) = &self.temp_manifest_path() {
-                edit_manifest_dependency_paths(&manifest, temp_manifest_path);
-            }
-        }
-        Ok(())
-    }
-
-    /// Clean up the temp directory that was created once the
-    /// server closes down.
-    pub(crate) fn remove_temp_dir(&self) {
-        if let Ok(dir) = self.temp_dir() {
-            dir.parent().map(fs::remove_dir);
-        }
-    }
-
-    pub(crate) fn create_temp_dir_from_workspace(
-        &self,
-        manifest_dir: &Path,
-    ) -> Result<(), LanguageServerError> {
-        let manifest = PackageManifestFile::from_dir(manifest_dir).map_err(|_| {
+    /// Find the manifest of the project containing the given directory and store its directory.
+    pub(crate) fn init_manifest_dir(&self, dir: &Path) -> Result<(), LanguageServerError> {
+        let manifest = PackageManifestFile::from_dir(dir).map_err(|_| {
             DocumentError::ManifestFileNotFound {
-                dir: manifest_dir.to_string_lossy().to_string(),
+                dir: dir.to_string_lossy().to_string(),
             }
         })?;
 
@@ -85,251 +44,76 @@ impl SyncWorkspace {
             .path()
             .parent()
             .ok_or(DirectoryError::ManifestDirNotFound)?;
-
-        // extract the project name from the path
-        let project_name = manifest_dir
-            .file_name()
-            .and_then(|name| name.to_str())
-            .ok_or(DirectoryError::CantExtractProjectName {
-                dir: manifest_dir.to_string_lossy().to_string(),
-            })?;
-
-        // Create a new temporary directory that we can clone the current workspace into.
-        let temp_dir = Builder::new()
-            .prefix(SyncWorkspace::LSP_TEMP_PREFIX)
-            .tempdir()
-            .map_err(|_| DirectoryError::TempDirFailed)?;
-
-        let temp_path = temp_dir
-            .into_path()
-            .canonicalize()
-            .map_err(|_| DirectoryError::CanonicalizeFailed)?
-            .join(project_name);
-
-        self.directories
-            .insert(Directory::Manifest, manifest_dir.to_path_buf());
-        self.directories.insert(Directory::Temp, temp_path);
-
-        Ok(())
-    }
-
-    pub(crate) fn clone_manifest_dir_to_temp(&self) -> Result<(), DirectoryError> {
-        copy_dir_contents(self.manifest_dir()?, self.temp_dir()?)
-            .map_err(|_| DirectoryError::CopyContentsFailed)?;
+        *self.manifest_dir.write() = Some(manifest_dir.to_path_buf());
+        *self.entry_path.write() = Some(manifest.entry_path());
+        *self.workspace_dir.write() = workspace_manifest(&manifest)
+            .map(|workspace_manifest| workspace_manifest.dir().to_path_buf());
 
         Ok(())
     }
 
-    /// Convert the Url path from the client to point to the same file in our temp folder
-    pub(crate) fn workspace_to_temp_url(&self, uri: &Url) -> Result<Url, DirectoryError> {
-        convert_url(uri, &self.temp_dir()?, &self.manifest_dir()?)
-    }
-
-    /// Convert the [Url] path from the temp folder to point to the same file in the users workspace.
-    pub(crate) fn temp_to_workspace_url(&self, uri: &Url) -> Result<Url, DirectoryError> {
-        convert_url(uri, &self.manifest_dir()?, &self.temp_dir()?)
-    }
-
-    /// If it is a path to a temp directory, convert the path in the [Span] to the same file in the user's
-    /// workspace. Otherwise, return the span as-is.
-    pub(crate) fn temp_to_workspace_span(
-        &self,
-        source_engine: &SourceEngine,
-        span: &Span,
-    ) -> Result<Span, DirectoryError> {
-        let url = get_url_from_span(source_engine, span)?;
-        if is_path_in_temp_workspace(&url) {
-            let converted_url = convert_url(&url, &self.manifest_dir()?, &self.temp_dir()?)?;
-            let converted_path = get_path_from_url(&converted_url)?;
-            let source_id = source_engine.get_source_id(&converted_path);
-            let converted_span = Span::new(
-                span.src().clone(),
-                span.start(),
-                span.end(),
-                Some(source_id),
-            );
-            match converted_span {
-                Some(span) => Ok(span),
-                None => Err(DirectoryError::SpanFromPathFailed {
-                    path: converted_path.to_string_lossy().to_string(),
-                }),
-            }
-        } else {
-            Ok(span.clone())
-        }
-    }
-
-    /// If path is part of the users workspace, then convert URL from temp to workspace dir.
-    /// Otherwise, pass through if it points to a dependency path
-    pub(crate) fn to_workspace_url(&self, url: Url) -> Option<Url> {
-        if is_path_in_temp_workspace(&url) {
-            Some(self.temp_to_workspace_url(&url).ok()?)
-        } else {
-            Some(url)
-        }
-    }
-
-    pub(crate) fn temp_manifest_path(&self) -> Option<PathBuf> {
-        self.temp_dir()
-            .map(|dir| dir.join(sway_utils::constants::MANIFEST_FILE_NAME))
-            .ok()
-    }
-
     pub fn manifest_path(&self) -> Option<PathBuf> {
         self.manifest_dir()
             .map(|dir| dir.join(sway_utils::constants::MANIFEST_FILE_NAME))
             .ok()
     }
 
-    /// Watch the manifest directory and check for any save events on Forc.toml
-    pub(crate) fn watch_and_sync_manifest(&self) {
-        let _ = self
-            .manifest_path()
-            .and_then(|manifest_path| PackageManifestFile::from_dir(manifest_path).ok())
-            .map(|manifest| {
-                let manifest_dir = Arc::new(manifest.clone());
-                if let Some(temp_manifest_path) = self.temp_manifest_path() {
-                    edit_manifest_dependency_paths(&manifest, &temp_manifest_path);
-
-                    let handle = tokio::spawn(async move {
-                        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
-                        // Setup debouncer. No specific tickrate, max debounce time 2 seconds
-                        let mut debouncer = new_debouncer(Duration::from_secs(1), move |event| {
-                            if let Ok(e) = event {
-                                let _ = tx.blocking_send(e);
-                            }
-                        })
-                        .unwrap();
-
-                        debouncer
-                            .watcher()
-                            .watch(manifest_dir.as_ref().path(), RecursiveMode::NonRecursive)
-                            .unwrap();
+    /// Return the path to the entry file of the project.
+    pub(crate) fn entry_path(&self) -> Option<PathBuf> {
+        self.entry_path.read().clone()
+    }
 
-                        while let Some(_events) = rx.recv().await {
-                            // Rescan the Forc.toml and convert
-                            // relative paths to absolute. Save into our temp directory.
-                            edit_manifest_dependency_paths(&manifest, &temp_manifest_path);
-                        }
-                    });
+    /// Return the path to the directory of the workspace manifest, if the project is a member of
+    /// a workspace.
+    pub(crate) fn workspace_dir(&self) -> Option<PathBuf> {
+        self.workspace_dir.read().clone()
+    }
 
-                    // Store the join handle so we can clean up the thread on shutdown
-                    {
-                        let mut join_handle = self.notify_join_handle.write();
-                        *join_handle = Some(handle);
-                    }
-                }
-            });
+    /// Check if the path is part of the user's workspace: the project itself or, for a member of
+    /// a workspace, any of the other members. Dependencies from outside of the workspace are
+    /// read-only.
+    pub(crate) fn is_in_workspace(&self, path: &Path) -> bool {
+        self.workspace_dir()
+            .or_else(|| self.manifest_dir().ok())
+            .is_some_and(|dir| path.starts_with(dir))
     }
 
     /// Return the path to the projects manifest directory.
     pub(crate) fn manifest_dir(&self) -> Result<PathBuf, DirectoryError> {
-        self.directories
-            .try_get(&Directory::Manifest)
-            .try_unwrap()
-            .map(|item| item.value().clone())
+        self.manifest_dir
+            .read()
+            .clone()
             .ok_or(DirectoryError::ManifestDirNotFound)
     }
-
-    /// Return the path to the temporary directory that was created for the current session.
-    pub(crate) fn temp_dir(&self) -> Result<PathBuf, DirectoryError> {
-        self.directories
-            .try_get(&Directory::Temp)
-            .try_unwrap()
-            .map(|item| item.value().clone())
-            .ok_or(DirectoryError::TempDirNotFound)
-    }
 }
 
-/// Check if the current path is part of the users workspace.
-/// Returns false if the path is from a dependency
-pub(crate) fn is_path_in_temp_workspace(uri: &Url) -> bool {
-    uri.as_ref().contains(SyncWorkspace::LSP_TEMP_PREFIX)
+/// Store the contents of the document at the given [Url] in the [SourceEngine], so that they
+/// are compiled in place of the contents of the file on disk.
+pub(crate) fn update_overlay(
+    source_engine: &SourceEngine,
+    uri: &Url,
+    src: &str,
+) -> Result<(), DirectoryError> {
+    let path = get_path_from_url(uri)?;
+    source_engine.set_overlay(path, Arc::from(src));
+    Ok(())
 }
 
-fn convert_url(uri: &Url, from: &Path, to: &PathBuf) -> Result<Url, DirectoryError> {
-    let path = from.join(
-        PathBuf::from(uri.path())
-            .strip_prefix(to)
-            .map_err(DirectoryError::StripPrefixError)?,
-    );
-    get_url_from_path(&path)
+/// Remove the contents of the document at the given [Url] from the [SourceEngine], so that
+/// the file is read from disk again.
+///
+/// The cached module of the file was built from the removed contents, so it is removed as well.
+/// Returns whether the document had an overlay.
+pub(crate) fn remove_overlay(engines: &Engines, uri: &Url) -> Result<bool, DirectoryError> {
+    let path = get_path_from_url(uri)?;
+    let had_overlay = engines.se().remove_overlay(&path).is_some();
+    if had_overlay {
+        engines.qe().remove_module_cache_entries(&path);
+    }
+    Ok(had_overlay)
 }
 
-/// Deserialize the manifest file and loop through the dependencies.
-/// Check if the dependency is specifying a 'path'.
-/// If so, check if the path is relative and convert the relative path to an absolute path.
-/// Edit the toml entry using toml_edit with the absolute path.
-/// Save the manifest to temp_dir/Forc.toml.
-pub(crate) fn edit_manifest_dependency_paths(
-    manifest: &PackageManifestFile,
-    temp_manifest_path: &Path,
-) {
-    // Key = name of the dependency that has been specified will a relative path
-    // Value = the absolute path that should be used to overwrite the relateive path
-    let mut dependency_map: IndexMap<String, PathBuf> = IndexMap::new();
-
-    if let Some(deps) = &manifest.dependencies {
-        for (name, dep) in deps {
-            if let Dependency::Detailed(details) = dep {
-                if details.path.is_some() {
-                    if let Some(abs_path) = manifest.dep_path(name) {
-                        dependency_map.insert(name.clone(), abs_path);
-                    }
-                }
-            }
-        }
-    }
-
-    if dependency_map.capacity() != 0 {
-        if let Ok(mut file) = File::open(manifest.path()) {
-            let mut toml = String::new();
-            let _ = file.read_to_string(&mut toml);
-            if let Ok(mut manifest_toml) = toml.parse::<toml_edit::DocumentMut>() {
-                for (name, abs_path) in dependency_map {
-                    manifest_toml["dependencies"][&name]["path"] =
-                        toml_edit::value(abs_path.display().to_string());
-                }
-
-                if let Ok(mut file) = File::create(temp_manifest_path) {
-                    let _ = file.write_all(manifest_toml.to_string().as_bytes());
-                }
-            }
-        }
-    }
-}
-
-/// Copies only the specified files from the source directory to the target directory.
-/// This function targets files ending with `.sw`, and the specific files `Forc.toml` and `Forc.lock`.
-/// It returns `Ok(true)` if any relevant files were copied over, and `Ok(false)` if no such files were found.
-fn copy_dir_contents(
-    src_dir: impl AsRef<Path>,
-    target_dir: impl AsRef<Path>,
-) -> std::io::Result<bool> {
-    let mut has_relevant_files = false;
-    for entry in fs::read_dir(&src_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        let ty = entry.file_type()?;
-        if ty.is_dir() {
-            // Recursively check the directory; if it has relevant files, create the target directory
-            if copy_dir_contents(&path, target_dir.as_ref().join(entry.file_name()))? {
-                has_relevant_files = true;
-            }
-        } else if let Some(file_name_os) = path.file_name() {
-            if let Some(file_name) = file_name_os.to_str() {
-                if file_name.ends_with(&format!(".{SWAY_EXTENSION}"))
-                    || file_name == MANIFEST_FILE_NAME
-                    || file_name == LOCK_FILE_NAME
-                {
-                    if !has_relevant_files {
-                        fs::create_dir_all(&target_dir)?;
-                        has_relevant_files = true;
-                    }
-                    fs::copy(&path, target_dir.as_ref().join(file_name))?;
-                }
-            }
-        }
-    }
-    Ok(has_relevant_files)
+/// Returns the manifest of the workspace the package is a member of, if any.
+pub(crate) fn workspace_manifest(manifest: &PackageManifestFile) -> Option<WorkspaceManifestFile> {
+    manifest.workspace().ok().flatten()
 }
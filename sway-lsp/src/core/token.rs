@@ -180,6 +180,37 @@ impl Token {
             TypeDefinition::Ident(ident) => Some(TokenIdent::new(ident, engines.se())),
         })
     }
+
+    /// Return the [TypeId] of the value or item represented by the provided token, if known.
+    pub fn type_id(&self) -> Option<TypeId> {
+        match &self.typed {
+            Some(TypedAstToken::TypedDeclaration(ty::TyDecl::VariableDecl(variable_decl))) => {
+                Some(variable_decl.return_type)
+            }
+            Some(TypedAstToken::TypedExpression(expr)) => Some(expr.return_type),
+            Some(TypedAstToken::TypedConstantDeclaration(const_decl)) => {
+                Some(const_decl.return_type)
+            }
+            Some(TypedAstToken::TypedConfigurableDeclaration(configurable_decl)) => {
+                Some(configurable_decl.return_type)
+            }
+            Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => {
+                Some(fn_decl.return_type.type_id)
+            }
+            Some(TypedAstToken::TypedFunctionParameter(param)) => Some(param.type_argument.type_id),
+            Some(TypedAstToken::TypedStructField(field)) => Some(field.type_argument.type_id),
+            Some(TypedAstToken::TypedEnumVariant(variant)) => Some(variant.type_argument.type_id),
+            Some(TypedAstToken::TypedStorageField(field)) => Some(field.type_argument.type_id),
+            Some(TypedAstToken::TypedStorageAccessDescriptor(descriptor)) => {
+                Some(descriptor.type_id)
+            }
+            Some(TypedAstToken::TypedArgument(type_argument)) => Some(type_argument.type_id),
+            _ => match &self.type_def {
+                Some(TypeDefinition::TypeId(type_id)) => Some(*type_id),
+                _ => None,
+            },
+        }
+    }
 }
 
 /// A more convenient [Ident] type for use in the language server.
@@ -41,10 +41,6 @@ pub enum DocumentError {
     ManifestsLockPathFailed { dir: String },
     #[error("Document is already stored at {:?}", path)]
     DocumentAlreadyStored { path: String },
-    #[error("File wasn't able to be created at path {:?} : {:?}", path, err)]
-    UnableToCreateFile { path: String, err: String },
-    #[error("Unable to write string to file at {:?} : {:?}", path, err)]
-    UnableToWriteFile { path: String, err: String },
     #[error("File wasn't able to be removed at path {:?} : {:?}", path, err)]
     UnableToRemoveFile { path: String, err: String },
 
@@ -58,30 +54,16 @@ pub enum DocumentError {
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum DirectoryError {
-    #[error("Can't find temporary directory")]
-    TempDirNotFound,
     #[error("Can't find manifest directory")]
     ManifestDirNotFound,
-    #[error("Can't extract project name from {:?}", dir)]
-    CantExtractProjectName { dir: String },
     #[error("Failed to create hidden .lsp_locks directory: {0}")]
     LspLocksDirFailed(String),
-    #[error("Failed to create temp directory")]
-    TempDirFailed,
-    #[error("Failed to canonicalize path")]
-    CanonicalizeFailed,
-    #[error("Failed to copy workspace contents to temp directory")]
-    CopyContentsFailed,
-    #[error("Failed to create build plan. {0}")]
-    StripPrefixError(std::path::StripPrefixError),
     #[error("Unable to create Url from path {:?}", path)]
     UrlFromPathFailed { path: String },
     #[error("Unable to create Url from span {:?}", span)]
     UrlFromSpanFailed { span: String },
     #[error("Unable to create path from Url {:?}", url)]
     PathFromUrlFailed { url: String },
-    #[error("Unable to create span from path {:?}", path)]
-    SpanFromPathFailed { path: String },
     #[error("No program ID found for path {:?}", path)]
     ProgramIdNotFound { path: String },
 }
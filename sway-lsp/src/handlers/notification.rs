@@ -2,13 +2,15 @@
 //! Protocol. This module specifically handles notification messages sent by the Client.
 
 use crate::{
-    core::{document::Documents, session::Session},
+    capabilities,
+    core::{document::Documents, session::Session, sync},
     error::LanguageServerError,
     server_state::{CompilationContext, ServerState, TaskMessage},
+    utils::document::get_url_from_path,
 };
 use lsp_types::{
-    DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, FileChangeType, Url,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, FileChangeType, Url,
 };
 use std::{
     collections::BTreeMap,
@@ -20,6 +22,17 @@ pub async fn handle_did_open_text_document(
     state: &ServerState,
     params: DidOpenTextDocumentParams,
 ) -> Result<(), LanguageServerError> {
+    // Manifests are validated as they are edited, without compiling the project.
+    if capabilities::manifest::is_manifest(&params.text_document.uri) {
+        state
+            .manifests
+            .handle_open_file(&params.text_document.uri)
+            .await;
+        state
+            .publish_manifest_diagnostics(&params.text_document.uri)
+            .await;
+        return Ok(());
+    }
     let (uri, session) = state
         .uri_and_session_from_workspace(&params.text_document.uri)
         .await?;
@@ -28,6 +41,31 @@ pub async fn handle_did_open_text_document(
     // Otherwise, don't recompile the project when a new file in the project is opened
     // as the workspace is already compiled.
     if session.token_map().is_empty() {
+        // Compile the other members of the workspace first, so that references from them to the
+        // opened member are available as well.
+        for member_session in state.workspace_sessions(&session) {
+            if !member_session.token_map().is_empty() {
+                continue;
+            }
+            let Some(member_uri) = member_session
+                .sync
+                .entry_path()
+                .and_then(|path| get_url_from_path(&path).ok())
+            else {
+                continue;
+            };
+            let _ = state
+                .cb_tx
+                .send(TaskMessage::CompilationContext(CompilationContext {
+                    session: Some(member_session.clone()),
+                    uri: Some(member_uri),
+                    version: None,
+                    optimized_build: false,
+                    gc_options: state.config.read().garbage_collection.clone(),
+                    file_versions: BTreeMap::new(),
+                    dependent_sessions: vec![],
+                }));
+        }
         let _ = state
             .cb_tx
             .send(TaskMessage::CompilationContext(CompilationContext {
@@ -37,6 +75,7 @@ pub async fn handle_did_open_text_document(
                 optimized_build: false,
                 gc_options: state.config.read().garbage_collection.clone(),
                 file_versions: BTreeMap::new(),
+                dependent_sessions: vec![],
             }));
         state.is_compiling.store(true, Ordering::SeqCst);
 
@@ -79,6 +118,7 @@ fn send_new_compilation_request(
             optimized_build,
             gc_options: state.config.read().garbage_collection.clone(),
             file_versions,
+            dependent_sessions: state.dependent_workspace_sessions(&session),
         }));
 }
 
@@ -86,6 +126,15 @@ pub async fn handle_did_change_text_document(
     state: &ServerState,
     params: DidChangeTextDocumentParams,
 ) -> Result<(), LanguageServerError> {
+    if capabilities::manifest::is_manifest(&params.text_document.uri) {
+        state
+            .manifests
+            .update_text_document(&params.text_document.uri, &params.content_changes)?;
+        state
+            .publish_manifest_diagnostics(&params.text_document.uri)
+            .await;
+        return Ok(());
+    }
     if let Err(err) = state
         .pid_locked_files
         .mark_file_as_dirty(&params.text_document.uri)
@@ -96,10 +145,10 @@ pub async fn handle_did_change_text_document(
     let (uri, session) = state
         .uri_and_session_from_workspace(&params.text_document.uri)
         .await?;
-    state
+    let src = state
         .documents
-        .write_changes_to_file(&uri, &params.content_changes)
-        .await?;
+        .update_text_document(&uri, &params.content_changes)?;
+    sync::update_overlay(session.engines.read().se(), &uri, &src)?;
 
     let file_versions = file_versions(
         &state.documents,
@@ -145,7 +194,37 @@ pub(crate) async fn handle_did_save_text_document(
     let (uri, session) = state
         .uri_and_session_from_workspace(&params.text_document.uri)
         .await?;
-    session.sync.resync()?;
+    let file_versions = file_versions(&state.documents, &uri, None);
+    send_new_compilation_request(state, session.clone(), &uri, None, false, file_versions);
+    state.wait_for_parsing().await;
+    state
+        .publish_diagnostics(uri, params.text_document.uri, session)
+        .await;
+    Ok(())
+}
+
+pub async fn handle_did_close_text_document(
+    state: &ServerState,
+    params: DidCloseTextDocumentParams,
+) -> Result<(), LanguageServerError> {
+    if capabilities::manifest::is_manifest(&params.text_document.uri) {
+        let _ = state.manifests.remove_document(&params.text_document.uri);
+        return Ok(());
+    }
+    state
+        .pid_locked_files
+        .remove_dirty_flag(&params.text_document.uri)?;
+    // Unsaved changes are discarded when a document is closed, so the file on disk
+    // is compiled again.
+    let (uri, session) = state
+        .uri_and_session_from_workspace(&params.text_document.uri)
+        .await?;
+    let had_overlay = sync::remove_overlay(&session.engines.read(), &uri)?;
+    let _ = state.documents.remove_document(&uri);
+    state.documents.handle_open_file(&uri).await;
+    if !had_overlay {
+        return Ok(());
+    }
     let file_versions = file_versions(&state.documents, &uri, None);
     send_new_compilation_request(state, session.clone(), &uri, None, false, file_versions);
     state.wait_for_parsing().await;
@@ -160,10 +239,11 @@ pub(crate) async fn handle_did_change_watched_files(
     params: DidChangeWatchedFilesParams,
 ) -> Result<(), LanguageServerError> {
     for event in params.changes {
-        let (uri, _) = state.uri_and_session_from_workspace(&event.uri).await?;
+        let (uri, session) = state.uri_and_session_from_workspace(&event.uri).await?;
         if let FileChangeType::DELETED = event.typ {
             state.pid_locked_files.remove_dirty_flag(&event.uri)?;
             let _ = state.documents.remove_document(&uri);
+            sync::remove_overlay(&session.engines.read(), &uri)?;
         }
     }
     Ok(())
@@ -6,15 +6,17 @@ use crate::{
 };
 use forc_tracing::{tracing_subscriber, FmtSpan, StdioTracingWriter, TracingWriterMode};
 use lsp_types::{
-    CodeLens, CompletionResponse, DocumentFormattingParams, DocumentSymbolResponse,
-    InitializeResult, InlayHint, InlayHintParams, PrepareRenameResponse, RenameParams,
-    SemanticTokensParams, SemanticTokensRangeParams, SemanticTokensRangeResult,
-    SemanticTokensResult, TextDocumentIdentifier, Url, WorkspaceEdit,
+    CodeLens, CompletionResponse, DocumentFormattingParams, DocumentOnTypeFormattingParams,
+    DocumentRangeFormattingParams, DocumentSymbolResponse, InitializeResult, InlayHint,
+    InlayHintParams, PrepareRenameResponse, RenameParams, SemanticTokensParams,
+    SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
+    TextDocumentIdentifier, Url, WorkspaceEdit,
 };
 use std::{
     fs::File,
     io::Write,
     path::{Path, PathBuf},
+    sync::atomic::Ordering,
 };
 use sway_types::{Ident, Spanned};
 use sway_utils::PerformanceData;
@@ -50,6 +52,10 @@ pub fn handle_initialize(
             .init();
     }
     tracing::info!("Initializing the Sway Language Server");
+    state.supports_dynamic_registration.store(
+        crate::supports_dynamic_registration(&params.capabilities),
+        Ordering::Relaxed,
+    );
     Ok(InitializeResult {
         server_info: None,
         capabilities: crate::server_capabilities(),
@@ -76,6 +82,19 @@ pub async fn handle_document_symbol(
     }
 }
 
+pub async fn handle_workspace_symbol(
+    state: &ServerState,
+    params: lsp_types::WorkspaceSymbolParams,
+) -> Result<Option<Vec<lsp_types::SymbolInformation>>> {
+    let _ = state.wait_for_parsing().await;
+    let matches = state
+        .sessions
+        .iter()
+        .flat_map(|item| item.value().workspace_symbols(&params.query))
+        .collect();
+    Ok(Some(capabilities::workspace_symbol::rank_symbols(matches)))
+}
+
 pub async fn handle_goto_definition(
     state: &ServerState,
     params: lsp_types::GotoDefinitionParams,
@@ -95,23 +114,86 @@ pub async fn handle_goto_definition(
     }
 }
 
+pub async fn handle_goto_implementation(
+    state: &ServerState,
+    params: lsp_types::request::GotoImplementationParams,
+) -> Result<Option<lsp_types::request::GotoImplementationResponse>> {
+    let _ = state.wait_for_parsing().await;
+    match state
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::implementation::goto_implementation(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_goto_type_definition(
+    state: &ServerState,
+    params: lsp_types::request::GotoTypeDefinitionParams,
+) -> Result<Option<lsp_types::request::GotoTypeDefinitionResponse>> {
+    let _ = state.wait_for_parsing().await;
+    match state
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::type_definition::goto_type_definition(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
 pub async fn handle_completion(
     state: &ServerState,
     params: lsp_types::CompletionParams,
 ) -> Result<Option<lsp_types::CompletionResponse>> {
-    let trigger_char = params
-        .context
-        .as_ref()
-        .and_then(|ctx| ctx.trigger_character.as_deref())
-        .unwrap_or("");
-    let position = params.text_document_position.position;
+    let uri = &params.text_document_position.text_document.uri;
+    if capabilities::manifest::is_manifest(uri) {
+        return Ok(state
+            .manifests
+            .try_get(uri.path())
+            .try_unwrap()
+            .and_then(|document| {
+                capabilities::manifest::completion_items(
+                    document.get_text(),
+                    params.text_document_position.position,
+                )
+                .map(CompletionResponse::Array)
+            }));
+    }
+    let _ = state.wait_for_parsing().await;
     match state
         .uri_and_session_from_workspace(&params.text_document_position.text_document.uri)
         .await
     {
-        Ok((uri, session)) => Ok(session
-            .completion_items(&uri, position, trigger_char)
-            .map(CompletionResponse::Array)),
+        Ok((uri, session)) => {
+            let Some(document) = state.documents.try_get(uri.path()).try_unwrap() else {
+                return Ok(None);
+            };
+            let position = params.text_document_position.position;
+            Ok(capabilities::completion::completion_items(
+                session,
+                &uri,
+                position,
+                document.get_text(),
+            )
+            .map(CompletionResponse::Array))
+        }
         Err(err) => {
             tracing::error!("{}", err.to_string());
             Ok(None)
@@ -123,6 +205,19 @@ pub async fn handle_hover(
     state: &ServerState,
     params: lsp_types::HoverParams,
 ) -> Result<Option<lsp_types::Hover>> {
+    let uri = &params.text_document_position_params.text_document.uri;
+    if capabilities::manifest::is_manifest(uri) {
+        return Ok(state
+            .manifests
+            .try_get(uri.path())
+            .try_unwrap()
+            .and_then(|document| {
+                capabilities::manifest::hover(
+                    document.get_text(),
+                    params.text_document_position_params.position,
+                )
+            }));
+    }
     match state
         .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
         .await
@@ -179,7 +274,14 @@ pub async fn handle_rename(
         Ok((uri, session)) => {
             let new_name = params.new_name;
             let position = params.text_document_position.position;
-            match capabilities::rename::rename(session, new_name, &uri, position) {
+            let workspace_sessions = state.workspace_sessions(&session);
+            match capabilities::rename::rename(
+                session,
+                &workspace_sessions,
+                new_name,
+                &uri,
+                position,
+            ) {
                 Ok(res) => Ok(Some(res)),
                 Err(err) => {
                     tracing::error!("{}", err.to_string());
@@ -227,8 +329,242 @@ pub async fn handle_references(
     {
         Ok((uri, session)) => {
             let position = params.text_document_position.position;
-            Ok(session.token_references(&uri, position))
+            Ok(session.token_references(&uri, position, &state.workspace_sessions(&session)))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_signature_help(
+    state: &ServerState,
+    params: lsp_types::SignatureHelpParams,
+) -> Result<Option<lsp_types::SignatureHelp>> {
+    let _ = state.wait_for_parsing().await;
+    match state
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let Some(document) = state.documents.try_get(uri.path()).try_unwrap() else {
+                return Ok(None);
+            };
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::signature_help::signature_help(
+                session,
+                &uri,
+                position,
+                document.get_text(),
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_folding_range(
+    state: &ServerState,
+    params: lsp_types::FoldingRangeParams,
+) -> Result<Option<Vec<lsp_types::FoldingRange>>> {
+    match state
+        .uri_and_session_from_workspace(&params.text_document.uri)
+        .await
+    {
+        Ok((uri, _)) => Ok(state
+            .documents
+            .try_get(uri.path())
+            .try_unwrap()
+            .map(|document| capabilities::folding_range::folding_ranges(document.get_text()))),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_selection_range(
+    state: &ServerState,
+    params: lsp_types::SelectionRangeParams,
+) -> Result<Option<Vec<lsp_types::SelectionRange>>> {
+    match state
+        .uri_and_session_from_workspace(&params.text_document.uri)
+        .await
+    {
+        Ok((uri, _)) => Ok(state
+            .documents
+            .try_get(uri.path())
+            .try_unwrap()
+            .map(|document| {
+                capabilities::selection_range::selection_ranges(
+                    document.get_text(),
+                    params.positions,
+                )
+            })),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_document_link(
+    state: &ServerState,
+    params: lsp_types::DocumentLinkParams,
+) -> Result<Option<Vec<lsp_types::DocumentLink>>> {
+    let Ok(path) = params.text_document.uri.to_file_path() else {
+        return Ok(None);
+    };
+    if capabilities::manifest::is_manifest(&params.text_document.uri) {
+        // Prefer the text of the opened manifest, which may not have been saved yet.
+        let text = match state.manifests.get(params.text_document.uri.path()) {
+            Some(document) => Ok(document.get_text().to_string()),
+            None => std::fs::read_to_string(&path),
+        };
+        let (Some(manifest_dir), Ok(text)) = (path.parent(), text) else {
+            return Ok(None);
+        };
+        return Ok(Some(capabilities::document_link::manifest_links(
+            manifest_dir,
+            &text,
+        )));
+    }
+    match state
+        .uri_and_session_from_workspace(&params.text_document.uri)
+        .await
+    {
+        Ok((uri, _)) => Ok(state
+            .documents
+            .try_get(uri.path())
+            .try_unwrap()
+            .map(|document| capabilities::document_link::module_links(&path, document.get_text()))),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
         }
+    }
+}
+
+pub async fn handle_prepare_call_hierarchy(
+    state: &ServerState,
+    params: lsp_types::CallHierarchyPrepareParams,
+) -> Result<Option<Vec<lsp_types::CallHierarchyItem>>> {
+    let _ = state.wait_for_parsing().await;
+    match state
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::call_hierarchy::prepare_call_hierarchy(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_incoming_calls(
+    state: &ServerState,
+    params: lsp_types::CallHierarchyIncomingCallsParams,
+) -> Result<Option<Vec<lsp_types::CallHierarchyIncomingCall>>> {
+    let _ = state.wait_for_parsing().await;
+    match state.uri_and_session_from_workspace(&params.item.uri).await {
+        Ok((uri, session)) => {
+            let sessions = state
+                .sessions
+                .iter()
+                .map(|item| item.value().clone())
+                .collect::<Vec<_>>();
+            Ok(Some(capabilities::call_hierarchy::incoming_calls(
+                session,
+                &sessions,
+                &uri,
+                &params.item,
+            )))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_outgoing_calls(
+    state: &ServerState,
+    params: lsp_types::CallHierarchyOutgoingCallsParams,
+) -> Result<Option<Vec<lsp_types::CallHierarchyOutgoingCall>>> {
+    let _ = state.wait_for_parsing().await;
+    match state.uri_and_session_from_workspace(&params.item.uri).await {
+        Ok((uri, session)) => Ok(Some(capabilities::call_hierarchy::outgoing_calls(
+            session,
+            &uri,
+            &params.item,
+        ))),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_prepare_type_hierarchy(
+    state: &ServerState,
+    params: lsp_types::TypeHierarchyPrepareParams,
+) -> Result<Option<Vec<lsp_types::TypeHierarchyItem>>> {
+    let _ = state.wait_for_parsing().await;
+    match state
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::type_hierarchy::prepare_type_hierarchy(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_supertypes(
+    state: &ServerState,
+    params: lsp_types::TypeHierarchySupertypesParams,
+) -> Result<Option<Vec<lsp_types::TypeHierarchyItem>>> {
+    let _ = state.wait_for_parsing().await;
+    match state.uri_and_session_from_workspace(&params.item.uri).await {
+        Ok((uri, session)) => Ok(Some(capabilities::type_hierarchy::supertypes(
+            session,
+            &uri,
+            &params.item,
+        ))),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_subtypes(
+    state: &ServerState,
+    params: lsp_types::TypeHierarchySubtypesParams,
+) -> Result<Option<Vec<lsp_types::TypeHierarchyItem>>> {
+    let _ = state.wait_for_parsing().await;
+    match state.uri_and_session_from_workspace(&params.item.uri).await {
+        Ok((uri, session)) => Ok(Some(capabilities::type_hierarchy::subtypes(
+            session,
+            &uri,
+            &params.item,
+        ))),
         Err(err) => {
             tracing::error!("{}", err.to_string());
             Ok(None)
@@ -253,6 +589,46 @@ pub async fn handle_formatting(
         })
 }
 
+pub async fn handle_range_formatting(
+    state: &ServerState,
+    params: DocumentRangeFormattingParams,
+) -> Result<Option<Vec<lsp_types::TextEdit>>> {
+    let _ = state.wait_for_parsing().await;
+    state
+        .uri_and_session_from_workspace(&params.text_document.uri)
+        .await
+        .and_then(|(uri, _)| {
+            capabilities::formatting::format_range(&state.documents, &uri, params.range).map(Some)
+        })
+        .or_else(|err| {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        })
+}
+
+pub async fn handle_on_type_formatting(
+    state: &ServerState,
+    params: DocumentOnTypeFormattingParams,
+) -> Result<Option<Vec<lsp_types::TextEdit>>> {
+    let _ = state.wait_for_parsing().await;
+    let text_document_position = params.text_document_position;
+    state
+        .uri_and_session_from_workspace(&text_document_position.text_document.uri)
+        .await
+        .and_then(|(uri, _)| {
+            capabilities::formatting::format_on_type(
+                &state.documents,
+                &uri,
+                text_document_position.position,
+            )
+            .map(Some)
+        })
+        .or_else(|err| {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        })
+}
+
 pub async fn handle_code_action(
     state: &ServerState,
     params: lsp_types::CodeActionParams,
@@ -262,13 +638,21 @@ pub async fn handle_code_action(
         .uri_and_session_from_workspace(&params.text_document.uri)
         .await
     {
-        Ok((temp_uri, session)) => Ok(capabilities::code_actions(
-            session,
-            &params.range,
-            &params.text_document.uri,
-            &temp_uri,
-            &params.context.diagnostics,
-        )),
+        Ok((uri, session)) => {
+            let text = state
+                .documents
+                .try_get(uri.path())
+                .try_unwrap()
+                .map(|document| document.get_text().to_string())
+                .unwrap_or_default();
+            Ok(capabilities::code_actions(
+                session,
+                &params.range,
+                &uri,
+                &params.context.diagnostics,
+                &text,
+            ))
+        }
         Err(err) => {
             tracing::error!("{}", err.to_string());
             Ok(None)
@@ -15,12 +15,26 @@ mod traverse;
 pub mod utils;
 
 use lsp_types::{
-    CodeActionProviderCapability, CodeLensOptions, CompletionOptions, ExecuteCommandOptions,
-    HoverProviderCapability, OneOf, RenameOptions, SemanticTokensLegend, SemanticTokensOptions,
-    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, WorkDoneProgressOptions,
+    CallHierarchyServerCapability, ClientCapabilities, CodeActionProviderCapability,
+    CodeLensOptions, CompletionOptions, DocumentLinkOptions, DocumentOnTypeFormattingOptions,
+    ExecuteCommandOptions, FoldingRangeProviderCapability, HoverProviderCapability,
+    ImplementationProviderCapability, InitializeParams, OneOf, Registration, RenameOptions,
+    SelectionRangeProviderCapability, SemanticTokensLegend, SemanticTokensOptions,
+    ServerCapabilities, SignatureHelpOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TypeDefinitionProviderCapability, WorkDoneProgressOptions,
 };
+use serde_json::Value;
 use server_state::ServerState;
-use tower_lsp::{LspService, Server};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::Service;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    LspService, Server,
+};
 
 pub async fn start() {
     let (service, socket) = LspService::build(ServerState::new)
@@ -30,7 +44,7 @@ pub async fn start() {
         .custom_method("sway/metrics", ServerState::metrics)
         .finish();
     Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
-        .serve(service)
+        .serve(StaticCapabilities(service))
         .await;
 }
 
@@ -38,6 +52,7 @@ pub async fn start() {
 /// indicating its support for various language server protocol features.
 pub fn server_capabilities() -> ServerCapabilities {
     ServerCapabilities {
+        call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
         code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
         code_lens_provider: Some(CodeLensOptions {
             resolve_provider: Some(false),
@@ -49,12 +64,25 @@ pub fn server_capabilities() -> ServerCapabilities {
         definition_provider: Some(OneOf::Left(true)),
         document_formatting_provider: Some(OneOf::Left(true)),
         document_highlight_provider: Some(OneOf::Left(true)),
+        document_link_provider: Some(DocumentLinkOptions {
+            resolve_provider: Some(false),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+            first_trigger_character: "}".to_string(),
+            more_trigger_character: Some(vec![";".to_string()]),
+        }),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
         document_symbol_provider: Some(OneOf::Left(true)),
         execute_command_provider: Some(ExecuteCommandOptions {
             commands: vec![],
             ..Default::default()
         }),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
+        implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
         inlay_hint_provider: Some(OneOf::Left(true)),
         rename_provider: Some(OneOf::Right(RenameOptions {
             prepare_provider: Some(true),
@@ -63,6 +91,7 @@ pub fn server_capabilities() -> ServerCapabilities {
             },
         })),
         references_provider: Some(OneOf::Left(true)),
+        selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
         semantic_tokens_provider: Some(
             SemanticTokensOptions {
                 legend: SemanticTokensLegend {
@@ -74,9 +103,100 @@ pub fn server_capabilities() -> ServerCapabilities {
             }
             .into(),
         ),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
         text_document_sync: Some(TextDocumentSyncCapability::Kind(
             TextDocumentSyncKind::INCREMENTAL,
         )),
+        type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
         ..ServerCapabilities::default()
     }
 }
+
+/// Returns the capabilities registered with the client once it is initialized,
+/// as they can not be declared in the [ServerCapabilities] of `lsp-types`.
+///
+/// They are only registered with clients supporting their dynamic registration,
+/// other clients are given [static_capabilities] instead.
+pub fn dynamic_registrations() -> Vec<Registration> {
+    vec![Registration {
+        id: "sway/typeHierarchy".to_string(),
+        method: "textDocument/prepareTypeHierarchy".to_string(),
+        register_options: Some(serde_json::json!({
+            "documentSelector": [{ "language": "sway" }]
+        })),
+    }]
+}
+
+/// Returns the capabilities of [dynamic_registrations] as declared in the [ServerCapabilities]
+/// of clients that do not support registering them dynamically.
+pub fn static_capabilities() -> serde_json::Map<String, Value> {
+    let mut capabilities = serde_json::Map::new();
+    capabilities.insert("typeHierarchyProvider".to_string(), Value::Bool(true));
+    capabilities
+}
+
+/// Checks if the client supports the dynamic registration of the capabilities of
+/// [dynamic_registrations].
+pub fn supports_dynamic_registration(client_capabilities: &ClientCapabilities) -> bool {
+    client_capabilities
+        .text_document
+        .as_ref()
+        .and_then(|text_document| text_document.type_hierarchy.as_ref())
+        .and_then(|type_hierarchy| type_hierarchy.dynamic_registration)
+        .unwrap_or(false)
+}
+
+/// Wraps a language server service to declare the [static_capabilities] in the result of the
+/// `initialize` request, unless the client supports registering them dynamically.
+///
+/// The [ServerCapabilities] of `lsp-types` have no field for them, so they are added to the
+/// serialized result instead.
+pub struct StaticCapabilities<S>(pub S);
+
+impl<S> Service<Request> for StaticCapabilities<S>
+where
+    S: Service<Request, Response = Option<Response>>,
+    S::Future: Send + 'static,
+{
+    type Response = Option<Response>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let declare_static_capabilities = request.method() == "initialize"
+            && !request
+                .params()
+                .and_then(|params| serde_json::from_value::<InitializeParams>(params.clone()).ok())
+                .is_some_and(|params| supports_dynamic_registration(&params.capabilities));
+        let response = self.0.call(request);
+        Box::pin(async move {
+            Ok(response.await?.map(|response| {
+                if !declare_static_capabilities {
+                    return response;
+                }
+                let (id, result) = response.into_parts();
+                let result = result.map(|mut result| {
+                    if let Some(capabilities) = result
+                        .get_mut("capabilities")
+                        .and_then(Value::as_object_mut)
+                    {
+                        capabilities.extend(static_capabilities());
+                    }
+                    result
+                });
+                Response::from_parts(id, result)
+            }))
+        })
+    }
+}
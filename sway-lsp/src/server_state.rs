@@ -1,13 +1,15 @@
 //! The context or environment in which the language server functions.
 
 use crate::{
+    capabilities,
     config::{Config, GarbageCollectionConfig, Warnings},
     core::{
         document::{Documents, PidLockedFiles},
         session::{self, Session},
+        sync::workspace_manifest,
     },
     error::{DirectoryError, DocumentError, LanguageServerError},
-    utils::{debug, keyword_docs::KeywordDocs},
+    utils::{debug, document::get_url_from_path, keyword_docs::KeywordDocs},
 };
 use crossbeam_channel::{Receiver, Sender};
 use dashmap::{mapref::multiple::RefMulti, DashMap};
@@ -16,14 +18,14 @@ use forc_pkg::PackageManifestFile;
 use lsp_types::{Diagnostic, Url};
 use parking_lot::{Mutex, RwLock};
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{BTreeMap, HashSet, VecDeque},
     process::Command,
 };
 use std::{
     mem,
     path::PathBuf,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
 };
@@ -36,6 +38,8 @@ const DEFAULT_SESSION_CACHE_CAPACITY: usize = 4;
 /// `ServerState` is the primary mutable state of the language server
 pub struct ServerState {
     pub(crate) client: Option<Client>,
+    /// Whether the client supports registering the capabilities of [crate::dynamic_registrations].
+    pub(crate) supports_dynamic_registration: AtomicBool,
     pub config: Arc<RwLock<Config>>,
     pub(crate) keyword_docs: Arc<KeywordDocs>,
     /// A Least Recently Used (LRU) cache of [Session]s, each representing a project opened in the user's workspace.
@@ -43,6 +47,8 @@ pub struct ServerState {
     /// evicting the least recently used sessions when the capacity is reached.
     pub sessions: LruSessionCache,
     pub documents: Documents,
+    /// The `Forc.toml` manifests opened in the user's workspace, validated as they are edited.
+    pub manifests: Documents,
     // Compilation thread related fields
     pub(crate) retrigger_compilation: Arc<AtomicBool>,
     pub is_compiling: Arc<AtomicBool>,
@@ -59,10 +65,12 @@ impl Default for ServerState {
         let (cb_tx, cb_rx) = crossbeam_channel::bounded(1);
         let state = ServerState {
             client: None,
+            supports_dynamic_registration: AtomicBool::new(false),
             config: Arc::new(RwLock::new(Config::default())),
             keyword_docs: Arc::new(KeywordDocs::new()),
             sessions: LruSessionCache::new(DEFAULT_SESSION_CACHE_CAPACITY),
             documents: Documents::new(),
+            manifests: Documents::new(),
             retrigger_compilation: Arc::new(AtomicBool::new(false)),
             is_compiling: Arc::new(AtomicBool::new(false)),
             cb_tx,
@@ -105,6 +113,9 @@ pub struct CompilationContext {
     pub optimized_build: bool,
     pub gc_options: GarbageCollectionConfig,
     pub file_versions: BTreeMap<PathBuf, Option<u64>>,
+    /// The sessions of the other members of the workspace that depend on the package being
+    /// compiled. They share their engines with the session being compiled.
+    pub dependent_sessions: Vec<Arc<Session>>,
 }
 
 impl ServerState {
@@ -149,6 +160,19 @@ impl ServerState {
                                     err.to_string()
                                 );
                             }
+                            // The members of the workspace depending on the package refer to the
+                            // declarations of the collected module, so their programs are collected
+                            // as well and compiled again once the engines are swapped.
+                            for member_session in &ctx.dependent_sessions {
+                                if let Err(err) =
+                                    member_session.garbage_collect_program(&mut engines_clone)
+                                {
+                                    tracing::error!(
+                                        "Unable to perform garbage collection: {}",
+                                        err.to_string()
+                                    );
+                                }
+                            }
                         }
 
                         let lsp_mode = Some(LspConfig {
@@ -186,6 +210,13 @@ impl ServerState {
                                                     &mut *session.engines.write(),
                                                     &mut engines_clone,
                                                 );
+                                                if ctx.gc_options.gc_enabled {
+                                                    compile_workspace_members(
+                                                        &session,
+                                                        &ctx.dependent_sessions,
+                                                        experimental,
+                                                    );
+                                                }
                                             }
                                         }
                                         *last_compilation_state.write() =
@@ -282,14 +313,25 @@ impl ServerState {
         self.cb_tx
             .send(TaskMessage::Terminate)
             .expect("failed to send terminate message");
-
-        let _ = self.sessions.iter().map(|item| {
-            let session = item.value();
-            session.shutdown();
-        });
         Ok(())
     }
 
+    /// Registers the capabilities returned by [crate::dynamic_registrations] with the client,
+    /// if it supports registering them dynamically.
+    pub(crate) async fn register_dynamic_capabilities(&self) {
+        if !self.supports_dynamic_registration.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(client) = self.client.as_ref() {
+            if let Err(err) = client
+                .register_capability(crate::dynamic_registrations())
+                .await
+            {
+                tracing::warn!("Failed to register dynamic capabilities: {}", err);
+            }
+        }
+    }
+
     pub(crate) async fn publish_diagnostics(
         &self,
         uri: Url,
@@ -306,6 +348,24 @@ impl ServerState {
         }
     }
 
+    /// Publishes the diagnostics of validating the opened manifest at the given [Url].
+    pub(crate) async fn publish_manifest_diagnostics(&self, uri: &Url) {
+        let (Ok(path), Some(document)) = (uri.to_file_path(), self.manifests.get(uri.path()))
+        else {
+            return;
+        };
+        let Some(manifest_dir) = path.parent() else {
+            return;
+        };
+        let diagnostics = capabilities::manifest::diagnostics(manifest_dir, document.get_text());
+        drop(document);
+        if let Some(client) = self.client.as_ref() {
+            client
+                .publish_diagnostics(uri.clone(), diagnostics, None)
+                .await;
+        }
+    }
+
     fn diagnostics(&self, uri: &Url, session: Arc<Session>) -> Vec<Diagnostic> {
         let mut diagnostics_to_publish = vec![];
         let config = &self.config.read();
@@ -338,14 +398,13 @@ impl ServerState {
     }
 
     /// Constructs and returns a tuple of `(Url, Arc<Session>)` from a given workspace URI.
-    /// The returned URL represents the temp directory workspace.
+    /// The workspace is compiled in place, so the returned URL is the workspace URI itself.
     pub async fn uri_and_session_from_workspace(
         &self,
         workspace_uri: &Url,
     ) -> Result<(Url, Arc<Session>), LanguageServerError> {
         let session = self.url_to_session(workspace_uri).await?;
-        let uri = session.sync.workspace_to_temp_url(workspace_uri)?;
-        Ok((uri, session))
+        Ok((workspace_uri.clone(), session))
     }
 
     async fn url_to_session(&self, uri: &Url) -> Result<Arc<Session>, LanguageServerError> {
@@ -376,14 +435,144 @@ impl ServerState {
             return Ok(session);
         }
 
-        // If no session can be found, then we need to call init and insert a new session into the map
-        let session = Arc::new(Session::new());
+        // If no session can be found, then we need to call init and insert a new session into the map.
+        // Members of the same workspace share their engines, so that declarations of one member
+        // can be resolved from the others.
+        let workspace_manifest = PackageManifestFile::from_dir(&*manifest_dir)
+            .ok()
+            .and_then(|manifest| workspace_manifest(&manifest));
+        // The members of a workspace are compiled together, so they must all fit in the cache.
+        if let Some(workspace_manifest) = &workspace_manifest {
+            self.sessions.reserve(workspace_manifest.members().count());
+        }
+        let workspace_engines = workspace_manifest.as_ref().and_then(|workspace_manifest| {
+            self.sessions
+                .iter()
+                .find(|item| {
+                    item.value().sync.workspace_dir().as_deref() == Some(workspace_manifest.dir())
+                })
+                .map(|item| item.value().engines.clone())
+        });
+        let is_new_workspace = workspace_engines.is_none();
+        let engines = workspace_engines.unwrap_or_default();
+        let session = Arc::new(Session::with_engines(engines.clone()));
         session.init(uri, &self.documents).await?;
         self.sessions
             .insert((*manifest_dir).clone(), session.clone());
 
+        // Load the other members of a newly opened workspace, so that references to the
+        // declarations of the opened member are found across the whole workspace.
+        if let Some(workspace_manifest) = workspace_manifest.filter(|_| is_new_workspace) {
+            let member_manifests = workspace_manifest.member_pkg_manifests().map_err(|_| {
+                DocumentError::MemberManifestsFailed {
+                    dir: workspace_manifest.dir().to_string_lossy().to_string(),
+                }
+            })?;
+            for member_manifest in member_manifests.flatten() {
+                let member_dir = member_manifest.dir().to_path_buf();
+                if member_dir == *manifest_dir || self.sessions.get(&member_dir).is_some() {
+                    continue;
+                }
+                let member_session = Arc::new(Session::with_engines(engines.clone()));
+                let member_uri = get_url_from_path(&member_dir)?;
+                if let Err(err) = member_session.init(&member_uri, &self.documents).await {
+                    tracing::error!("{}", err.to_string());
+                    continue;
+                }
+                self.sessions.insert(member_dir, member_session);
+            }
+        }
+
         Ok(session)
     }
+
+    /// Returns the sessions of the other members of the workspace the given session belongs to.
+    pub fn workspace_sessions(&self, session: &Arc<Session>) -> Vec<Arc<Session>> {
+        self.sessions
+            .iter()
+            .filter(|item| {
+                !Arc::ptr_eq(item.value(), session)
+                    && Arc::ptr_eq(&item.value().engines, &session.engines)
+            })
+            .map(|item| item.value().clone())
+            .collect()
+    }
+
+    /// Returns the sessions of the other members of the workspace that depend on the package of the
+    /// given session, either directly or through other members.
+    pub fn dependent_workspace_sessions(&self, session: &Arc<Session>) -> Vec<Arc<Session>> {
+        let Ok(manifest_dir) = session.sync.manifest_dir() else {
+            return vec![];
+        };
+        let mut members: Vec<_> = self
+            .workspace_sessions(session)
+            .into_iter()
+            .filter_map(|member_session| {
+                let member_dir = member_session.sync.manifest_dir().ok()?;
+                let manifest = PackageManifestFile::from_dir(&member_dir).ok()?;
+                let dep_dirs: Vec<_> = manifest
+                    .deps()
+                    .filter_map(|(dep_name, _)| manifest.dep_path(dep_name))
+                    .collect();
+                Some((member_session, canonical_dir(member_dir), dep_dirs))
+            })
+            .collect();
+        let mut changed_dirs = HashSet::from([canonical_dir(manifest_dir)]);
+        let mut dependents = vec![];
+        while let Some(index) = members.iter().position(|(_, _, dep_dirs)| {
+            dep_dirs
+                .iter()
+                .any(|dep_dir| changed_dirs.contains(dep_dir))
+        }) {
+            let (member_session, member_dir, _) = members.swap_remove(index);
+            changed_dirs.insert(member_dir);
+            dependents.push(member_session);
+        }
+        dependents
+    }
+}
+
+/// Returns the canonical form of the given directory, so that it can be compared with the paths of
+/// dependencies, or the directory itself if it cannot be canonicalized.
+fn canonical_dir(dir: PathBuf) -> PathBuf {
+    dir.canonicalize().unwrap_or(dir)
+}
+
+/// Compiles the members of the workspace depending on the given session again, after the engines
+/// they share were garbage collected, so that their tokens refer to the new declarations.
+fn compile_workspace_members(
+    session: &Arc<Session>,
+    dependent_sessions: &[Arc<Session>],
+    experimental: sway_core::ExperimentalFlags,
+) {
+    for member_session in dependent_sessions {
+        let Some(member_uri) = member_session
+            .sync
+            .entry_path()
+            .and_then(|path| get_url_from_path(&path).ok())
+        else {
+            continue;
+        };
+        let mut engines_clone = session.engines.read().clone();
+        let lsp_mode = Some(LspConfig {
+            optimized_build: false,
+            file_versions: BTreeMap::new(),
+        });
+        match session::parse_project(
+            &member_uri,
+            &engines_clone,
+            None,
+            lsp_mode,
+            member_session.clone(),
+            experimental,
+        ) {
+            Ok(()) => {
+                engines_clone.qe().commit();
+                mem::swap(&mut *session.engines.write(), &mut engines_clone);
+            }
+            Err(err) => tracing::error!("{}", err.to_string()),
+        }
+    }
 }
 
 /// A Least Recently Used (LRU) cache for storing and managing `Session` objects.
@@ -394,7 +583,7 @@ pub struct LruSessionCache {
     /// Keeps track of the order in which sessions were accessed, with most recent at the front.
     usage_order: Arc<Mutex<VecDeque<PathBuf>>>,
     /// The maximum number of sessions that can be stored in the cache.
-    capacity: usize,
+    capacity: AtomicUsize,
 }
 
 impl LruSessionCache {
@@ -403,10 +592,15 @@ impl LruSessionCache {
         LruSessionCache {
             sessions: Arc::new(DashMap::new()),
             usage_order: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
-            capacity,
+            capacity: AtomicUsize::new(capacity),
         }
     }
 
+    /// Grows the capacity of the cache to at least the given number of sessions.
+    pub fn reserve(&self, capacity: usize) {
+        self.capacity.fetch_max(capacity, Ordering::SeqCst);
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = RefMulti<'_, PathBuf, Arc<Session>>> {
         self.sessions.iter()
     }
@@ -414,7 +608,7 @@ impl LruSessionCache {
     /// Retrieves a session from the cache and updates its position to the front of the usage order.
     pub fn get(&self, path: &PathBuf) -> Option<Arc<Session>> {
         if let Some(session) = self.sessions.try_get(path).try_unwrap() {
-            if self.sessions.len() >= self.capacity {
+            if self.sessions.len() >= self.capacity.load(Ordering::SeqCst) {
                 self.move_to_front(path);
             }
             Some(session.clone())
@@ -433,7 +627,7 @@ impl LruSessionCache {
             self.move_to_front(&path);
         } else {
             // New session
-            if self.sessions.len() >= self.capacity {
+            if self.sessions.len() >= self.capacity.load(Ordering::SeqCst) {
                 self.evict_least_used();
             }
             self.sessions.insert(path.clone(), session);
@@ -505,6 +699,22 @@ mod tests {
         assert!(cache.get(&path3).is_some());
     }
 
+    #[test]
+    fn test_lru_session_cache_reserve() {
+        let cache = LruSessionCache::new(2);
+        cache.reserve(3);
+        cache.reserve(1);
+        let paths: Vec<_> = (1..=4)
+            .map(|i| PathBuf::from(format!("/path/{i}")))
+            .collect();
+        for path in &paths {
+            cache.insert(path.clone(), Arc::new(Session::new()));
+        }
+
+        assert!(cache.get(&paths[0]).is_none());
+        assert!(paths[1..].iter().all(|path| cache.get(path).is_some()));
+    }
+
     #[test]
     fn test_lru_session_cache_update_order() {
         let cache = LruSessionCache::new(2);
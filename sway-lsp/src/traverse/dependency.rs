@@ -38,6 +38,14 @@ pub fn collect_parsed_declaration(node: &AstNode, ctx: &ParseContext) {
                 let decl = ctx.engines.pe().get_enum(decl_id);
                 (decl.name.clone(), SymbolKind::Enum)
             }
+            Declaration::AbiDeclaration(decl_id) => {
+                let decl = ctx.engines.pe().get_abi(decl_id);
+                (decl.name.clone(), SymbolKind::Trait)
+            }
+            Declaration::ConfigurableDeclaration(decl_id) => {
+                let decl = ctx.engines.pe().get_configurable(decl_id);
+                (decl.name.clone(), SymbolKind::Const)
+            }
             _ => return,
         };
 
@@ -67,6 +75,12 @@ pub fn collect_typed_declaration(node: &ty::TyAstNode, ctx: &ParseContext) {
             ty::TyDecl::EnumDecl(ty::EnumDecl { decl_id }) => {
                 ctx.engines.de().get_enum(decl_id).name().clone()
             }
+            ty::TyDecl::AbiDecl(ty::AbiDecl { decl_id }) => {
+                ctx.engines.de().get_abi(decl_id).name().clone()
+            }
+            ty::TyDecl::ConfigurableDecl(ty::ConfigurableDecl { decl_id }) => {
+                ctx.engines.de().get_configurable(decl_id).name().clone()
+            }
             ty::TyDecl::VariableDecl(variable) => variable.name.clone(),
             _ => return,
         };
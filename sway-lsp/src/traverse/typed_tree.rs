@@ -259,8 +259,12 @@ impl Parse for ty::TyExpression {
                 }
                 contract_call_params.values().for_each(|exp| exp.parse(ctx));
                 adaptive_iter(arguments, |(ident, exp)| {
+                    // The identifiers of the arguments are the parameters of the function, which
+                    // keep their typed parameter once it is collected.
                     if let Some(mut token) = ctx.tokens.try_get_mut_with_retry(&ctx.ident(ident)) {
-                        token.typed = Some(TypedAstToken::Ident(ident.clone()));
+                        if token.typed.is_none() {
+                            token.typed = Some(TypedAstToken::Ident(ident.clone()));
+                        }
                     }
                     exp.parse(ctx);
                 });
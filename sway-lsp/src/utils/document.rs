@@ -1,5 +1,5 @@
 use crate::error::DirectoryError;
-use lsp_types::Url;
+use lsp_types::{Position, Url};
 use std::path::PathBuf;
 use sway_types::{SourceEngine, Span};
 
@@ -29,3 +29,27 @@ pub fn get_url_from_span(source_engine: &SourceEngine, span: &Span) -> Result<Ur
         })
     }
 }
+
+/// Returns the byte offset of the given [Position] within the text.
+pub(crate) fn offset_of_position(text: &str, position: Position) -> Option<usize> {
+    let line_start = if position.line == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(position.line as usize - 1)?.0 + 1
+    };
+    let line = text[line_start..].split('\n').next()?;
+    let character_offset = line
+        .char_indices()
+        .nth(position.character as usize)
+        .map_or(line.len(), |(i, _)| i);
+    Some(line_start + character_offset)
+}
+
+/// Returns the [Position] of the given byte offset within the text.
+pub(crate) fn position_of_offset(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() as u32;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character = before[line_start..].chars().count() as u32;
+    Position { line, character }
+}
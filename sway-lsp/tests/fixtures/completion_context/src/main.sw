@@ -0,0 +1,24 @@
+contract;
+
+enum Color {
+    Red: (),
+    Blue: u64,
+}
+
+storage {
+    counter: u64 = 0,
+    enabled: bool = false,
+}
+
+abi MyContract {
+    #[storage(read)]
+    fn count() -> u64;
+}
+
+impl MyContract for Contract {
+    #[storage(read)]
+    fn count() -> u64 {
+        let _color = Color::Red;
+        storage.counter.read()
+    }
+}
@@ -0,0 +1,37 @@
+contract;
+
+trait Shape {
+    fn area(self) -> u64;
+}
+
+trait Solid: Shape {
+    fn volume(self) -> u64;
+}
+
+struct Square {
+    side: u64,
+}
+
+impl Shape for Square {
+    fn area(self) -> u64 {
+        square(self.side)
+    }
+}
+
+fn square(n: u64) -> u64 {
+    n * n
+}
+
+fn total_area(a: Square, b: Square) -> u64 {
+    a.area() + b.area() + square(2)
+}
+
+abi Area {
+    fn area_of(side: u64) -> u64;
+}
+
+impl Area for Contract {
+    fn area_of(side: u64) -> u64 {
+        total_area(Square { side }, Square { side: 1 })
+    }
+}
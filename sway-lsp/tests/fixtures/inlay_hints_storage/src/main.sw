@@ -0,0 +1,26 @@
+contract;
+
+storage {
+    counter: u64 = 0,
+}
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment(amount: u64) -> u64;
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment(amount: u64) -> u64 {
+        let value = storage.counter.read() + amount;
+        storage.counter.write(value);
+        value
+    }
+}
+
+fn pick<T, U>(a: T, _b: U) -> T
+where
+    T: Eq,
+{
+    a
+}
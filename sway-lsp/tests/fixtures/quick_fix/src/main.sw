@@ -0,0 +1,16 @@
+script;
+
+enum Color {
+    Red: (),
+    Green: (),
+}
+
+fn unused_fn() {}
+
+fn main() -> u64 {
+    let unused = 1;
+    let color = Color::Red;
+    match color {
+        Color::Red => 0,
+    }
+}
@@ -0,0 +1,26 @@
+script;
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+fn main() -> u64 {
+    let x = 1;
+    let y = add(x, 2);
+    y * 2
+}
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn shorthand() -> Point {
+    let x = 1 + 2;
+    Point { x, y: x }
+}
+
+fn side_effects() -> u64 {
+    let sum = add(1, 2);
+    sum + sum
+}
@@ -0,0 +1,24 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+impl Point {
+    /// Returns the sum of the coordinates, scaled by `factor`.
+    fn scaled_sum(self, factor: u64, offset: u64) -> u64 {
+        (self.x + self.y) * factor + offset
+    }
+}
+
+/// Adds two numbers.
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+fn main() {
+    let p = Point { x: 1, y: 2 };
+    let sum = add(1, 2);
+    let _scaled = p.scaled_sum(sum, 3);
+}
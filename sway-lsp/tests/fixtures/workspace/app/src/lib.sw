@@ -0,0 +1,7 @@
+library;
+
+use utils::answer;
+
+pub fn use_answer() -> u64 {
+    answer()
+}
@@ -0,0 +1,9 @@
+library;
+
+pub fn answer() -> u64 {
+    42
+}
+
+pub fn double_answer() -> u64 {
+    answer()
+}
@@ -0,0 +1,34 @@
+contract;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+impl Point {
+    fn norm(self) -> u64 {
+        self.x + self.y
+    }
+}
+
+const MAX_POINTS: u64 = 10;
+
+configurable {
+    POINT_SCALE: u64 = 2,
+}
+
+storage {
+    points: u64 = 0,
+}
+
+abi PointStore {
+    #[storage(read)]
+    fn point_count() -> u64;
+}
+
+impl PointStore for Contract {
+    #[storage(read)]
+    fn point_count() -> u64 {
+        storage.points.read()
+    }
+}
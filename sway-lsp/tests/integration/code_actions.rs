@@ -507,6 +507,7 @@ pub(crate) async fn code_action_auto_import_struct_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("EvmAddress".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -550,6 +551,7 @@ pub(crate) async fn code_action_auto_import_struct_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepStruct".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -597,6 +599,7 @@ pub(crate) async fn code_action_auto_import_enum_request(server: &ServerState, u
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("AuthError".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -640,6 +643,7 @@ pub(crate) async fn code_action_auto_import_enum_request(server: &ServerState, u
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepEnum".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -688,6 +692,7 @@ pub(crate) async fn code_action_auto_import_function_request(server: &ServerStat
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("deep_fun".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -736,6 +741,7 @@ pub(crate) async fn code_action_auto_import_constant_request(server: &ServerStat
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("TEST_CONST".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -781,6 +787,7 @@ pub(crate) async fn code_action_auto_import_trait_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("TryFrom".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -824,6 +831,7 @@ pub(crate) async fn code_action_auto_import_trait_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepTrait".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -870,6 +878,7 @@ pub(crate) async fn code_action_auto_import_alias_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("A".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -909,3 +918,253 @@ pub(crate) async fn code_action_auto_import_alias_request(server: &ServerState,
     let actual = send_request(server, &params).await;
     assert_eq!(expected, actual);
 }
+
+fn create_text_edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+    TextEdit {
+        range: Range {
+            start: Position::new(start.0, start.1),
+            end: Position::new(end.0, end.1),
+        },
+        new_text: new_text.to_string(),
+    }
+}
+
+pub(crate) async fn code_action_extract_variable_request(server: &ServerState, uri: &Url) {
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(8, 12),
+            end: Position::new(8, 21),
+        },
+        None,
+    );
+    let changes = HashMap::from([(
+        uri.clone(),
+        vec![
+            create_text_edit((8, 4), (8, 4), "let add1 = add(x, 2);\n    "),
+            create_text_edit((8, 12), (8, 21), "add1"),
+        ],
+    )]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Extract into variable".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::REFACTOR_EXTRACT),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_extract_function_request(server: &ServerState, uri: &Url) {
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(7, 4),
+            end: Position::new(8, 22),
+        },
+        None,
+    );
+    let changes = HashMap::from([(
+        uri.clone(),
+        vec![
+            create_text_edit((7, 4), (8, 22), "let y = extracted_function();"),
+            create_text_edit(
+                (10, 1),
+                (10, 1),
+                "\n\nfn extracted_function() -> u64 {\n    let x = 1;\n    let y = add(x, 2);\n    y\n}",
+            ),
+        ],
+    )]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Extract into function".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::REFACTOR_EXTRACT),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_inline_variable_request(server: &ServerState, uri: &Url) {
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(7, 8),
+            end: Position::new(7, 8),
+        },
+        None,
+    );
+    let changes = HashMap::from([(
+        uri.clone(),
+        vec![
+            create_text_edit((7, 0), (8, 0), ""),
+            create_text_edit((8, 16), (8, 17), "1"),
+        ],
+    )]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Inline variable".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::REFACTOR_INLINE),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_inline_variable_shorthand_field_request(
+    server: &ServerState,
+    uri: &Url,
+) {
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(18, 8),
+            end: Position::new(18, 8),
+        },
+        None,
+    );
+    let changes = HashMap::from([(
+        uri.clone(),
+        vec![
+            create_text_edit((18, 0), (19, 0), ""),
+            create_text_edit((19, 12), (19, 13), "x: 1 + 2"),
+            create_text_edit((19, 18), (19, 19), "(1 + 2)"),
+        ],
+    )]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Inline variable".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::REFACTOR_INLINE),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_inline_variable_side_effects_request(
+    server: &ServerState,
+    uri: &Url,
+) {
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(23, 8),
+            end: Position::new(23, 8),
+        },
+        None,
+    );
+    // The call would be made twice once inlined.
+    let actual = send_request(server, &params).await;
+    assert!(actual.is_empty(), "{actual:#?}");
+}
+
+pub(crate) async fn code_action_remove_unused_request(server: &ServerState, uri: &Url) {
+    let range = Range {
+        start: Position::new(7, 3),
+        end: Position::new(7, 12),
+    };
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(7, 0),
+            end: Position::new(7, 0),
+        },
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                dead_code: true,
+                ..Default::default()
+            },
+        ),
+    );
+    let changes = HashMap::from([(uri.clone(), vec![create_text_edit((7, 0), (9, 0), "")])]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Remove unused declaration".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::QUICKFIX),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_unused_variable_request(server: &ServerState, uri: &Url) {
+    let range = Range {
+        start: Position::new(10, 8),
+        end: Position::new(10, 14),
+    };
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(10, 4),
+            end: Position::new(10, 4),
+        },
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                dead_code: true,
+                ..Default::default()
+            },
+        ),
+    );
+    let changes = HashMap::from([(uri.clone(), vec![create_text_edit((10, 8), (10, 8), "_")])]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Rename to `_unused`".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::QUICKFIX),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_missing_match_arms_request(server: &ServerState, uri: &Url) {
+    let range = Range {
+        start: Position::new(12, 4),
+        end: Position::new(14, 5),
+    };
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position::new(12, 4),
+            end: Position::new(12, 4),
+        },
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                missing_patterns: Some("`Color::Green`".to_string()),
+                ..Default::default()
+            },
+        ),
+    );
+    let changes = HashMap::from([(
+        uri.clone(),
+        vec![create_text_edit(
+            (14, 0),
+            (14, 0),
+            "        Color::Green => {},\n",
+        )],
+    )]);
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Add missing match arms".to_string(),
+        changes,
+        None,
+        Some(CodeActionKind::QUICKFIX),
+    )];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
@@ -11,6 +11,7 @@ use sway_lsp::{
     handlers::request,
     lsp_ext::{ShowAstParams, VisualizeParams},
     server_state::ServerState,
+    StaticCapabilities,
 };
 use sway_utils::PerformanceData;
 use tower::{Service, ServiceExt};
@@ -47,6 +48,19 @@ pub(crate) async fn initialize_request(service: &mut LspService<ServerState>) ->
     initialize
 }
 
+/// Sends an `initialize` request with the given client capabilities through the [StaticCapabilities]
+/// service, and returns the capabilities of the server.
+pub(crate) async fn initialize_capabilities_request(
+    service: &mut StaticCapabilities<LspService<ServerState>>,
+    client_capabilities: Option<ClientCapabilities>,
+) -> serde_json::Value {
+    let params = json!({ "capabilities": client_capabilities.unwrap_or_default() });
+    let initialize = build_request_with_id("initialize", params, 1);
+    let response = service.ready().await.unwrap().call(initialize).await;
+    let (_, result) = response.unwrap().unwrap().into_parts();
+    result.unwrap()["capabilities"].clone()
+}
+
 pub(crate) async fn initialized_notification(service: &mut LspService<ServerState>) {
     let initialized = Request::build("initialized").finish();
     let response = call_request(service, initialized).await;
@@ -275,6 +289,56 @@ pub(crate) async fn format_request(server: &ServerState, uri: &Url) {
     assert!(!response.unwrap().is_empty());
 }
 
+pub(crate) async fn range_format_request(server: &ServerState, uri: &Url) {
+    let params = DocumentRangeFormattingParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        range: Range::new(Position::new(49, 8), Position::new(49, 14)),
+        options: FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let response = request::handle_range_formatting(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        response,
+        vec![TextEdit {
+            range: Range::new(Position::new(49, 0), Position::new(49, 18)),
+            new_text: "        return 20;".to_string(),
+        }]
+    );
+}
+
+pub(crate) async fn on_type_format_request(server: &ServerState, uri: &Url) {
+    let params = DocumentOnTypeFormattingParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position::new(49, 18),
+        },
+        ch: ";".to_string(),
+        options: FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        },
+    };
+    let response = request::handle_on_type_formatting(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        response,
+        vec![TextEdit {
+            range: Range::new(Position::new(49, 0), Position::new(49, 18)),
+            new_text: "        return 20;".to_string(),
+        }]
+    );
+}
+
 pub(crate) async fn highlight_request(server: &ServerState, uri: &Url) {
     let params = DocumentHighlightParams {
         text_document_position_params: TextDocumentPositionParams {
@@ -496,14 +560,48 @@ pub(crate) async fn completion_request(server: &ServerState, uri: &Url) {
                         character: 8,
                     },
                 },
-                new_text: "get(foo)".to_string(),
+                new_text: "get(${1:foo})".to_string(),
             })),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
         },
     ]);
     assert_eq!(expected, res.unwrap());
 }
 
+pub(crate) async fn completion_context_request(server: &ServerState, uri: &Url) {
+    let completion_labels = |line, character| {
+        let params = CompletionParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        };
+        async move {
+            match request::handle_completion(server, params).await.unwrap() {
+                Some(CompletionResponse::Array(items)) => {
+                    items.into_iter().map(|item| item.label).collect::<Vec<_>>()
+                }
+                _ => vec![],
+            }
+        }
+    };
+
+    // Arguments of the `storage` attribute.
+    assert_eq!(completion_labels(18, 16).await, vec!["read", "write"]);
+
+    // Enum variants after `Color::`.
+    let labels = completion_labels(20, 31).await;
+    assert!(labels.contains(&"Red".to_string()));
+    assert!(labels.contains(&"Blue".to_string()));
+
+    // Storage fields after `storage.`.
+    assert_eq!(completion_labels(21, 23).await, vec!["counter", "enabled"]);
+}
+
 pub(crate) async fn definition_check<'a>(server: &ServerState, go_to: &'a GotoDefinition<'a>) {
     let params = GotoDefinitionParams {
         text_document_position_params: TextDocumentPositionParams {
@@ -664,6 +762,363 @@ pub fn create_did_change_params(
     }
 }
 
+pub(crate) async fn signature_help_request(server: &ServerState, uri: &Url) {
+    let signature_help = |line, character| {
+        let params = SignatureHelpParams {
+            context: None,
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+        };
+        request::handle_signature_help(server, params)
+    };
+
+    // Function call, cursor on the second argument.
+    let res = signature_help(21, 21).await.unwrap().unwrap();
+    let signature = &res.signatures[0];
+    assert_eq!(signature.label, "fn add(a: u64, b: u64) -> u64");
+    assert_eq!(signature.active_parameter, Some(1));
+    assert_eq!(
+        signature.documentation,
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: "Adds two numbers.".to_string(),
+        }))
+    );
+
+    // Method call, the `self` parameter is skipped when counting arguments.
+    let res = signature_help(22, 36).await.unwrap().unwrap();
+    let signature = &res.signatures[0];
+    assert_eq!(
+        signature.label,
+        "fn scaled_sum(self, factor: u64, offset: u64) -> u64"
+    );
+    assert_eq!(signature.active_parameter, Some(2));
+
+    // Outside of any call.
+    let res = signature_help(20, 8).await.unwrap();
+    assert!(res.is_none());
+}
+
+pub(crate) async fn workspace_symbol_request(server: &ServerState, _uri: &Url) {
+    let workspace_symbol = |query: &str| {
+        let params = WorkspaceSymbolParams {
+            query: query.to_string(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        request::handle_workspace_symbol(server, params)
+    };
+    let container_of = |symbols: &[SymbolInformation], name: &str, kind: SymbolKind| {
+        symbols
+            .iter()
+            .filter(|symbol| symbol.name == name && symbol.kind == kind)
+            .map(|symbol| symbol.container_name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let symbols = workspace_symbol("point").await.unwrap().unwrap();
+    assert_eq!(symbols[0].name, "Point");
+    assert_eq!(symbols[0].kind, SymbolKind::STRUCT);
+    assert_eq!(
+        container_of(&symbols, "points", SymbolKind::FIELD),
+        vec![Some("storage".to_string())]
+    );
+    assert_eq!(
+        container_of(&symbols, "POINT_SCALE", SymbolKind::CONSTANT).len(),
+        1
+    );
+    assert!(container_of(&symbols, "point_count", SymbolKind::FUNCTION)
+        .contains(&Some("PointStore".to_string())));
+    assert_eq!(
+        container_of(&symbols, "PointStore", SymbolKind::INTERFACE).len(),
+        1
+    );
+
+    // Methods are contained by their implementing type.
+    let symbols = workspace_symbol("norm").await.unwrap().unwrap();
+    assert_eq!(
+        container_of(&symbols, "norm", SymbolKind::FUNCTION),
+        vec![Some("Point".to_string())]
+    );
+
+    // Names containing the characters of the query in order also match.
+    let symbols = workspace_symbol("mxpts").await.unwrap().unwrap();
+    assert!(symbols.iter().any(|symbol| symbol.name == "MAX_POINTS"));
+
+    // Declarations from the standard library are included.
+    let symbols = workspace_symbol("Vec").await.unwrap().unwrap();
+    assert_eq!(symbols[0].name, "Vec");
+    assert_eq!(symbols[0].container_name, Some("std::vec".to_string()));
+}
+
+pub(crate) async fn call_hierarchy_request(server: &ServerState, uri: &Url) {
+    let prepare = |line, character| {
+        let params = CallHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+        };
+        request::handle_prepare_call_hierarchy(server, params)
+    };
+
+    let items = prepare(20, 4).await.unwrap().unwrap();
+    assert_eq!(items.len(), 1);
+    let square = items[0].clone();
+    assert_eq!(square.name, "square");
+    assert_eq!(square.kind, SymbolKind::FUNCTION);
+
+    let params = CallHierarchyIncomingCallsParams {
+        item: square,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let incoming = request::handle_incoming_calls(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut callers = incoming
+        .iter()
+        .map(|call| call.from.name.as_str())
+        .collect::<Vec<_>>();
+    callers.sort();
+    assert_eq!(callers, vec!["area", "total_area"]);
+
+    let total_area = prepare(24, 4).await.unwrap().unwrap()[0].clone();
+    let params = CallHierarchyOutgoingCallsParams {
+        item: total_area,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let outgoing = request::handle_outgoing_calls(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut callees = outgoing
+        .iter()
+        .map(|call| (call.to.name.as_str(), call.from_ranges.len()))
+        .collect::<Vec<_>>();
+    callees.sort();
+    assert_eq!(callees, vec![("area", 2), ("square", 1)]);
+}
+
+pub(crate) async fn type_hierarchy_request(server: &ServerState, uri: &Url) {
+    let prepare = |line, character| {
+        let params = TypeHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+        };
+        request::handle_prepare_type_hierarchy(server, params)
+    };
+
+    let square = prepare(10, 8).await.unwrap().unwrap()[0].clone();
+    assert_eq!(square.name, "Square");
+    assert_eq!(square.kind, SymbolKind::STRUCT);
+    let params = TypeHierarchySupertypesParams {
+        item: square,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let supertypes = request::handle_supertypes(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let supertypes = supertypes
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(supertypes, vec!["Shape"]);
+
+    let shape = prepare(2, 7).await.unwrap().unwrap()[0].clone();
+    assert_eq!(shape.name, "Shape");
+    assert_eq!(shape.kind, SymbolKind::INTERFACE);
+    let params = TypeHierarchySubtypesParams {
+        item: shape,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let subtypes = request::handle_subtypes(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut subtypes = subtypes
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect::<Vec<_>>();
+    subtypes.sort();
+    assert_eq!(subtypes, vec!["Solid", "Square"]);
+}
+
+pub(crate) async fn implementation_request(server: &ServerState, uri: &Url) {
+    let implementations = |line, character| {
+        let params = tower_lsp::lsp_types::request::GotoImplementationParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        request::handle_goto_implementation(server, params)
+    };
+    let ranges = |response: Option<GotoDefinitionResponse>| match response {
+        Some(GotoDefinitionResponse::Array(locations)) => locations
+            .into_iter()
+            .map(|location| {
+                assert_eq!(&location.uri, uri);
+                location.range
+            })
+            .collect::<Vec<_>>(),
+        response => panic!("unexpected implementation response: {response:?}"),
+    };
+
+    // From the method declared in the trait to the method implemented for `Square`.
+    let response = implementations(3, 7).await.unwrap();
+    assert_eq!(
+        ranges(response),
+        vec![Range::new(Position::new(15, 7), Position::new(15, 11))]
+    );
+
+    // From a call of the method to its implementations.
+    let response = implementations(25, 6).await.unwrap();
+    assert_eq!(
+        ranges(response),
+        vec![Range::new(Position::new(15, 7), Position::new(15, 11))]
+    );
+
+    // From the method declared in the ABI to the method implemented for the contract.
+    let response = implementations(29, 7).await.unwrap();
+    assert_eq!(
+        ranges(response),
+        vec![Range::new(Position::new(33, 7), Position::new(33, 14))]
+    );
+
+    // From the trait to the impl block.
+    let response = implementations(2, 7).await.unwrap();
+    let ranges = ranges(response);
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].start, Position::new(14, 0));
+
+    // Free functions have no implementations.
+    let response = implementations(20, 4).await.unwrap();
+    assert!(response.is_none());
+}
+
+pub(crate) async fn type_definition_request(server: &ServerState, uri: &Url) {
+    let type_definition = |line, character| {
+        let params = tower_lsp::lsp_types::request::GotoTypeDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        request::handle_goto_type_definition(server, params)
+    };
+
+    // From a variable to the declaration of its type.
+    let response = type_definition(25, 4).await.unwrap();
+    assert_eq!(
+        response,
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            uri.clone(),
+            Range::new(Position::new(10, 7), Position::new(10, 13)),
+        )))
+    );
+
+    // From a parameter to the declaration of its type.
+    let response = type_definition(24, 14).await.unwrap();
+    assert_eq!(
+        response,
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            uri.clone(),
+            Range::new(Position::new(10, 7), Position::new(10, 13)),
+        )))
+    );
+
+    // Builtin types have no declaration.
+    let response = type_definition(11, 4).await.unwrap();
+    assert!(response.is_none());
+}
+
+pub(crate) async fn folding_range_request(server: &ServerState, uri: &Url) {
+    let params = FoldingRangeParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let res = request::handle_folding_range(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let folds = res
+        .iter()
+        .map(|folding_range| (folding_range.start_line, folding_range.end_line))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        folds,
+        vec![
+            (2, 3),
+            (6, 7),
+            (10, 11),
+            (14, 17),
+            (15, 16),
+            (20, 21),
+            (24, 25),
+            (28, 29),
+            (32, 35),
+            (33, 34),
+        ]
+    );
+}
+
+pub(crate) async fn selection_range_request(server: &ServerState, uri: &Url) {
+    let params = SelectionRangeParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        positions: vec![Position::new(34, 29)],
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let res = request::handle_selection_range(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut ranges = vec![];
+    let mut selection_range = res.first();
+    while let Some(range) = selection_range {
+        ranges.push(range.range);
+        selection_range = range.parent.as_deref();
+    }
+    let range = |start_line, start_character, end_line, end_character| {
+        Range::new(
+            Position::new(start_line, start_character),
+            Position::new(end_line, end_character),
+        )
+    };
+    assert_eq!(
+        ranges,
+        vec![
+            range(34, 28, 34, 32),
+            range(34, 26, 34, 34),
+            range(34, 19, 34, 34),
+            range(34, 18, 34, 55),
+            range(34, 8, 34, 55),
+            range(33, 33, 35, 5),
+            range(33, 4, 35, 5),
+            range(32, 23, 36, 1),
+            range(32, 0, 36, 1),
+        ]
+    );
+}
+
 pub(crate) async fn inlay_hints_request<'a>(
     server: &ServerState,
     uri: &Url,
@@ -690,10 +1145,10 @@ pub(crate) async fn inlay_hints_request<'a>(
         InlayHint {
             position: Position {
                 line: 25,
-                character: 25,
+                character: 10,
             },
-            label: InlayHintLabel::String("foo: ".to_string()),
-            kind: Some(InlayHintKind::PARAMETER),
+            label: InlayHintLabel::String(": u64".to_string()),
+            kind: Some(InlayHintKind::TYPE),
             text_edits: None,
             tooltip: None,
             padding_left: Some(false),
@@ -703,9 +1158,9 @@ pub(crate) async fn inlay_hints_request<'a>(
         InlayHint {
             position: Position {
                 line: 25,
-                character: 28,
+                character: 25,
             },
-            label: InlayHintLabel::String("bar: ".to_string()),
+            label: InlayHintLabel::String("foo: ".to_string()),
             kind: Some(InlayHintKind::PARAMETER),
             text_edits: None,
             tooltip: None,
@@ -716,9 +1171,9 @@ pub(crate) async fn inlay_hints_request<'a>(
         InlayHint {
             position: Position {
                 line: 25,
-                character: 31,
+                character: 28,
             },
-            label: InlayHintLabel::String("long_argument_name: ".to_string()),
+            label: InlayHintLabel::String("bar: ".to_string()),
             kind: Some(InlayHintKind::PARAMETER),
             text_edits: None,
             tooltip: None,
@@ -729,10 +1184,10 @@ pub(crate) async fn inlay_hints_request<'a>(
         InlayHint {
             position: Position {
                 line: 25,
-                character: 10,
+                character: 31,
             },
-            label: InlayHintLabel::String(": u64".to_string()),
-            kind: Some(InlayHintKind::TYPE),
+            label: InlayHintLabel::String("long_argument_name: ".to_string()),
+            kind: Some(InlayHintKind::PARAMETER),
             text_edits: None,
             tooltip: None,
             padding_left: Some(false),
@@ -750,6 +1205,131 @@ pub(crate) async fn inlay_hints_request<'a>(
     Some(res)
 }
 
+pub(crate) async fn inlay_hints_skipped_params_and_closing_brace_request(
+    server: &ServerState,
+    uri: &Url,
+) -> Option<Vec<InlayHint>> {
+    let params = InlayHintParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        range: Range {
+            start: Position {
+                line: 24,
+                character: 0,
+            },
+            end: Position {
+                line: 50,
+                character: 0,
+            },
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let res = request::handle_inlay_hints(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+    let parameter_labels_on_line = |line: u32| {
+        res.iter()
+            .filter(|hint| {
+                hint.position.line == line && hint.kind == Some(InlayHintKind::PARAMETER)
+            })
+            .map(|hint| match &hint.label {
+                InlayHintLabel::String(label) => label.clone(),
+                InlayHintLabel::LabelParts(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Arguments named after their parameter don't get a hint.
+    assert_eq!(
+        parameter_labels_on_line(30),
+        vec!["long_argument_name: ".to_string()]
+    );
+    assert!(parameter_labels_on_line(32).is_empty());
+
+    // `main` spans more lines than the default minimum, so its closing brace gets a hint.
+    let expected = InlayHint {
+        position: Position {
+            line: 49,
+            character: 1,
+        },
+        label: InlayHintLabel::String("fn main".to_string()),
+        kind: None,
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(true),
+        padding_right: Some(false),
+        data: None,
+    };
+    assert!(
+        res.iter().any(|hint| compare_inlay_hints(&expected, hint)),
+        "Missing closing brace hint.\nActual:\n{:#?}",
+        res
+    );
+    Some(res)
+}
+
+pub(crate) async fn inlay_hints_storage_keys_and_generic_closing_brace_request(
+    server: &ServerState,
+    uri: &Url,
+) -> Option<Vec<InlayHint>> {
+    let params = InlayHintParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        range: Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: 27,
+                character: 0,
+            },
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let res = request::handle_inlay_hints(server, params)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // sha256("storage.counter")
+    let key = "0x6e3c7b4f69bbff7132c3c3a62883a6868f47b0bc2a7f21605f29038cd9a5e05f";
+    for (line, character) in [(14, 35), (15, 23)] {
+        let expected = InlayHint {
+            position: Position { line, character },
+            label: InlayHintLabel::String(key.to_string()),
+            kind: None,
+            text_edits: None,
+            tooltip: None,
+            padding_left: Some(true),
+            padding_right: Some(false),
+            data: None,
+        };
+        assert!(
+            res.iter().any(|hint| compare_inlay_hints(&expected, hint)),
+            "Missing storage key hint.\nActual:\n{:#?}",
+            res
+        );
+    }
+
+    // The type parameters and where clause of `pick` don't get closing brace hints of their own.
+    let closing_brace_hints = res
+        .iter()
+        .filter(|hint| {
+            hint.position
+                == Position {
+                    line: 25,
+                    character: 1,
+                }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(closing_brace_hints.len(), 1, "Actual:\n{:#?}", res);
+    assert!(matches!(
+        &closing_brace_hints[0].label,
+        InlayHintLabel::String(label) if label == "fn pick -> T"
+    ));
+    Some(res)
+}
+
 // This is a helper function to compare two inlay hints. because PartialEq is not implemented for InlayHint
 fn compare_inlay_hints(a: &InlayHint, b: &InlayHint) -> bool {
     a.position == b.position
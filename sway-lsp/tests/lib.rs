@@ -4,11 +4,12 @@ pub mod integration;
 
 use crate::integration::{code_actions, lsp};
 use lsp_types::*;
-use std::{fs, path::PathBuf};
+use std::{fs, path::PathBuf, sync::Arc};
 use sway_lsp::{
     config::LspClient,
     handlers::{notification, request},
     server_state::ServerState,
+    StaticCapabilities,
 };
 use sway_lsp_test_utils::{
     assert_server_requests, dir_contains_forc_manifest, doc_comments_dir, e2e_language_dir,
@@ -144,6 +145,33 @@ fn initialize() {
     });
 }
 
+#[test]
+fn initialize_static_and_dynamic_capabilities() {
+    run_async!({
+        // Clients not supporting the dynamic registration of the type hierarchy are given it statically.
+        let (service, _) = LspService::new(ServerState::new);
+        let mut service = StaticCapabilities(service);
+        let capabilities = lsp::initialize_capabilities_request(&mut service, None).await;
+        assert_eq!(capabilities["typeHierarchyProvider"], true);
+        assert_eq!(capabilities["hoverProvider"], true);
+
+        let (service, _) = LspService::new(ServerState::new);
+        let mut service = StaticCapabilities(service);
+        let client_capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                    dynamic_registration: Some(true),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let capabilities =
+            lsp::initialize_capabilities_request(&mut service, Some(client_capabilities)).await;
+        assert!(capabilities.get("typeHierarchyProvider").is_none());
+    });
+}
+
 #[test]
 fn did_open() {
     run_async!({
@@ -164,6 +192,53 @@ fn did_change() {
     });
 }
 
+#[test]
+fn did_change_compiles_unsaved_buffer() {
+    run_async!({
+        let server = ServerState::default();
+        let path = test_fixtures_dir().join("unsaved_buffer/src/main.sw");
+        let uri = open(&server, path.clone()).await;
+        let (_, session) = server.uri_and_session_from_workspace(&uri).await.unwrap();
+        let name = "unsaved".to_string();
+        assert_eq!(session.token_map().tokens_for_name(&name).count(), 0);
+
+        // The edit is compiled through the overlay, without being written to disk.
+        let mut params = lsp::create_did_change_params(
+            &uri,
+            2,
+            Position {
+                line: 1,
+                character: 0,
+            },
+            Position {
+                line: 1,
+                character: 0,
+            },
+            0,
+        );
+        params.content_changes[0].text = "\nfn unsaved() {}\n".into();
+        notification::handle_did_change_text_document(&server, params)
+            .await
+            .unwrap();
+        server
+            .is_compiling
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        server.wait_for_parsing().await;
+        assert!(session.token_map().tokens_for_name(&name).count() > 0);
+        assert!(!fs::read_to_string(&path).unwrap().contains("unsaved"));
+
+        // Closing the document discards the edit and compiles the file on disk again.
+        let params = DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+        };
+        notification::handle_did_close_text_document(&server, params)
+            .await
+            .unwrap();
+        assert_eq!(session.token_map().tokens_for_name(&name).count(), 0);
+        let _ = server.shutdown_server();
+    });
+}
+
 #[test]
 fn did_cache_test() {
     run_async!({
@@ -1952,6 +2027,72 @@ fn rename() {
     });
 }
 
+#[test]
+fn references_and_rename_across_workspace_members() {
+    run_async!({
+        let server = ServerState::default();
+        let workspace_dir = test_fixtures_dir().join("workspace");
+        let uri = open(&server, workspace_dir.join("app/src/lib.sw")).await;
+        let utils_uri = Url::from_file_path(workspace_dir.join("utils/src/lib.sw")).unwrap();
+
+        // References to `answer` include the call made from the `utils` member.
+        let params = ReferenceParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position::new(5, 6),
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: ReferenceContext {
+                include_declaration: true,
+            },
+        };
+        let references = request::handle_references(&server, params)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(references.iter().any(|location| location.uri == utils_uri
+            && location.range == Range::new(Position::new(7, 4), Position::new(7, 10))));
+
+        // Renaming `answer` edits the declaration and the call in the `utils` member.
+        let rename = Rename {
+            req_uri: &uri,
+            req_line: 5,
+            req_char: 6,
+            new_name: "the_answer", // from answer
+        };
+        assert!(lsp::prepare_rename_request(&server, &rename)
+            .await
+            .is_some());
+        let changes = lsp::rename_request(&server, &rename).await.changes.unwrap();
+        assert_eq!(changes.get(&uri).unwrap().len(), 2);
+        assert_eq!(changes.get(&utils_uri).unwrap().len(), 2);
+        let _ = server.shutdown_server();
+    });
+}
+
+#[test]
+fn dependent_workspace_sessions() {
+    run_async!({
+        let server = ServerState::default();
+        let workspace_dir = test_fixtures_dir().join("workspace");
+        let uri = open(&server, workspace_dir.join("app/src/lib.sw")).await;
+        let utils_uri = Url::from_file_path(workspace_dir.join("utils/src/lib.sw")).unwrap();
+        let (_, app_session) = server.uri_and_session_from_workspace(&uri).await.unwrap();
+        let (_, utils_session) = server
+            .uri_and_session_from_workspace(&utils_uri)
+            .await
+            .unwrap();
+
+        // `app` depends on `utils`, so only changes to `utils` affect the other member.
+        let dependents = server.dependent_workspace_sessions(&utils_session);
+        assert_eq!(dependents.len(), 1);
+        assert!(Arc::ptr_eq(&dependents[0], &app_session));
+        assert!(server.dependent_workspace_sessions(&app_session).is_empty());
+        let _ = server.shutdown_server();
+    });
+}
+
 #[test]
 fn publish_diagnostics_dead_code_warning() {
     run_async!({
@@ -2005,6 +2146,16 @@ lsp_capability_test!(
     lsp::format_request,
     doc_comments_dir().join("src/main.sw")
 );
+lsp_capability_test!(
+    range_format,
+    lsp::range_format_request,
+    doc_comments_dir().join("src/main.sw")
+);
+lsp_capability_test!(
+    on_type_format,
+    lsp::on_type_format_request,
+    doc_comments_dir().join("src/main.sw")
+);
 lsp_capability_test!(
     highlight,
     lsp::highlight_request,
@@ -2075,6 +2226,46 @@ lsp_capability_test!(
     code_actions::code_action_auto_import_alias_request,
     test_fixtures_dir().join("auto_import/src/main.sw")
 );
+lsp_capability_test!(
+    code_action_extract_variable,
+    code_actions::code_action_extract_variable_request,
+    test_fixtures_dir().join("refactor/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_extract_function,
+    code_actions::code_action_extract_function_request,
+    test_fixtures_dir().join("refactor/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_inline_variable,
+    code_actions::code_action_inline_variable_request,
+    test_fixtures_dir().join("refactor/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_inline_variable_shorthand_field,
+    code_actions::code_action_inline_variable_shorthand_field_request,
+    test_fixtures_dir().join("refactor/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_inline_variable_side_effects,
+    code_actions::code_action_inline_variable_side_effects_request,
+    test_fixtures_dir().join("refactor/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_remove_unused,
+    code_actions::code_action_remove_unused_request,
+    test_fixtures_dir().join("quick_fix/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_unused_variable,
+    code_actions::code_action_unused_variable_request,
+    test_fixtures_dir().join("quick_fix/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_missing_match_arms,
+    code_actions::code_action_missing_match_arms_request,
+    test_fixtures_dir().join("quick_fix/src/main.sw")
+);
 lsp_capability_test!(
     code_lens,
     lsp::code_lens_request,
@@ -2090,11 +2281,81 @@ lsp_capability_test!(
     lsp::completion_request,
     test_fixtures_dir().join("completion/src/main.sw")
 );
+lsp_capability_test!(
+    completion_context,
+    lsp::completion_context_request,
+    test_fixtures_dir().join("completion_context/src/main.sw")
+);
+lsp_capability_test!(
+    signature_help,
+    lsp::signature_help_request,
+    test_fixtures_dir().join("signature_help/src/main.sw")
+);
+lsp_capability_test!(
+    workspace_symbol,
+    lsp::workspace_symbol_request,
+    test_fixtures_dir().join("workspace_symbol/src/main.sw")
+);
+lsp_capability_test!(
+    call_hierarchy,
+    lsp::call_hierarchy_request,
+    test_fixtures_dir().join("hierarchy/src/main.sw")
+);
+lsp_capability_test!(
+    type_hierarchy,
+    lsp::type_hierarchy_request,
+    test_fixtures_dir().join("hierarchy/src/main.sw")
+);
+lsp_capability_test!(
+    implementation,
+    lsp::implementation_request,
+    test_fixtures_dir().join("hierarchy/src/main.sw")
+);
+lsp_capability_test!(
+    type_definition,
+    lsp::type_definition_request,
+    test_fixtures_dir().join("hierarchy/src/main.sw")
+);
+lsp_capability_test!(
+    folding_range,
+    lsp::folding_range_request,
+    test_fixtures_dir().join("hierarchy/src/main.sw")
+);
+lsp_capability_test!(
+    selection_range,
+    lsp::selection_range_request,
+    test_fixtures_dir().join("hierarchy/src/main.sw")
+);
 lsp_capability_test!(
     inlay_hints_function_params,
     lsp::inlay_hints_request,
     test_fixtures_dir().join("inlay_hints/src/main.sw")
 );
+lsp_capability_test!(
+    inlay_hints_skipped_params_and_closing_brace,
+    lsp::inlay_hints_skipped_params_and_closing_brace_request,
+    test_fixtures_dir().join("inlay_hints/src/main.sw")
+);
+
+#[test]
+fn inlay_hints_storage_keys_and_generic_closing_brace() {
+    run_async!({
+        let server = ServerState::default();
+        {
+            let mut config = server.config.write();
+            config.inlay_hints.storage_key_hints = true;
+            config.inlay_hints.closing_brace_hints_min_lines = 1;
+            config.inlay_hints.max_length = None;
+        }
+        let uri = open(
+            &server,
+            test_fixtures_dir().join("inlay_hints_storage/src/main.sw"),
+        )
+        .await;
+        lsp::inlay_hints_storage_keys_and_generic_closing_brace_request(&server, &uri).await;
+        let _ = server.shutdown_server();
+    });
+}
 
 // This method iterates over all of the examples in the e2e language should_pass dir
 // and saves the lexed, parsed, and typed ASTs to the users home directory.
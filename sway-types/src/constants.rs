@@ -37,6 +37,9 @@ pub const DOC_COMMENT_ATTRIBUTE_NAME: &str = "doc-comment";
 /// The attribute used for Sway in-language unit tests.
 pub const TEST_ATTRIBUTE_NAME: &str = "test";
 
+/// The attribute used for Sway in-language benchmarks.
+pub const BENCH_ATTRIBUTE_NAME: &str = "bench";
+
 /// The valid attribute string used for payable functions.
 pub const PAYABLE_ATTRIBUTE_NAME: &str = "payable";
 
@@ -55,18 +58,24 @@ pub const DEPRECATED_ATTRIBUTE_NAME: &str = "deprecated";
 
 pub const FALLBACK_ATTRIBUTE_NAME: &str = "fallback";
 
+/// The valid attribute strings related to the formatter.
+pub const FMT_ATTRIBUTE_NAME: &str = "fmt";
+pub const FMT_SKIP_ARG_NAME: &str = "skip";
+
 /// The list of valid attributes.
 pub const VALID_ATTRIBUTE_NAMES: &[&str] = &[
     STORAGE_PURITY_ATTRIBUTE_NAME,
     DOC_ATTRIBUTE_NAME,
     DOC_COMMENT_ATTRIBUTE_NAME,
     TEST_ATTRIBUTE_NAME,
+    BENCH_ATTRIBUTE_NAME,
     INLINE_ATTRIBUTE_NAME,
     PAYABLE_ATTRIBUTE_NAME,
     ALLOW_ATTRIBUTE_NAME,
     CFG_ATTRIBUTE_NAME,
     DEPRECATED_ATTRIBUTE_NAME,
     FALLBACK_ATTRIBUTE_NAME,
+    FMT_ATTRIBUTE_NAME,
 ];
 
 pub const CORE: &str = "core";
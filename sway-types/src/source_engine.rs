@@ -2,7 +2,9 @@ use crate::{ProgramId, SourceId};
 use parking_lot::RwLock;
 use std::{
     collections::{BTreeSet, HashMap},
-    path::PathBuf,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
 /// The Source Engine manages a relationship between file paths and their corresponding
@@ -14,6 +16,11 @@ use std::{
 /// The Source Engine is designed to be thread-safe. Its internal structures are
 /// secured by the RwLock mechanism. This allows its functions to be invoked using
 /// a straightforward non-mutable reference, ensuring safe concurrent access.
+///
+/// The Source Engine also holds an overlay of in-memory file contents, such as the unsaved
+/// buffers of an editor, that shadow the contents of the files on disk when the compiler reads
+/// source code through [SourceEngine::read_source]. The overlay is shared between clones of the
+/// engine, as it reflects the state of the files rather than of a compilation.
 #[derive(Debug, Default)]
 pub struct SourceEngine {
     next_source_id: RwLock<u32>,
@@ -22,6 +29,7 @@ pub struct SourceEngine {
     next_program_id: RwLock<u16>,
     manifest_path_to_program_map: RwLock<HashMap<PathBuf, ProgramId>>,
     module_to_sources_map: RwLock<HashMap<ProgramId, BTreeSet<SourceId>>>,
+    overlay: Arc<RwLock<HashMap<PathBuf, Arc<str>>>>,
 }
 
 impl Clone for SourceEngine {
@@ -35,6 +43,7 @@ impl Clone for SourceEngine {
                 self.manifest_path_to_program_map.read().clone(),
             ),
             module_to_sources_map: RwLock::new(self.module_to_sources_map.read().clone()),
+            overlay: self.overlay.clone(),
         }
     }
 }
@@ -149,4 +158,53 @@ impl SourceEngine {
         let s = self.module_to_sources_map.read();
         s.get(&program_id).cloned()
     }
+
+    /// Sets the in-memory contents of the file at the given path. Until the overlay is removed,
+    /// [SourceEngine::read_source] returns these contents instead of the ones on disk.
+    pub fn set_overlay(&self, path: PathBuf, src: Arc<str>) {
+        self.overlay.write().insert(path, src);
+    }
+
+    /// Removes the in-memory contents of the file at the given path, if any, so that the file
+    /// is read from disk again.
+    pub fn remove_overlay(&self, path: &Path) -> Option<Arc<str>> {
+        self.overlay.write().remove(path)
+    }
+
+    /// Returns true if the file at the given path has in-memory contents.
+    pub fn has_overlay(&self, path: &Path) -> bool {
+        self.overlay.read().contains_key(path)
+    }
+
+    /// Reads the source code of the file at the given path, preferring its in-memory contents
+    /// over the ones on disk.
+    pub fn read_source(&self, path: &Path) -> io::Result<Arc<str>> {
+        if let Some(src) = self.overlay.read().get(path) {
+            return Ok(src.clone());
+        }
+        std::fs::read_to_string(path).map(Arc::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_shadows_file_on_disk() {
+        let source_engine = SourceEngine::default();
+        let path = PathBuf::from("/path/that/does/not/exist/main.sw");
+        assert!(source_engine.read_source(&path).is_err());
+
+        source_engine.set_overlay(path.clone(), Arc::from("library;"));
+        assert_eq!(&*source_engine.read_source(&path).unwrap(), "library;");
+        // Clones share the overlay.
+        let clone = source_engine.clone();
+        clone.set_overlay(path.clone(), Arc::from("script;"));
+        assert_eq!(&*source_engine.read_source(&path).unwrap(), "script;");
+
+        source_engine.remove_overlay(&path);
+        assert!(!clone.has_overlay(&path));
+        assert!(source_engine.read_source(&path).is_err());
+    }
 }
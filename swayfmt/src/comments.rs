@@ -1,6 +1,9 @@
 use crate::{
+    config::{comments::Comments, manifest::Config},
+    constants::{MIN_WRAPPED_COMMENT_WIDTH, SKIP_COMMENT},
+    error::FormatterWarning,
     formatter::FormattedCode,
-    parse::parse_snippet,
+    parse::{lex, parse_snippet},
     utils::map::{
         byte_span::{ByteSpan, LeafSpans},
         comments::CommentMap,
@@ -8,9 +11,15 @@ use crate::{
     Format, Formatter, FormatterError,
 };
 use ropey::Rope;
-use std::{fmt::Write, ops::Range};
-use sway_ast::token::{Comment, CommentKind};
-use sway_types::{Span, Spanned};
+use std::{fmt::Write, ops::Range, sync::Arc};
+use sway_ast::{
+    attribute::{AttributeDecl, AttributeHashKind},
+    token::{Comment, CommentKind, CommentedTokenTree, CommentedTree},
+};
+use sway_types::{
+    constants::{FMT_ATTRIBUTE_NAME, FMT_SKIP_ARG_NAME},
+    Span, Spanned,
+};
 
 pub type UnformattedCode = String;
 
@@ -21,6 +30,8 @@ pub struct CommentsContext {
     /// Original unformatted code that the formatter tries to format.
     /// The Formatter requires this to preserve newlines between comments.
     unformatted_code: UnformattedCode,
+    /// Spans of the unformatted code that were copied verbatim, see [write_skipped].
+    skipped_spans: Vec<ByteSpan>,
 }
 
 impl CommentsContext {
@@ -28,11 +39,102 @@ impl CommentsContext {
         Self {
             map,
             unformatted_code,
+            skipped_spans: vec![],
         }
     }
     pub fn unformatted_code(&self) -> &str {
         &self.unformatted_code
     }
+    /// Checks if the given range of the unformatted code lies within code that was copied verbatim.
+    pub fn is_skipped(&self, range: &Range<usize>) -> bool {
+        self.skipped_spans
+            .iter()
+            .any(|span| span.start <= range.start && range.end < span.end)
+    }
+    /// Returns the ranges of the formatted code that were copied verbatim, see [write_skipped].
+    ///
+    /// The skipped code is found in the formatted code in the order it was written.
+    pub fn skipped_ranges(&self, formatted_code: &str) -> Vec<Range<usize>> {
+        let mut ranges = vec![];
+        let mut cursor = 0;
+        for span in &self.skipped_spans {
+            let skipped = &self.unformatted_code[span.start..span.end];
+            if let Some(offset) = formatted_code[cursor..].find(skipped) {
+                let start = cursor + offset;
+                cursor = start + skipped.len();
+                ranges.push(start..cursor);
+            }
+        }
+        ranges
+    }
+}
+
+/// Checks if the given range of the formatted code lies within one of the `skipped` ranges.
+fn is_within(skipped: &[Range<usize>], range: &Range<usize>) -> bool {
+    skipped
+        .iter()
+        .any(|skipped| skipped.start <= range.start && range.end <= skipped.end)
+}
+
+/// Checks if the item or statement with the given span and attributes must be left unformatted,
+/// either because it is annotated with `#[fmt(skip)]`, or because it is preceded by a
+/// `// swayfmt-skip` comment on the line before it.
+pub(crate) fn is_fmt_skipped(
+    formatter: &Formatter,
+    span: &Span,
+    attribute_list: &[AttributeDecl],
+) -> bool {
+    let has_skip_attribute = attribute_list.iter().any(|attr_decl| {
+        matches!(attr_decl.hash_kind, AttributeHashKind::Outer(_))
+            && attr_decl.attribute.get().into_iter().any(|attr| {
+                attr.name.as_str() == FMT_ATTRIBUTE_NAME
+                    && attr.args.as_ref().is_some_and(|args| {
+                        args.get()
+                            .into_iter()
+                            .any(|arg| arg.name.as_str() == FMT_SKIP_ARG_NAME)
+                    })
+            })
+    });
+    has_skip_attribute
+        || (is_in_unformatted_code(formatter, span)
+            && formatter.comments_context.unformatted_code()[..span.start()]
+                .trim_end()
+                .lines()
+                .last()
+                .is_some_and(|line| line.trim() == SKIP_COMMENT))
+}
+
+/// Writes the original source of an item or statement that must be left unformatted, and
+/// removes the comments within it from the [CommentMap], since they are copied along with it.
+pub(crate) fn write_skipped(
+    formatted_code: &mut FormattedCode,
+    span: &Span,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    write!(formatted_code, "{}", span.as_str())?;
+    if is_in_unformatted_code(formatter, span) {
+        let range = span.start()..span.end();
+        formatter
+            .comments_context
+            .map
+            .retain(|bs, _| !bs.contained_within(&range));
+        formatter
+            .comments_context
+            .skipped_spans
+            .push(ByteSpan::from(span.clone()));
+    }
+
+    Ok(())
+}
+
+/// Checks that the span points into the unformatted code of the [CommentsContext], rather than
+/// into a snippet parsed separately.
+fn is_in_unformatted_code(formatter: &Formatter, span: &Span) -> bool {
+    formatter
+        .comments_context
+        .unformatted_code()
+        .get(span.start()..span.end())
+        == Some(span.as_str())
 }
 
 #[inline]
@@ -109,6 +211,11 @@ pub fn write_comments(
 
         for comment in comments_iter {
             let newlines = collect_newlines_after_comment(&formatter.comments_context, comment);
+            // Block comments written on lines of their own are kept on lines of their own.
+            let is_own_line_block_comment = !newlines.is_empty()
+                && formatter.comments_context.unformatted_code()[..comment.span().start()]
+                    .trim_end_matches([' ', '\t'])
+                    .ends_with('\n');
 
             match comment.comment_kind {
                 CommentKind::Newlined => {
@@ -120,6 +227,15 @@ pub fn write_comments(
                         newlines
                     )?;
                 }
+                CommentKind::Inlined | CommentKind::Multilined if is_own_line_block_comment => {
+                    write!(
+                        formatted_code,
+                        "{}{}{}",
+                        formatter.indent_to_str()?,
+                        comment.span().as_str(),
+                        newlines
+                    )?;
+                }
                 CommentKind::Trailing => {
                     write_trailing_comment(formatted_code, comment)?;
                 }
@@ -381,6 +497,479 @@ fn insert_after_span(
     Ok(comment_str.chars().count())
 }
 
+/// The kind of a comment that can be rewritten by [rewrite_comments].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RewritableComment {
+    /// A `//` comment.
+    Line,
+    /// A `/* */` comment.
+    Block,
+    /// A `///` or `//!` doc comment.
+    Doc,
+}
+
+/// Collects the spans of the line, block and doc comments of the token stream.
+fn collect_rewritable_comments(
+    commented_token_tree: &CommentedTokenTree,
+    comments: &mut Vec<(Range<usize>, RewritableComment)>,
+) {
+    match commented_token_tree {
+        CommentedTokenTree::Comment(comment) => {
+            let kind = if comment.span().as_str().starts_with("/*") {
+                RewritableComment::Block
+            } else {
+                RewritableComment::Line
+            };
+            comments.push((comment.span().start()..comment.span().end(), kind));
+        }
+        CommentedTokenTree::Tree(CommentedTree::DocComment(doc_comment)) => comments.push((
+            doc_comment.span().start()..doc_comment.span().end(),
+            RewritableComment::Doc,
+        )),
+        CommentedTokenTree::Tree(CommentedTree::Group(group)) => {
+            for item in group.token_stream.token_trees() {
+                collect_rewritable_comments(item, comments);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rewraps the `//`, `///` and `//!` comments of the formatted code that exceed the configured
+/// `comment_width`, and converts `/* */` comments to `//` comments when `normalize_comments` is
+/// set.
+///
+/// Only comments written on lines of their own are rewritten. Markdown code blocks within doc
+/// comments, and comments within the `skipped` ranges, are left as they are.
+pub(crate) fn rewrite_comments(
+    formatted_code: &mut FormattedCode,
+    config: &Comments,
+    skipped: &[Range<usize>],
+) -> Result<(), FormatterError> {
+    if !config.wrap_comments && !config.normalize_comments {
+        return Ok(());
+    }
+    let code: Arc<str> = Arc::from(formatted_code.as_str());
+    let mut comments = vec![];
+    for commented_token_tree in lex(&code)?.token_trees() {
+        collect_rewritable_comments(commented_token_tree, &mut comments);
+    }
+
+    let mut rewritten = String::with_capacity(code.len());
+    let mut last_end = 0;
+    let mut previous_doc_comment_end = None;
+    let mut in_code_block = false;
+    for (range, kind) in comments {
+        let line_start = code[..range.start].rfind('\n').map_or(0, |index| index + 1);
+        let indent = &code[line_start..range.start];
+        let is_own_line = indent.trim().is_empty()
+            && code[range.end..]
+                .split('\n')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .is_empty();
+        let comment = &code[range.clone()];
+
+        if kind == RewritableComment::Doc {
+            // Code blocks span consecutive doc comments only.
+            let is_consecutive = previous_doc_comment_end
+                .is_some_and(|end| code[end..range.start].trim().is_empty());
+            if !is_consecutive {
+                in_code_block = false;
+            }
+            previous_doc_comment_end = Some(range.end);
+            let is_fence = comment[3..].trim_start().starts_with("```");
+            if is_fence {
+                in_code_block = !in_code_block;
+            }
+            if in_code_block || is_fence {
+                continue;
+            }
+        }
+        if !is_own_line || is_within(skipped, &range) {
+            continue;
+        }
+
+        let lines = match kind {
+            RewritableComment::Block if config.normalize_comments => {
+                match block_comment_lines(comment) {
+                    Some(lines) => lines,
+                    None => continue,
+                }
+            }
+            RewritableComment::Line | RewritableComment::Doc => vec![comment.to_string()],
+            RewritableComment::Block => continue,
+        };
+        let lines = if config.wrap_comments {
+            lines
+                .into_iter()
+                .flat_map(|line| wrap_comment_line(&line, indent, config.comment_width))
+                .collect()
+        } else {
+            lines
+        };
+        rewritten.push_str(&code[last_end..range.start]);
+        rewritten.push_str(&lines.join(&format!("\n{indent}")));
+        last_end = range.end;
+    }
+    rewritten.push_str(&code[last_end..]);
+    *formatted_code = rewritten;
+
+    Ok(())
+}
+
+/// Converts a `/* */` comment to the lines of the equivalent `//` comment, or returns `None` if
+/// it is a doc comment or contains nested comments.
+fn block_comment_lines(comment: &str) -> Option<Vec<String>> {
+    if comment.starts_with("/**") || comment.starts_with("/*!") {
+        return None;
+    }
+    let content = comment.strip_prefix("/*")?.strip_suffix("*/")?;
+    if content.contains("/*") || content.contains("*/") {
+        return None;
+    }
+    let mut lines = content.lines().map(str::trim).collect::<Vec<_>>();
+    // Drop the lines holding only the opening and closing delimiters.
+    if lines.first().is_some_and(|line| line.is_empty()) {
+        lines.remove(0);
+    }
+    if lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    // Strip the leading `*` decorating the lines of the comment, if all of them have it.
+    if lines.len() > 1 && lines.iter().all(|line| line.starts_with('*')) {
+        lines = lines
+            .into_iter()
+            .map(|line| line.trim_start_matches('*').trim_start())
+            .collect();
+    }
+    Some(
+        lines
+            .into_iter()
+            .map(|line| {
+                if line.is_empty() {
+                    "//".to_string()
+                } else {
+                    format!("// {line}")
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Breaks a `//`, `///` or `//!` comment line into lines fitting within `comment_width`, including
+/// the indentation of the comment.
+fn wrap_comment_line(line: &str, indent: &str, comment_width: usize) -> Vec<String> {
+    if indent.chars().count() + line.chars().count() <= comment_width {
+        return vec![line.to_string()];
+    }
+    let prefix = ["///", "//!", "//"]
+        .into_iter()
+        .find(|prefix| line.starts_with(*prefix))
+        .unwrap_or_default();
+    let text = &line[prefix.len()..];
+    let body = text.trim_start();
+    let leading = &text[..text.len() - body.len()];
+    // Markdown headings and tables, and lines without spaces such as links, are not wrapped.
+    if body.starts_with('#') || body.starts_with('|') || !body.contains(' ') {
+        return vec![line.to_string()];
+    }
+    // Continuation lines of list items are aligned with the text of the item.
+    let list_marker_width = ["- ", "* ", "+ "]
+        .into_iter()
+        .find(|marker| body.starts_with(*marker))
+        .map_or(0, str::len);
+    let continuation = format!("{leading}{}", " ".repeat(list_marker_width));
+
+    let available = comment_width
+        .saturating_sub(indent.chars().count() + prefix.len() + leading.len())
+        .max(MIN_WRAPPED_COMMENT_WIDTH);
+    let mut lines: Vec<String> = vec![];
+    let mut current = String::new();
+    for word in body.split_whitespace() {
+        let width = if lines.is_empty() {
+            available
+        } else {
+            available.saturating_sub(list_marker_width)
+        };
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let leading = if index == 0 {
+                leading
+            } else {
+                continuation.as_str()
+            };
+            format!("{prefix}{leading}{text}")
+        })
+        .collect()
+}
+
+/// Formats the fenced `sway` code blocks of the `///` and `//!` doc comments of the formatted
+/// code, except for the doc comments within the `skipped` ranges. Code blocks that cannot be
+/// parsed are left as they are, and reported as warnings on the lines of the original source `src`.
+pub(crate) fn format_doc_comment_code(
+    formatted_code: &mut FormattedCode,
+    src: &str,
+    skipped: &[Range<usize>],
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    if !formatter.config.comments.format_code_in_doc_comments {
+        return Ok(());
+    }
+    let code: Arc<str> = Arc::from(formatted_code.as_str());
+    let mut rewritten = String::with_capacity(code.len());
+    let mut last_end = 0;
+    let mut unformattable = vec![];
+    for group in doc_comment_groups(&code)? {
+        let start = group[0].start;
+        let end = group[group.len() - 1].end;
+        if is_within(skipped, &(start..end)) {
+            continue;
+        }
+        let prefix = &code[start..start + 3];
+        let lines = group
+            .iter()
+            .map(|range| &code[range.start + 3..range.end])
+            .collect::<Vec<_>>();
+        let Some(lines) = format_doc_code_blocks(&lines, &formatter.config, &mut unformattable)
+        else {
+            continue;
+        };
+        let line_start = code[..start].rfind('\n').map_or(0, |index| index + 1);
+        let indent = &code[line_start..start];
+        rewritten.push_str(&code[last_end..start]);
+        rewritten.push_str(
+            &lines
+                .iter()
+                .map(|line| format!("{prefix}{line}"))
+                .collect::<Vec<_>>()
+                .join(&format!("\n{indent}")),
+        );
+        last_end = end;
+    }
+    rewritten.push_str(&code[last_end..]);
+    *formatted_code = rewritten;
+
+    if !unformattable.is_empty() {
+        // The unformattable code blocks are left as they are, so they are found in the same
+        // order within the doc comments of the source.
+        let mut src_blocks = sway_doc_code_blocks(src)?.into_iter();
+        for (snippet, err) in unformattable {
+            if let Some((line, _)) = src_blocks.find(|(_, src_snippet)| *src_snippet == snippet) {
+                formatter
+                    .warnings
+                    .push(FormatterWarning::UnformattableDocCodeBlock { line, err });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the ranges of the `///` and `//!` doc comments of the code, grouped by the comments
+/// written on consecutive lines.
+fn doc_comment_groups(code: &Arc<str>) -> Result<Vec<Vec<Range<usize>>>, FormatterError> {
+    let mut comments = vec![];
+    for commented_token_tree in lex(code)?.token_trees() {
+        collect_rewritable_comments(commented_token_tree, &mut comments);
+    }
+    let mut groups: Vec<Vec<Range<usize>>> = vec![];
+    for (range, kind) in comments {
+        if kind != RewritableComment::Doc {
+            continue;
+        }
+        match groups.last_mut() {
+            Some(group)
+                if code[group[group.len() - 1].end..range.start]
+                    .trim()
+                    .is_empty()
+                    && code[group[0].start..].starts_with(&code[range.start..range.start + 3]) =>
+            {
+                group.push(range)
+            }
+            _ => groups.push(vec![range]),
+        }
+    }
+    Ok(groups)
+}
+
+/// Returns the `sway` code blocks of the doc comments of the source, along with the line of their
+/// opening fence, starting from 1.
+fn sway_doc_code_blocks(src: &str) -> Result<Vec<(usize, String)>, FormatterError> {
+    let src: Arc<str> = Arc::from(src);
+    let mut blocks = vec![];
+    for group in doc_comment_groups(&src)? {
+        let lines = group
+            .iter()
+            .map(|range| &src[range.start + 3..range.end])
+            .collect::<Vec<_>>();
+        for block in doc_code_blocks(&lines) {
+            if let Some(snippet) = block.sway_snippet {
+                let line = src[..group[block.fence].start].matches('\n').count() + 1;
+                blocks.push((line, snippet));
+            }
+        }
+    }
+    Ok(blocks)
+}
+
+/// A fenced code block within the lines of a doc comment.
+struct DocCodeBlock {
+    /// The index of the line of the opening fence.
+    fence: usize,
+    /// The indices of the lines of code, between the fences.
+    lines: Range<usize>,
+    /// The code of a `sway` code block, with the indentation of its fence removed from its lines.
+    sway_snippet: Option<String>,
+}
+
+/// Returns the fenced code blocks within the lines of a doc comment.
+fn doc_code_blocks(lines: &[&str]) -> Vec<DocCodeBlock> {
+    let mut blocks = vec![];
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        let fence = line.trim_start();
+        let Some(tag) = fence.strip_prefix("```") else {
+            continue;
+        };
+        let Some(len) = lines[index..]
+            .iter()
+            .position(|line| line.trim_start().starts_with("```"))
+        else {
+            continue;
+        };
+        let sway_snippet = (tag.split(',').next().map(str::trim) == Some("sway")).then(|| {
+            // The lines of the code block are indented like its opening fence.
+            let leading = &line[..line.len() - fence.len()];
+            lines[index..index + len]
+                .iter()
+                .map(|line| line.strip_prefix(leading).unwrap_or(line.trim_start()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+        blocks.push(DocCodeBlock {
+            fence: index - 1,
+            lines: index..index + len,
+            sway_snippet,
+        });
+        index += len + 1;
+    }
+    blocks
+}
+
+/// Formats the `sway` code blocks within the lines of a doc comment, without their `///` or `//!`
+/// prefixes. Returns `None` if there is nothing to format.
+///
+/// The code blocks that cannot be formatted are pushed to `unformattable` along with the error.
+fn format_doc_code_blocks(
+    lines: &[&str],
+    config: &Config,
+    unformattable: &mut Vec<(String, String)>,
+) -> Option<Vec<String>> {
+    let mut formatted_lines = vec![];
+    let mut changed = false;
+    let mut next = 0;
+    for block in doc_code_blocks(lines) {
+        let Some(snippet) = block.sway_snippet else {
+            continue;
+        };
+        let fence = lines[block.fence];
+        let leading = &fence[..fence.len() - fence.trim_start().len()];
+        let code_lines = &lines[block.lines.clone()];
+        formatted_lines.extend(
+            lines[next..block.lines.start]
+                .iter()
+                .map(|line| line.to_string()),
+        );
+        match format_doc_code(&snippet, config) {
+            Ok(formatted) => {
+                let formatted_block = formatted
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            String::new()
+                        } else {
+                            format!("{leading}{line}")
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                changed |= formatted_block.iter().ne(code_lines.iter());
+                formatted_lines.extend(formatted_block);
+            }
+            Err(err) => {
+                unformattable.push((snippet, err.to_string()));
+                formatted_lines.extend(code_lines.iter().map(|line| line.to_string()));
+            }
+        }
+        next = block.lines.end;
+    }
+    formatted_lines.extend(lines[next..].iter().map(|line| line.to_string()));
+    changed.then_some(formatted_lines)
+}
+
+/// Formats a snippet of Sway code from a doc comment, which is either a whole module, a list of
+/// items, or the statements of a function body.
+fn format_doc_code(snippet: &str, config: &Config) -> Result<String, FormatterError> {
+    let mut formatter = Formatter {
+        config: config.clone(),
+        ..Default::default()
+    };
+    // Code blocks in the doc comments of the snippet itself are left as they are.
+    formatter.config.comments.format_code_in_doc_comments = false;
+
+    let is_module = ["library", "script", "contract", "predicate"]
+        .into_iter()
+        .any(|kind| {
+            snippet
+                .trim_start()
+                .strip_prefix(kind)
+                .is_some_and(|rest| rest.trim_start().starts_with(';'))
+        });
+    if is_module {
+        return Ok(formatter
+            .format(Arc::from(snippet), None)?
+            .trim_end()
+            .to_string());
+    }
+
+    // Snippets are most often a list of items, or statements to be run within a function.
+    let items = format!("library;\n\n{snippet}");
+    if let Ok(formatted) = formatter.format(Arc::from(items), None) {
+        return Ok(formatted
+            .trim_start_matches("library;")
+            .trim_matches('\n')
+            .to_string());
+    }
+    let statements = format!("library;\n\nfn main() {{\n{snippet}\n}}");
+    let formatted = formatter.format(Arc::from(statements), None)?;
+    formatter.indent();
+    let indent = formatter.indent_to_str()?;
+    let body = formatted
+        .lines()
+        .skip_while(|line| !line.starts_with("fn main()"))
+        .skip(1)
+        .collect::<Vec<_>>();
+    Ok(body[..body.len().saturating_sub(1)]
+        .iter()
+        .map(|line| line.strip_prefix(indent.as_ref()).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::map::byte_span::ByteSpan;
@@ -9,6 +9,8 @@ pub struct Comments {
     pub comment_width: usize,
     /// Convert /* */ comments to // comments where possible
     pub normalize_comments: bool,
+    /// Format the Sway code blocks within doc comments.
+    pub format_code_in_doc_comments: bool,
 }
 
 impl Default for Comments {
@@ -17,6 +19,7 @@ impl Default for Comments {
             wrap_comments: false,
             comment_width: DEFAULT_MAX_COMMENT_WIDTH,
             normalize_comments: false,
+            format_code_in_doc_comments: false,
         }
     }
 }
@@ -30,6 +33,9 @@ impl Comments {
             normalize_comments: opts
                 .normalize_comments
                 .unwrap_or(default.normalize_comments),
+            format_code_in_doc_comments: opts
+                .format_code_in_doc_comments
+                .unwrap_or(default.format_code_in_doc_comments),
         }
     }
 }
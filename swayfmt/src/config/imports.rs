@@ -32,9 +32,9 @@ pub enum GroupImports {
     #[default]
     Preserve,
     /// Discard existing groups, and create new groups for
-    ///  1. `std` / `core` / `alloc` imports
+    ///  1. `std` / `core` imports
     ///  2. other imports
-    ///  3. `self` / `crate` / `super` imports
+    ///  3. `::` / `self` / `crate` / `super` imports, and imports of submodules
     StdExternalCrate,
     /// Discard existing groups, and create a single group for everything
     One,
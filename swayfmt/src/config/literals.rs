@@ -4,10 +4,13 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Copy, Clone, Default)]
 pub struct Literals {
-    /// Format string literals where necessary.
+    /// Not supported: string literals can not be split without changing their value, so they are
+    /// always left as they are. Setting this option has no effect other than a warning.
     pub format_strings: bool,
-    /// Format hexadecimal integer literals.
+    /// Format hexadecimal integer and `b256` literals.
     pub hex_literal_case: HexLiteralCase,
+    /// Group the digits of long decimal integer literals by thousands with `_` separators.
+    pub digit_separators: bool,
 }
 
 impl Literals {
@@ -16,6 +19,7 @@ impl Literals {
         Self {
             format_strings: opts.format_strings.unwrap_or(default.format_strings),
             hex_literal_case: opts.hex_literal_case.unwrap_or(default.hex_literal_case),
+            digit_separators: opts.digit_separators.unwrap_or(default.digit_separators),
         }
     }
 }
@@ -3,7 +3,8 @@ use crate::config::user_opts::OrderingOptions;
 
 #[derive(Debug, Clone)]
 pub struct Ordering {
-    /// Reorder import and extern crate statements alphabetically.
+    /// Reorder the names imported within braces alphabetically, along with the `use` statements
+    /// when they are grouped or merged.
     pub reorder_imports: bool,
     /// Reorder module statements alphabetically in group.
     pub reorder_modules: bool,
@@ -46,6 +46,7 @@ pub struct ItemsOptions {
 pub struct LiteralsOptions {
     pub format_strings: Option<bool>,
     pub hex_literal_case: Option<HexLiteralCase>,
+    pub digit_separators: Option<bool>,
 }
 /// See parent struct [Expressions].
 #[derive(Serialize, Deserialize, Debug, Copy, Clone)]
@@ -80,4 +81,5 @@ pub struct CommentsOptions {
     pub wrap_comments: Option<bool>,
     pub comment_width: Option<usize>,
     pub normalize_comments: Option<bool>,
+    pub format_code_in_doc_comments: Option<bool>,
 }
@@ -40,6 +40,18 @@ pub const DEFAULT_INLINE_ATTR_WIDTH: usize = 0;
 
 /// Default max length of comments.
 pub const DEFAULT_MAX_COMMENT_WIDTH: usize = 80;
+/// The minimum width of the text of a wrapped comment line, regardless of its indentation.
+pub const MIN_WRAPPED_COMMENT_WIDTH: usize = 20;
+
+//LITERALS
+
+/// The minimum number of digits of a decimal literal for its digits to be grouped by `_` separators.
+pub const MIN_SEPARATED_DIGITS: usize = 6;
+
+//SKIP
+
+/// The comment marking the item or statement on the line below it to be left unformatted.
+pub const SKIP_COMMENT: &str = "// swayfmt-skip";
 
 //NEWLINE_STYLE
 
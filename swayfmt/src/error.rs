@@ -22,6 +22,15 @@ pub enum FormatterError {
     SyntaxError,
 }
 
+/// Issues found while formatting, that do not prevent the code from being formatted.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FormatterWarning {
+    #[error("Unable to format the Sway code block in the doc comment on line {line}: {err}")]
+    UnformattableDocCodeBlock { line: usize, err: String },
+    #[error("The `format_strings` option is not supported and has no effect, string literals are left as they are")]
+    FormatStringsUnsupported,
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("failed to parse config: {err}")]
@@ -1,16 +1,18 @@
 use self::shape::Shape;
-use crate::comments::{write_comments, CommentsContext};
+use crate::comments::{format_doc_comment_code, rewrite_comments, write_comments, CommentsContext};
+use crate::items::item_use::organize::organize_imports;
 use crate::parse::parse_file;
 use crate::utils::map::comments::CommentMap;
 use crate::utils::map::{newline::handle_newlines, newline_style::apply_newline_style};
 pub use crate::{
     config::manifest::Config,
-    error::{ConfigError, FormatterError},
+    error::{ConfigError, FormatterError, FormatterWarning},
 };
 use std::{borrow::Cow, fmt::Write, path::Path, sync::Arc};
 use sway_core::BuildConfig;
 use sway_types::{SourceEngine, Spanned};
 
+mod range;
 pub(crate) mod shape;
 
 #[derive(Debug, Default, Clone)]
@@ -19,6 +21,8 @@ pub struct Formatter {
     pub shape: Shape,
     pub config: Config,
     pub comments_context: CommentsContext,
+    /// The warnings emitted by the last call to [Formatter::format].
+    pub warnings: Vec<FormatterWarning>,
 }
 
 pub type FormattedCode = String;
@@ -85,6 +89,11 @@ impl Formatter {
         src: Arc<str>,
         build_config: Option<&BuildConfig>,
     ) -> Result<FormattedCode, FormatterError> {
+        self.warnings.clear();
+        if self.config.literals.format_strings {
+            self.warnings
+                .push(FormatterWarning::FormatStringsUnsupported);
+        }
         // apply the width heuristics settings from the `Config`
         self.shape.apply_width_heuristics(
             self.config
@@ -92,7 +101,13 @@ impl Formatter {
                 .heuristics_pref
                 .to_width_heuristics(self.config.whitespace.max_width),
         );
+        // The warnings refer to the lines of the source as it was given.
+        let original_src = src.clone();
         let src = src.trim();
+        // Group, merge and sort the `use` statements first, so that the comments and newlines
+        // are handled for the reorganized source.
+        let organized_src = organize_imports(&self.source_engine, src, &self.config)?;
+        let src = organized_src.as_deref().unwrap_or(src);
 
         let path = build_config.map(|build_config| build_config.canonical_root_module());
         // Formatted code will be pushed here with raw newline style.
@@ -125,6 +140,12 @@ impl Formatter {
             &mut formatted_code,
             self,
         )?;
+        // Format the code blocks of doc comments, leaving the code copied verbatim as it is
+        let skipped = self.comments_context.skipped_ranges(&formatted_code);
+        format_doc_comment_code(&mut formatted_code, &original_src, &skipped, self)?;
+        // Wrap and normalize comments
+        let skipped = self.comments_context.skipped_ranges(&formatted_code);
+        rewrite_comments(&mut formatted_code, &self.config.comments, &skipped)?;
         // Replace newlines with specified `NewlineStyle`
         apply_newline_style(
             self.config.whitespace.newline_style,
@@ -0,0 +1,276 @@
+//! Formatting of the items and statements overlapping a range of a file, leaving the rest of the
+//! file untouched.
+
+use super::{FormattedCode, Formatter};
+use crate::{error::FormatterError, parse::parse_file};
+use std::{ops::Range, sync::Arc};
+use sway_ast::{
+    attribute::Annotated, CodeBlockContents, ItemFn, ItemImplItem, ItemKind, Module, Statement,
+};
+use sway_core::BuildConfig;
+use sway_types::{Span, Spanned};
+
+/// An item or statement of a module, along with the items or statements nested within it that
+/// can be formatted on their own.
+struct Node {
+    span: Span,
+    /// The kind and name of the node, used to check that a formatted node is the one it replaces.
+    key: String,
+    children: Vec<Node>,
+}
+
+impl Formatter {
+    /// Formats the items or statements of `src` overlapping the given byte range.
+    ///
+    /// Returns the byte range of `src` to replace along with the formatted code replacing it, or
+    /// `None` if the range overlaps no items or statements. The range to replace spans from the
+    /// first to the last of the overlapping items, or of the overlapping statements if the range
+    /// lies within the body of a single function.
+    pub fn format_range(
+        &mut self,
+        src: Arc<str>,
+        range: Range<usize>,
+        build_config: Option<&BuildConfig>,
+    ) -> Result<Option<(Range<usize>, FormattedCode)>, FormatterError> {
+        let formatted_code = self.format(src.clone(), build_config)?;
+        let src_module = parse_file(&self.source_engine, src.clone(), None)?;
+        let formatted_module = parse_file(
+            &self.source_engine,
+            Arc::from(formatted_code.as_str()),
+            None,
+        )?;
+        let src_nodes = module_nodes(&src_module.value);
+        let formatted_nodes = module_nodes(&formatted_module.value);
+
+        let Some((path, selected)) = select_nodes(&src_nodes, &range) else {
+            return Ok(None);
+        };
+        // Formatting mostly leaves the items and statements of the file as they are, so the
+        // selected nodes are found at the same place among the formatted nodes. Should that not
+        // be the case, e.g. because imports were reordered, the whole file is replaced instead.
+        let (Some(src_level), Some(formatted_level)) = (
+            nodes_at(&src_nodes, &path),
+            nodes_at(&formatted_nodes, &path),
+        ) else {
+            return Ok(Some((0..src.len(), formatted_code)));
+        };
+        if src_level.len() != formatted_level.len()
+            || src_level[selected.clone()]
+                .iter()
+                .zip(&formatted_level[selected.clone()])
+                .any(|(src_node, formatted_node)| src_node.key != formatted_node.key)
+        {
+            return Ok(Some((0..src.len(), formatted_code)));
+        }
+
+        let mut src_start = src_level[selected.start].span.start();
+        let src_end = src_level[selected.end - 1].span.end();
+        let mut formatted_start = formatted_level[selected.start].span.start();
+        let formatted_end = formatted_level[selected.end - 1].span.end();
+        // Reindent the first line of the selected nodes if they start their line.
+        if let (Some(src_line_start), Some(formatted_line_start)) = (
+            indented_line_start(&src, src_start),
+            indented_line_start(&formatted_code, formatted_start),
+        ) {
+            src_start = src_line_start;
+            formatted_start = formatted_line_start;
+        }
+        Ok(Some((
+            src_start..src_end,
+            formatted_code[formatted_start..formatted_end].to_string(),
+        )))
+    }
+}
+
+fn module_nodes(module: &Module) -> Vec<Node> {
+    module
+        .items
+        .iter()
+        .map(|item| {
+            let children = match &item.value {
+                ItemKind::Fn(item_fn) => fn_nodes(item_fn),
+                ItemKind::Impl(item_impl) => item_impl
+                    .contents
+                    .get()
+                    .iter()
+                    .map(impl_item_node)
+                    .collect(),
+                _ => vec![],
+            };
+            Node {
+                span: item.span(),
+                key: item_key(&item.value),
+                children,
+            }
+        })
+        .collect()
+}
+
+fn item_key(item: &ItemKind) -> String {
+    match item {
+        ItemKind::Submodule(submodule) => format!("mod {}", submodule.name),
+        // Imports are only the same if they import the same items.
+        ItemKind::Use(item_use) => format!("use {}", without_whitespace(item_use.tree.span())),
+        ItemKind::Struct(item_struct) => format!("struct {}", item_struct.name),
+        ItemKind::Enum(item_enum) => format!("enum {}", item_enum.name),
+        ItemKind::Fn(item_fn) => format!("fn {}", item_fn.fn_signature.name),
+        ItemKind::Trait(item_trait) => format!("trait {}", item_trait.name),
+        ItemKind::Impl(item_impl) => format!("impl {}", without_whitespace(item_impl.ty.span())),
+        ItemKind::Abi(item_abi) => format!("abi {}", item_abi.name),
+        ItemKind::Const(item_const) => format!("const {}", item_const.name),
+        ItemKind::Storage(_) => "storage".to_string(),
+        ItemKind::Configurable(_) => "configurable".to_string(),
+        ItemKind::TypeAlias(item_type_alias) => format!("type {}", item_type_alias.name),
+        ItemKind::Error(..) => "error".to_string(),
+    }
+}
+
+fn without_whitespace(span: Span) -> String {
+    span.as_str().split_whitespace().collect()
+}
+
+fn impl_item_node(impl_item: &Annotated<ItemImplItem>) -> Node {
+    let span = match impl_item.attribute_list.first() {
+        Some(attribute) => Span::join(attribute.span(), &impl_item.value.span()),
+        None => impl_item.value.span(),
+    };
+    let (key, children) = match &impl_item.value {
+        ItemImplItem::Fn(item_fn) => (
+            format!("fn {}", item_fn.fn_signature.name),
+            fn_nodes(item_fn),
+        ),
+        ItemImplItem::Const(item_const) => (format!("const {}", item_const.name), vec![]),
+        ItemImplItem::Type(trait_type) => (format!("type {}", trait_type.name), vec![]),
+    };
+    Node {
+        span,
+        key,
+        children,
+    }
+}
+
+fn fn_nodes(item_fn: &ItemFn) -> Vec<Node> {
+    let CodeBlockContents {
+        statements,
+        final_expr_opt,
+        ..
+    } = item_fn.body.get();
+    statements
+        .iter()
+        .map(|statement| {
+            let key = match statement {
+                Statement::Let(_) => "let".to_string(),
+                Statement::Item(item) => item_key(&item.value),
+                Statement::Expr { .. } => "expr".to_string(),
+                Statement::Error(..) => "error".to_string(),
+            };
+            (statement.span(), key)
+        })
+        .chain(
+            final_expr_opt
+                .iter()
+                .map(|expr| (expr.span(), "expr".to_string())),
+        )
+        .map(|(span, key)| Node {
+            span,
+            key,
+            children: vec![],
+        })
+        .collect()
+}
+
+/// Returns the path of child indices to the innermost nodes overlapping the range, and the
+/// indices of those nodes.
+fn select_nodes(nodes: &[Node], range: &Range<usize>) -> Option<(Vec<usize>, Range<usize>)> {
+    let mut overlapping = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.span.start() < range.end && range.start < node.span.end())
+        .map(|(index, _)| index);
+    let first = overlapping.next()?;
+    let last = overlapping.next_back().unwrap_or(first);
+    let node = &nodes[first];
+    if first == last && node.span.start() <= range.start && range.end <= node.span.end() {
+        if let Some((mut path, selected)) = select_nodes(&node.children, range) {
+            path.insert(0, first);
+            return Some((path, selected));
+        }
+    }
+    Some((vec![], first..last + 1))
+}
+
+fn nodes_at<'a>(nodes: &'a [Node], path: &[usize]) -> Option<&'a [Node]> {
+    match path.split_first() {
+        Some((index, path)) => nodes_at(&nodes.get(*index)?.children, path),
+        None => Some(nodes),
+    }
+}
+
+/// Returns the start of the line of the given offset if only whitespace precedes it on its line.
+fn indented_line_start(text: &str, offset: usize) -> Option<usize> {
+    let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    text[line_start..offset]
+        .trim()
+        .is_empty()
+        .then_some(line_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::imports::GroupImports;
+
+    fn format_range(src: &str, range: Range<usize>) -> String {
+        let (replaced, code) = Formatter::default()
+            .format_range(Arc::from(src), range, None)
+            .unwrap()
+            .unwrap();
+        format!("{}{}{}", &src[..replaced.start], code, &src[replaced.end..])
+    }
+
+    #[test]
+    fn format_range_of_items() {
+        let src = "library;\n\nfn  foo( ) {}\n\nfn  bar( ) {}\n";
+        let start = src.find("fn  bar").unwrap();
+        assert_eq!(
+            format_range(src, start..start + 1),
+            "library;\n\nfn  foo( ) {}\n\nfn bar() {}\n"
+        );
+    }
+
+    #[test]
+    fn format_range_of_statements() {
+        let src = "library;\n\nfn foo() {\n    let  a=1;\n      let  b=2;\n}\n";
+        let start = src.find("let  b").unwrap();
+        assert_eq!(
+            format_range(src, start..start + 3),
+            "library;\n\nfn foo() {\n    let  a=1;\n    let b = 2;\n}\n"
+        );
+    }
+
+    #[test]
+    fn format_range_of_reordered_imports() {
+        let mut formatter = Formatter::default();
+        formatter.config.imports.group_imports = GroupImports::One;
+        let src = "library;\n\nuse std::option::Option;\nuse  std::hash::Hash;\n";
+        let start = src.find("use  std").unwrap();
+        let (replaced, code) = formatter
+            .format_range(Arc::from(src), start..start + 1, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(replaced, 0..src.len());
+        assert_eq!(
+            code,
+            "library;\n\nuse std::hash::Hash;\nuse std::option::Option;\n"
+        );
+    }
+
+    #[test]
+    fn format_range_outside_items() {
+        let src = "library;\n\nfn foo() {}\n";
+        assert!(Formatter::default()
+            .format_range(Arc::from(src), 9..10, None)
+            .unwrap()
+            .is_none());
+    }
+}
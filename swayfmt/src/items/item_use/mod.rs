@@ -15,6 +15,7 @@ use sway_types::{
     Spanned,
 };
 
+pub(crate) mod organize;
 #[cfg(test)]
 mod tests;
 
@@ -91,17 +92,19 @@ impl Format for UseTree {
 
                         ord_vec.push(buf);
                     }
-                    ord_vec.sort_by(|a, b| {
-                        if a == b {
-                            std::cmp::Ordering::Equal
-                        } else if a == "self" || b == "*" {
-                            std::cmp::Ordering::Less
-                        } else if b == "self" || a == "*" {
-                            std::cmp::Ordering::Greater
-                        } else {
-                            a.to_lowercase().cmp(&b.to_lowercase())
-                        }
-                    });
+                    if formatter.config.ordering.reorder_imports {
+                        ord_vec.sort_by(|a, b| {
+                            if a == b {
+                                std::cmp::Ordering::Equal
+                            } else if a == "self" || b == "*" {
+                                std::cmp::Ordering::Less
+                            } else if b == "self" || a == "*" {
+                                std::cmp::Ordering::Greater
+                            } else {
+                                a.to_lowercase().cmp(&b.to_lowercase())
+                            }
+                        });
+                    }
                     for (use_tree, comma) in ord_vec.iter_mut().zip(commas.iter()) {
                         write!(use_tree, "{}", comma.span().as_str())?;
                     }
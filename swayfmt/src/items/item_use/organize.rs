@@ -0,0 +1,432 @@
+//! Grouping, merging and sorting of the `use` statements of a module, as configured by the
+//! [Imports] and [Ordering] options.
+//!
+//! Imports are reorganized on the source, before it is formatted: each run of consecutive `use`
+//! statements is replaced with the reorganized statements, which are then formatted like any
+//! other item. Comments and attributes between statements end a run, and runs containing a
+//! statement with a comment within its `use` tree are left untouched, as the comment would be lost
+//! when the statement is merged or split.
+
+use crate::{
+    config::{
+        imports::{GroupImports, ImportGranularity, Imports},
+        manifest::Config,
+        ordering::Ordering,
+    },
+    error::FormatterError,
+    parse::parse_file,
+};
+use std::sync::Arc;
+use sway_ast::{ItemKind, ItemUse, Module, UseTree};
+use sway_types::{SourceEngine, Spanned};
+
+/// The kind of module an import refers to, used to group imports with
+/// [GroupImports::StdExternalCrate].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportKind {
+    /// Imports from `std` or `core`.
+    Std,
+    /// Imports from any other library.
+    External,
+    /// Imports from the current package.
+    Local,
+}
+
+/// The last element of an import path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Leaf {
+    /// `self`, or the name of the imported item.
+    Name(String),
+    /// `name as alias`.
+    Rename(String, String),
+    /// `*`.
+    Glob,
+}
+
+/// A single imported item, as found in a flattened `use` tree.
+#[derive(Debug, Clone)]
+struct ImportPath {
+    visibility: Option<String>,
+    root_import: bool,
+    segments: Vec<String>,
+    leaf: Leaf,
+}
+
+/// A `use` statement to write, along with the kind of module it imports from.
+#[derive(Debug)]
+struct UseStatement {
+    kind: ImportKind,
+    code: String,
+}
+
+/// The kind, visibility, root and path of the imports merged into the same `use` statement.
+type MergeKey = (ImportKind, Option<String>, bool, Vec<String>);
+
+/// Imports merged into a tree of modules.
+#[derive(Debug, Default)]
+struct ImportTree {
+    /// Whether the module itself is imported, i.e. `self`.
+    is_self: bool,
+    /// The renamed and glob imports of the module.
+    leaves: Vec<Leaf>,
+    /// The submodules and items of the module, in the order they were inserted.
+    children: Vec<(String, ImportTree)>,
+}
+
+impl ImportTree {
+    fn insert(&mut self, segments: &[String], leaf: &Leaf) {
+        match segments.split_first() {
+            Some((first, rest)) => self.child(first).insert(rest, leaf),
+            None => match leaf {
+                Leaf::Name(name) if name == "self" => self.is_self = true,
+                Leaf::Name(name) => self.child(name).is_self = true,
+                _ if self.leaves.contains(leaf) => {}
+                _ => self.leaves.push(leaf.clone()),
+            },
+        }
+    }
+
+    fn child(&mut self, name: &str) -> &mut ImportTree {
+        let index = match self.children.iter().position(|(child, _)| child == name) {
+            Some(index) => index,
+            None => {
+                self.children
+                    .push((name.to_string(), ImportTree::default()));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index].1
+    }
+
+    /// Returns the code of the imports of the tree, as they are written within braces.
+    fn items(&self) -> Vec<String> {
+        let mut items = vec![];
+        if self.is_self {
+            items.push("self".to_string());
+        }
+        for (name, child) in &self.children {
+            let child_items = child.items();
+            match child_items.as_slice() {
+                [item] if item == "self" => items.push(name.clone()),
+                [item] => items.push(format!("{name}::{item}")),
+                _ => items.push(format!("{name}::{{{}}}", child_items.join(", "))),
+            }
+        }
+        items.extend(self.leaves.iter().map(leaf_code));
+        items
+    }
+}
+
+/// Returns the source with its `use` statements grouped, merged and sorted as configured, or
+/// `None` if the imports are configured to be kept as they are.
+pub(crate) fn organize_imports(
+    source_engine: &SourceEngine,
+    src: &str,
+    config: &Config,
+) -> Result<Option<String>, FormatterError> {
+    let Imports {
+        group_imports,
+        imports_granularity,
+        ..
+    } = config.imports;
+    if matches!(group_imports, GroupImports::Preserve)
+        && matches!(imports_granularity, ImportGranularity::Preserve)
+    {
+        return Ok(None);
+    }
+    let module = parse_file(source_engine, Arc::from(src), None)?.value;
+    let local_modules = module
+        .submodules()
+        .map(|submodule| submodule.name.as_str().to_string())
+        .collect::<Vec<_>>();
+
+    let mut organized = String::with_capacity(src.len());
+    let mut end_of_previous_run = 0;
+    for run in use_runs(src, &module) {
+        let Some(code) = organize_run(src, &run, &local_modules, config) else {
+            continue;
+        };
+        let range = run.first().unwrap().span().start()..run.last().unwrap().span().end();
+        organized.push_str(&src[end_of_previous_run..range.start]);
+        organized.push_str(&code);
+        end_of_previous_run = range.end;
+    }
+    organized.push_str(&src[end_of_previous_run..]);
+    Ok(Some(organized))
+}
+
+/// Returns the runs of consecutive `use` statements of the module that are only separated by
+/// whitespace, so comments between statements end a run.
+fn use_runs<'a>(src: &str, module: &'a Module) -> Vec<Vec<&'a ItemUse>> {
+    let mut runs: Vec<Vec<&ItemUse>> = vec![];
+    let mut current: Vec<&ItemUse> = vec![];
+    for item in &module.items {
+        match &item.value {
+            ItemKind::Use(item_use) if item.attribute_list.is_empty() => {
+                let is_adjacent = match current.last() {
+                    Some(previous) => src[previous.span().end()..item_use.span().start()]
+                        .trim()
+                        .is_empty(),
+                    None => true,
+                };
+                if !is_adjacent {
+                    runs.push(std::mem::take(&mut current));
+                }
+                current.push(item_use);
+            }
+            _ => runs.push(std::mem::take(&mut current)),
+        }
+    }
+    runs.push(current);
+    runs.retain(|run| !run.is_empty());
+    runs
+}
+
+/// Returns the reorganized code of a run of `use` statements, or `None` if the statements can't be
+/// reorganized.
+fn organize_run(
+    src: &str,
+    run: &[&ItemUse],
+    local_modules: &[String],
+    config: &Config,
+) -> Option<String> {
+    // `use` trees don't contain string literals, so any comment marker starts a comment.
+    if run.iter().any(|item_use| {
+        let span = item_use.span();
+        let code = span.as_str();
+        code.contains("//") || code.contains("/*")
+    }) {
+        return None;
+    }
+
+    // Existing groups are the statements separated by blank lines.
+    let blocks: Vec<&[&ItemUse]> = match config.imports.group_imports {
+        GroupImports::Preserve => {
+            let mut blocks = vec![];
+            let mut start = 0;
+            for index in 1..run.len() {
+                let between = &src[run[index - 1].span().end()..run[index].span().start()];
+                if between.matches('\n').count() > 1 {
+                    blocks.push(&run[start..index]);
+                    start = index;
+                }
+            }
+            blocks.push(&run[start..]);
+            blocks
+        }
+        GroupImports::StdExternalCrate | GroupImports::One => vec![run],
+    };
+
+    let mut groups = vec![];
+    for block in blocks {
+        let mut statements = block_statements(block, local_modules, config.imports)?;
+        sort_statements(&mut statements, &config.ordering);
+        match config.imports.group_imports {
+            GroupImports::StdExternalCrate => {
+                for kind in [ImportKind::Std, ImportKind::External, ImportKind::Local] {
+                    let group = statements
+                        .iter()
+                        .filter(|statement| statement.kind == kind)
+                        .map(|statement| statement.code.clone())
+                        .collect::<Vec<_>>();
+                    if !group.is_empty() {
+                        groups.push(group);
+                    }
+                }
+            }
+            GroupImports::Preserve | GroupImports::One => groups.push(
+                statements
+                    .into_iter()
+                    .map(|statement| statement.code)
+                    .collect(),
+            ),
+        }
+    }
+    Some(
+        groups
+            .iter()
+            .map(|group| group.join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    )
+}
+
+/// Returns the statements importing the items of the block, merged or split to the configured
+/// granularity.
+fn block_statements(
+    block: &[&ItemUse],
+    local_modules: &[String],
+    imports: Imports,
+) -> Option<Vec<UseStatement>> {
+    let mut paths = vec![];
+    let mut statements = vec![];
+    for item_use in block {
+        let visibility = item_use
+            .visibility
+            .as_ref()
+            .map(|pub_token| pub_token.span().as_str().to_string());
+        let root_import = item_use.root_import.is_some();
+        let mut item_paths = vec![];
+        flatten_use_tree(
+            &item_use.tree,
+            &mut vec![],
+            &visibility,
+            root_import,
+            &mut item_paths,
+        )?;
+        if let ImportGranularity::Preserve = imports.imports_granularity {
+            let kind = item_paths.first().map_or(ImportKind::External, |path| {
+                import_kind(path, local_modules)
+            });
+            statements.push(UseStatement {
+                kind,
+                code: item_use.span().as_str().to_string(),
+            });
+        }
+        paths.extend(item_paths);
+    }
+    if let ImportGranularity::Preserve = imports.imports_granularity {
+        return Some(statements);
+    }
+
+    // Imports are only merged with imports of the same kind, visibility and root.
+    let mut merged: Vec<(MergeKey, ImportTree)> = vec![];
+    for path in paths {
+        let kind = match imports.group_imports {
+            GroupImports::StdExternalCrate => import_kind(&path, local_modules),
+            GroupImports::Preserve | GroupImports::One => ImportKind::External,
+        };
+        let merge_key = match imports.imports_granularity {
+            ImportGranularity::One => vec![],
+            ImportGranularity::Crate => vec![path
+                .segments
+                .first()
+                .cloned()
+                .unwrap_or_else(|| leaf_name(&path.leaf))],
+            ImportGranularity::Module if path.segments.is_empty() => {
+                vec![leaf_name(&path.leaf)]
+            }
+            ImportGranularity::Module => path.segments.clone(),
+            ImportGranularity::Item | ImportGranularity::Preserve => {
+                let mut key = path.segments.clone();
+                key.push(leaf_code(&path.leaf));
+                key
+            }
+        };
+        let key = (kind, path.visibility.clone(), path.root_import, merge_key);
+        let index = match merged.iter().position(|(existing, _)| existing == &key) {
+            Some(index) => index,
+            None => {
+                merged.push((key, ImportTree::default()));
+                merged.len() - 1
+            }
+        };
+        merged[index].1.insert(&path.segments, &path.leaf);
+    }
+    Some(
+        merged
+            .into_iter()
+            .map(|((kind, visibility, root_import, _), tree)| {
+                let items = tree.items();
+                let tree_code = match items.as_slice() {
+                    [item] => item.clone(),
+                    _ => format!("{{{}}}", items.join(", ")),
+                };
+                UseStatement {
+                    kind,
+                    code: format!(
+                        "{}use {}{};",
+                        visibility.map(|v| format!("{v} ")).unwrap_or_default(),
+                        if root_import { "::" } else { "" },
+                        tree_code
+                    ),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Collects the paths of all items imported by the `use` tree.
+fn flatten_use_tree(
+    tree: &UseTree,
+    segments: &mut Vec<String>,
+    visibility: &Option<String>,
+    root_import: bool,
+    paths: &mut Vec<ImportPath>,
+) -> Option<()> {
+    let leaf = match tree {
+        UseTree::Name { name } => Leaf::Name(name.as_str().to_string()),
+        UseTree::Rename { name, alias, .. } => {
+            Leaf::Rename(name.as_str().to_string(), alias.as_str().to_string())
+        }
+        UseTree::Glob { .. } => Leaf::Glob,
+        UseTree::Path { prefix, suffix, .. } => {
+            segments.push(prefix.as_str().to_string());
+            flatten_use_tree(suffix, segments, visibility, root_import, paths)?;
+            segments.pop();
+            return Some(());
+        }
+        UseTree::Group { imports } => {
+            for tree in imports.get() {
+                flatten_use_tree(tree, segments, visibility, root_import, paths)?;
+            }
+            return Some(());
+        }
+        UseTree::Error { .. } => return None,
+    };
+    paths.push(ImportPath {
+        visibility: visibility.clone(),
+        root_import,
+        segments: segments.clone(),
+        leaf,
+    });
+    Some(())
+}
+
+/// Returns the kind of module the path imports from.
+fn import_kind(path: &ImportPath, local_modules: &[String]) -> ImportKind {
+    let first = path
+        .segments
+        .first()
+        .cloned()
+        .unwrap_or_else(|| leaf_name(&path.leaf));
+    if path.root_import
+        || matches!(first.as_str(), "self" | "super" | "crate")
+        || local_modules.contains(&first)
+    {
+        ImportKind::Local
+    } else if matches!(first.as_str(), "std" | "core") {
+        ImportKind::Std
+    } else {
+        ImportKind::External
+    }
+}
+
+fn leaf_name(leaf: &Leaf) -> String {
+    match leaf {
+        Leaf::Name(name) | Leaf::Rename(name, _) => name.clone(),
+        Leaf::Glob => "*".to_string(),
+    }
+}
+
+fn leaf_code(leaf: &Leaf) -> String {
+    match leaf {
+        Leaf::Name(name) => name.clone(),
+        Leaf::Rename(name, alias) => format!("{name} as {alias}"),
+        Leaf::Glob => "*".to_string(),
+    }
+}
+
+/// Sorts the statements alphabetically by their path, if imports are to be reordered.
+fn sort_statements(statements: &mut [UseStatement], ordering: &Ordering) {
+    if !ordering.reorder_imports {
+        return;
+    }
+    let path = |statement: &UseStatement| -> String {
+        let code = statement.code.trim_start_matches("pub ");
+        code.trim_start_matches("use ")
+            .trim_end_matches(';')
+            .to_lowercase()
+    };
+    statements.sort_by_key(path);
+}
@@ -1,3 +1,5 @@
+use super::organize::organize_imports;
+use crate::config::{imports::ImportGranularity, manifest::Config};
 use forc_tracing::{println_green, println_red};
 use paste::paste;
 use prettydiff::{basic::DiffOp, diff_lines};
@@ -29,3 +31,21 @@ fmt_test_item!(single_line_sort  "use foo::{bar, baz, Quux::{a, b, C}};",
 fmt_test_item!(single_import_without_braces      "use std::tx::tx_id;",
           braced_single_import      "use std::tx::{tx_id};"
 );
+
+#[test]
+fn imports_with_comments_in_tree_are_not_reorganized() {
+    let mut config = Config::default();
+    config.imports.imports_granularity = ImportGranularity::Item;
+    let src = "library;
+
+use std::{
+    hash::Hash, // Hashing.
+    option::Option,
+};
+use foo::Bar;
+";
+    let organized = organize_imports(&sway_types::SourceEngine::default(), src, &config)
+        .unwrap()
+        .unwrap();
+    assert_eq!(organized, src);
+}
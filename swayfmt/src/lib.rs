@@ -14,5 +14,8 @@ mod module;
 pub mod parse;
 mod utils;
 
-pub use crate::formatter::{Format, Formatter};
-pub use error::FormatterError;
+pub use crate::{
+    constants::SWAY_FORMAT_FILE_NAME,
+    formatter::{Format, Formatter},
+};
+pub use error::{FormatterError, FormatterWarning};
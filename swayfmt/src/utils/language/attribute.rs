@@ -1,5 +1,5 @@
 use crate::{
-    comments::write_comments,
+    comments::{is_fmt_skipped, write_comments, write_skipped},
     constants::NEW_LINE,
     formatter::*,
     utils::{
@@ -12,15 +12,41 @@ use sway_ast::attribute::{Annotated, Attribute, AttributeArg, AttributeDecl, Att
 use sway_types::{
     ast::{Delimiter, PunctKind},
     constants::DOC_COMMENT_ATTRIBUTE_NAME,
-    Spanned,
+    Span, Spanned,
 };
 
+/// Extends the span up to the `;` following it, if any, as the spans of items such as constants
+/// do not include their terminating semicolon.
+fn with_terminating_semicolon(span: Span) -> Span {
+    let rest = &span.src()[span.end()..];
+    let after_whitespace = rest.trim_start();
+    if !after_whitespace.starts_with(';') {
+        return span;
+    }
+    let end = span.end() + rest.len() - after_whitespace.len() + 1;
+    Span::new(
+        span.src().clone(),
+        span.start(),
+        end,
+        span.source_id().cloned(),
+    )
+    .unwrap_or(span)
+}
+
 impl<T: Format + Spanned + std::fmt::Debug> Format for Annotated<T> {
     fn format(
         &self,
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
+        let span = match self.attribute_list.first() {
+            Some(attr) => Span::join(attr.span(), &self.value.span()),
+            None => self.value.span(),
+        };
+        if is_fmt_skipped(formatter, &span, &self.attribute_list) {
+            formatter.write_indent_into_buffer(formatted_code)?;
+            return write_skipped(formatted_code, &with_terminating_semicolon(span), formatter);
+        }
         // format each `Attribute`
         let mut start = None;
         for attr in &self.attribute_list {
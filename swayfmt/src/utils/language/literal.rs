@@ -1,4 +1,6 @@
 use crate::{
+    config::literals::{HexLiteralCase, Literals},
+    constants::MIN_SEPARATED_DIGITS,
     formatter::*,
     utils::map::byte_span::{ByteSpan, LeafSpans},
 };
@@ -9,14 +11,18 @@ impl Format for Literal {
     fn format(
         &self,
         formatted_code: &mut FormattedCode,
-        _formatter: &mut Formatter,
+        formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
         match self {
             // TODO: do more digging into `Literal` and see if there is more formatting to do.
             Self::String(lit_string) => write!(formatted_code, "{}", lit_string.span.as_str())?,
             Self::Char(lit_char) => write!(formatted_code, "{}", lit_char.span.as_str())?,
             Self::Int(lit_int) => {
-                write!(formatted_code, "{}", lit_int.span.as_str())?;
+                write!(
+                    formatted_code,
+                    "{}",
+                    format_int(lit_int.span.as_str(), &formatter.config.literals)
+                )?;
                 if let Some((_, ty_span)) = &lit_int.ty_opt {
                     write!(formatted_code, "{}", ty_span.as_str())?;
                 }
@@ -27,6 +33,36 @@ impl Format for Literal {
     }
 }
 
+/// Applies the configured `hex_literal_case` to hexadecimal integer and `b256` literals, and
+/// groups the digits of long decimal literals if `digit_separators` is set.
+fn format_int(lit_int: &str, config: &Literals) -> String {
+    if let Some(digits) = lit_int.strip_prefix("0x") {
+        let digits = match config.hex_literal_case {
+            HexLiteralCase::Preserve => digits.to_string(),
+            HexLiteralCase::Upper => digits.to_uppercase(),
+            HexLiteralCase::Lower => digits.to_lowercase(),
+        };
+        return format!("0x{digits}");
+    }
+    let is_decimal = !lit_int.starts_with("0o") && !lit_int.starts_with("0b");
+    // Literals already containing separators are left as written.
+    if !config.digit_separators
+        || !is_decimal
+        || lit_int.contains('_')
+        || lit_int.len() < MIN_SEPARATED_DIGITS
+    {
+        return lit_int.to_string();
+    }
+    // Digits are grouped by three from the least significant one.
+    let digits = lit_int.chars().collect::<Vec<_>>();
+    digits
+        .rchunks(3)
+        .rev()
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
 impl LeafSpans for Literal {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         match self {
@@ -1,4 +1,5 @@
 use crate::{
+    comments::{is_fmt_skipped, write_skipped},
     formatter::{shape::LineStyle, *},
     utils::map::byte_span::{ByteSpan, LeafSpans},
 };
@@ -74,6 +75,11 @@ fn format_statement(
     formatted_code: &mut FormattedCode,
     formatter: &mut Formatter,
 ) -> Result<(), FormatterError> {
+    // Items handle their own `#[fmt(skip)]` attributes and skip comments.
+    if !matches!(statement, Statement::Item(_)) && is_fmt_skipped(formatter, &statement.span(), &[])
+    {
+        return write_skipped(formatted_code, &statement.span(), formatter);
+    }
     match statement {
         Statement::Let(let_stmt) => let_stmt.format(formatted_code, formatter)?,
         Statement::Item(item) => item.format(formatted_code, formatter)?,
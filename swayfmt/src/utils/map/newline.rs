@@ -5,6 +5,7 @@ use sway_ast::Module;
 use sway_types::SourceEngine;
 
 use crate::{
+    comments::CommentsContext,
     constants::NEW_LINE,
     formatter::{FormattedCode, Formatter},
     parse::parse_file,
@@ -132,6 +133,7 @@ pub fn handle_newlines(
         formatted_code,
         unformatted_input,
         newline_threshold,
+        &formatter.comments_context,
     )?;
     Ok(())
 }
@@ -164,6 +166,7 @@ fn add_newlines(
     formatted_code: &mut FormattedCode,
     unformatted_code: Arc<str>,
     newline_threshold: usize,
+    comments_context: &CommentsContext,
 ) -> Result<(), FormatterError> {
     let mut unformatted_newline_spans = unformatted_module.leaf_spans();
     let mut formatted_newline_spans = formatted_module.leaf_spans();
@@ -192,7 +195,10 @@ fn add_newlines(
         .skip(1)
         .zip(formatted_newline_spans.iter().skip(1))
     {
-        if previous_unformatted_newline_span.end < unformatted_newline_span.start {
+        // Code copied verbatim already contains its newlines.
+        let is_skipped = comments_context
+            .is_skipped(&(previous_unformatted_newline_span.end..unformatted_newline_span.start));
+        if previous_unformatted_newline_span.end < unformatted_newline_span.start && !is_skipped {
             // At its core, the spaces between leaf spans are nothing more than just whitespace characters,
             // and sometimes comments, since they are not considered valid AST nodes. We are interested in
             // these spaces (with comments, if any)
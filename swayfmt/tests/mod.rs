@@ -1,6 +1,13 @@
 use indoc::indoc;
 use std::sync::Arc;
-use swayfmt::{config::user_def::FieldAlignment, Formatter};
+use swayfmt::{
+    config::{
+        imports::{GroupImports, ImportGranularity},
+        literals::HexLiteralCase,
+        user_def::FieldAlignment,
+    },
+    Formatter, FormatterWarning,
+};
 use test_macros::assert_eq_pretty;
 
 /// Takes a configured formatter as input and formats a given input and checks the actual output against an
@@ -3155,3 +3162,605 @@ fn retain_in_keyword() {
         "#},
     );
 }
+
+#[test]
+fn group_imports_std_external_crate() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.group_imports = GroupImports::StdExternalCrate;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use ::utils::helper;
+        use foo::Bar;
+        use std::hash::Hash;
+
+        use ::other::Thing;
+        use core::ops::Eq;
+        "#},
+        indoc! {r#"
+        library;
+
+        use core::ops::Eq;
+        use std::hash::Hash;
+
+        use foo::Bar;
+
+        use ::other::Thing;
+        use ::utils::helper;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn group_imports_one() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.group_imports = GroupImports::One;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use std::hash::Hash;
+
+        use foo::Bar;
+        "#},
+        indoc! {r#"
+        library;
+
+        use foo::Bar;
+        use std::hash::Hash;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn imports_granularity_crate() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.imports_granularity = ImportGranularity::Crate;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use std::hash::Hash;
+        use std::option::Option;
+        use foo::bar::Baz;
+        use std::hash::Hasher;
+        use std::option::Option::*;
+        "#},
+        indoc! {r#"
+        library;
+
+        use foo::bar::Baz;
+        use std::{hash::{Hash, Hasher}, option::Option::{self, *}};
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn imports_granularity_module() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.imports_granularity = ImportGranularity::Module;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use std::hash::Hash;
+        use std::option::Option;
+        use std::hash::Hasher;
+        "#},
+        indoc! {r#"
+        library;
+
+        use std::hash::{Hash, Hasher};
+        use std::option::Option;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn imports_granularity_item() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.imports_granularity = ImportGranularity::Item;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use std::{option::Option, hash::{Hasher, Hash}};
+        "#},
+        indoc! {r#"
+        library;
+
+        use std::hash::Hash;
+        use std::hash::Hasher;
+        use std::option::Option;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn imports_granularity_one() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.imports_granularity = ImportGranularity::One;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use std::hash::Hash;
+        use foo::Bar;
+        "#},
+        indoc! {r#"
+        library;
+
+        use {foo::Bar, std::hash::Hash};
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn imports_with_comments_are_not_reorganized() {
+    let mut formatter = Formatter::default();
+    formatter.config.imports.group_imports = GroupImports::StdExternalCrate;
+    formatter.config.imports.imports_granularity = ImportGranularity::Crate;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use foo::Bar;
+        // The standard library.
+        use std::hash::Hash;
+        "#},
+        indoc! {r#"
+        library;
+
+        use foo::Bar;
+        // The standard library.
+        use std::hash::Hash;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn imports_not_reordered() {
+    let mut formatter = Formatter::default();
+    formatter.config.ordering.reorder_imports = false;
+    formatter.config.imports.group_imports = GroupImports::StdExternalCrate;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        use std::option::Option;
+        use foo::{c, a, b};
+        use std::hash::Hash;
+        "#},
+        indoc! {r#"
+        library;
+
+        use std::option::Option;
+        use std::hash::Hash;
+
+        use foo::{c, a, b};
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn wrap_line_comments() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.wrap_comments = true;
+    formatter.config.comments.comment_width = 40;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        // This comment is far too long to fit within the configured comment width.
+        fn foo() {}
+        "#},
+        indoc! {r#"
+        library;
+
+        // This comment is far too long to fit
+        // within the configured comment width.
+        fn foo() {}
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn wrap_doc_comments() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.wrap_comments = true;
+    formatter.config.comments.comment_width = 40;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        struct Foo {
+            /// The number of items stored in the collection, not including removed ones.
+            count: u64,
+        }
+        "#},
+        indoc! {r#"
+        library;
+
+        struct Foo {
+            /// The number of items stored in
+            /// the collection, not including
+            /// removed ones.
+            count: u64,
+        }
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn wrap_doc_comments_skips_code_blocks() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.wrap_comments = true;
+    formatter.config.comments.comment_width = 40;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        /// Returns the answer, which is computed by a very expensive function.
+        ///
+        /// ```sway
+        /// let answer = answer(); // A comment inside the code block stays as it is.
+        /// ```
+        fn answer() -> u64 {
+            42
+        }
+        "#},
+        indoc! {r#"
+        library;
+
+        /// Returns the answer, which is
+        /// computed by a very expensive
+        /// function.
+        ///
+        /// ```sway
+        /// let answer = answer(); // A comment inside the code block stays as it is.
+        /// ```
+        fn answer() -> u64 {
+            42
+        }
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn wrap_comments_skips_skipped_items() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.wrap_comments = true;
+    formatter.config.comments.comment_width = 40;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        #[fmt(skip)]
+        fn foo() {
+            // This comment is far too long to fit within the configured comment width.
+            let   x   =   1;
+        }
+
+        /// This comment is far too long to fit within the configured comment width.
+        fn bar() {}
+        "#},
+        indoc! {r#"
+        library;
+
+        #[fmt(skip)]
+        fn foo() {
+            // This comment is far too long to fit within the configured comment width.
+            let   x   =   1;
+        }
+
+        /// This comment is far too long to fit
+        /// within the configured comment width.
+        fn bar() {}
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn normalize_block_comments() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.normalize_comments = true;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        /* A block comment. */
+        fn foo() {}
+
+        /*
+         * Multiple lines
+         * of text.
+         */
+        fn bar() {}
+        "#},
+        indoc! {r#"
+        library;
+
+        // A block comment.
+        fn foo() {}
+
+        // Multiple lines
+        // of text.
+        fn bar() {}
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn hex_literal_case_upper() {
+    let mut formatter = Formatter::default();
+    formatter.config.literals.hex_literal_case = HexLiteralCase::Upper;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        const A: u64 = 0xdeadBEEFu64;
+        const B: b256 = 0x00000000000000000000000000000000000000000000000000000000000000ff;
+        "#},
+        indoc! {r#"
+        library;
+
+        const A: u64 = 0xDEADBEEFu64;
+        const B: b256 = 0x00000000000000000000000000000000000000000000000000000000000000FF;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn hex_literal_case_lower() {
+    let mut formatter = Formatter::default();
+    formatter.config.literals.hex_literal_case = HexLiteralCase::Lower;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        const A: u64 = 0xdeadBEEF;
+        "#},
+        indoc! {r#"
+        library;
+
+        const A: u64 = 0xdeadbeef;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn digit_separators() {
+    let mut formatter = Formatter::default();
+    formatter.config.literals.digit_separators = true;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        const A: u64 = 1000000;
+        const B: u64 = 12345;
+        const C: u64 = 10_0000;
+        const D: u64 = 0b1010101010;
+        "#},
+        indoc! {r#"
+        library;
+
+        const A: u64 = 1_000_000;
+        const B: u64 = 12345;
+        const C: u64 = 10_0000;
+        const D: u64 = 0b1010101010;
+        "#},
+        &mut formatter,
+    );
+}
+
+#[test]
+fn skip_attribute() {
+    check(
+        indoc! {r#"
+        library;
+
+        #[fmt(skip)]
+        const TABLE: [u64; 4] = [
+            1,    2,
+            30,   40,
+        ];
+
+        fn  foo() {}
+        "#},
+        indoc! {r#"
+        library;
+
+        #[fmt(skip)]
+        const TABLE: [u64; 4] = [
+            1,    2,
+            30,   40,
+        ];
+
+        fn foo() {}
+        "#},
+    );
+}
+
+#[test]
+fn skip_comment() {
+    check(
+        indoc! {r#"
+        library;
+
+        // swayfmt-skip
+        fn   answer() -> u64 {
+            asm(r1: 42) { r1: u64 }
+        }
+
+        fn foo() {
+            // swayfmt-skip
+            let   x   =   1;
+            let   y   =   2;
+        }
+        "#},
+        indoc! {r#"
+        library;
+
+        // swayfmt-skip
+        fn   answer() -> u64 {
+            asm(r1: 42) { r1: u64 }
+        }
+
+        fn foo() {
+            // swayfmt-skip
+            let   x   =   1;
+            let y = 2;
+        }
+        "#},
+    );
+}
+
+#[test]
+fn format_code_in_doc_comments() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.format_code_in_doc_comments = true;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        /// Returns the sum of `a` and `b`.
+        ///
+        /// ```sway
+        /// let   x =   add(1,2);
+        /// ```
+        fn add(a: u64, b: u64) -> u64 {
+            a + b
+        }
+
+        /// ```sway
+        /// fn   foo() {   }
+        /// struct Foo { a: u64 }
+        /// ```
+        fn bar() {}
+        "#},
+        indoc! {r#"
+        library;
+
+        /// Returns the sum of `a` and `b`.
+        ///
+        /// ```sway
+        /// let x = add(1, 2);
+        /// ```
+        fn add(a: u64, b: u64) -> u64 {
+            a + b
+        }
+
+        /// ```sway
+        /// fn foo() {}
+        /// struct Foo {
+        ///     a: u64,
+        /// }
+        /// ```
+        fn bar() {}
+        "#},
+        &mut formatter,
+    );
+    assert!(formatter.warnings.is_empty());
+}
+
+#[test]
+fn unparsable_code_in_doc_comments() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.format_code_in_doc_comments = true;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        /// ```sway
+        /// let x = ;
+        /// ```
+        fn foo() {}
+        "#},
+        indoc! {r#"
+        library;
+
+        /// ```sway
+        /// let x = ;
+        /// ```
+        fn foo() {}
+        "#},
+        &mut formatter,
+    );
+    assert!(matches!(
+        formatter.warnings.as_slice(),
+        [FormatterWarning::UnformattableDocCodeBlock { line: 3, .. }]
+    ));
+}
+
+#[test]
+fn unparsable_code_in_doc_comments_line() {
+    let mut formatter = Formatter::default();
+    formatter.config.comments.format_code_in_doc_comments = true;
+
+    // Formatting removes the leading lines and separates the items, the warning refers to the
+    // line of the code block in the source.
+    let src = "\n\n\nlibrary;\nfn bar() {}\n/// ```sway\n/// let x = ;\n/// ```\nfn foo() {}\n";
+    Formatter::format(&mut formatter, Arc::from(src), None).unwrap();
+    assert!(matches!(
+        formatter.warnings.as_slice(),
+        [FormatterWarning::UnformattableDocCodeBlock { line: 6, .. }]
+    ));
+}
+
+#[test]
+fn format_strings_is_unsupported() {
+    let mut formatter = Formatter::default();
+    formatter.config.literals.format_strings = true;
+
+    check_with_formatter(
+        indoc! {r#"
+        library;
+
+        const GREETING: str = "This string literal is far too long to fit within the maximum width of a line.";
+        "#},
+        indoc! {r#"
+        library;
+
+        const GREETING: str = "This string literal is far too long to fit within the maximum width of a line.";
+        "#},
+        &mut formatter,
+    );
+    assert_eq!(
+        formatter.warnings,
+        [FormatterWarning::FormatStringsUnsupported]
+    );
+}